@@ -0,0 +1,24 @@
+// Build script - embeds the git commit for GET /api/version when building from a checkout
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    println!("cargo:rerun-if-env-changed=HOMELABME_GIT_SHA");
+
+    // An explicitly provided SHA (e.g. from CI or a Docker build arg) wins
+    if std::env::var("HOMELABME_GIT_SHA").is_ok_and(|sha| !sha.is_empty()) {
+        return;
+    }
+
+    let output = Command::new("git").args(["rev-parse", "--short=12", "HEAD"]).output();
+    if let Ok(output) = output
+        && output.status.success()
+    {
+        let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !sha.is_empty() {
+            println!("cargo:rustc-env=HOMELABME_GIT_SHA={}", sha);
+        }
+    }
+}
@@ -15,6 +15,28 @@ pub struct GreetingDto {
 pub struct CreateGreetingRequest {
     pub message: String,
     pub language: Option<String>,
+    // Return the existing greeting with the same message and language instead of adding a copy
+    #[serde(default)]
+    pub dedup: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListGreetingsQuery {
+    // RFC3339 timestamps, both exclusive
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenderGreetingRequest {
+    #[serde(default)]
+    pub vars: std::collections::HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenderGreetingResponse {
+    pub id: String,
+    pub rendered: String,
 }
 
 #[derive(Debug, Serialize)]
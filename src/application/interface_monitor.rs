@@ -0,0 +1,278 @@
+// Interface monitor - polls the network interfaces and broadcasts what changed between polls
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use crate::domain::network_entities::*;
+use crate::domain::network_repositories::EventHistoryRepository;
+use crate::domain::network_services::NetworkConfigService;
+
+// Number of past events kept around for clients resuming with Last-Event-ID
+const RECENT_EVENTS_CAPACITY: usize = 100;
+// Up/down transitions remembered per interface for flap counting
+const TRANSITION_HISTORY_CAPACITY: usize = 256;
+
+struct MonitorState {
+    previous: Option<HashMap<String, NetworkInterface>>,
+    next_id: u64,
+    recent: VecDeque<InterfaceEvent>,
+    transitions: HashMap<String, VecDeque<chrono::DateTime<chrono::Utc>>>,
+}
+
+pub struct InterfaceMonitor {
+    network_service: Arc<dyn NetworkConfigService>,
+    sender: broadcast::Sender<InterfaceEvent>,
+    state: Mutex<MonitorState>,
+    event_history: Option<Arc<dyn EventHistoryRepository>>,
+}
+
+impl InterfaceMonitor {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        let (sender, _) = broadcast::channel(RECENT_EVENTS_CAPACITY);
+        Self {
+            network_service,
+            sender,
+            state: Mutex::new(MonitorState {
+                previous: None,
+                next_id: 1,
+                recent: VecDeque::with_capacity(RECENT_EVENTS_CAPACITY),
+                transitions: HashMap::new(),
+            }),
+            event_history: None,
+        }
+    }
+
+    // Also append every event to the persisted history
+    pub fn with_event_history(mut self, event_history: Arc<dyn EventHistoryRepository>) -> Self {
+        self.event_history = Some(event_history);
+        self
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<InterfaceEvent> {
+        self.sender.subscribe()
+    }
+
+    // Events newer than `last_id` that are still held in the recent buffer
+    pub fn events_since(&self, last_id: u64) -> Vec<InterfaceEvent> {
+        let state = self.state.lock().unwrap();
+        state.recent.iter().filter(|event| event.id > last_id).cloned().collect()
+    }
+
+    // Up/down transitions of `interface_name` in the last `window` seconds, or None for an interface
+    // the monitor has never seen. Only the most recent transitions are kept, so very long windows
+    // on a badly flapping link are capped at the history size.
+    pub fn flaps(&self, interface_name: &str, window_secs: u64) -> Option<InterfaceFlaps> {
+        let state = self.state.lock().unwrap();
+        let history = state.transitions.get(interface_name);
+        let known = state
+            .previous
+            .as_ref()
+            .is_some_and(|previous| previous.contains_key(interface_name));
+        if !known && history.is_none() {
+            return None;
+        }
+
+        let since = chrono::Utc::now() - chrono::Duration::seconds(window_secs.min(i64::MAX as u64) as i64);
+        let transitions = history
+            .map(|history| history.iter().filter(|at| **at >= since).count())
+            .unwrap_or(0);
+        Some(InterfaceFlaps {
+            interface_name: interface_name.to_string(),
+            window_secs,
+            transitions,
+            last_transition_at: history.and_then(|history| history.back().copied()),
+        })
+    }
+
+    // Poll on an interval until the process exits
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.poll().await {
+                    tracing::warn!("Interface monitor poll failed: {}", e);
+                }
+            }
+        })
+    }
+
+    // Take one snapshot and broadcast the differences from the previous one; a tick where nothing
+    // changed broadcasts nothing. The first snapshot only establishes the baseline.
+    pub async fn poll(&self) -> Result<(), String> {
+        let interfaces = self.network_service.get_network_interfaces().await?;
+        let current: HashMap<String, NetworkInterface> = interfaces
+            .into_iter()
+            .map(|interface| (interface.name.clone(), interface))
+            .collect();
+
+        let events = self.record_snapshot(current, chrono::Utc::now());
+        if let Some(event_history) = &self.event_history {
+            for event in &events {
+                if let Err(e) = event_history.append(&HistoryEvent::from(event)).await {
+                    tracing::warn!("Failed to record interface event for {}: {}", event.interface_name, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Diff a snapshot against the previous one, remember link transitions and broadcast the changes
+    fn record_snapshot(
+        &self,
+        current: HashMap<String, NetworkInterface>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<InterfaceEvent> {
+        let events = {
+            let mut state = self.state.lock().unwrap();
+            let changes = match &state.previous {
+                Some(previous) => diff_snapshots(previous, &current),
+                None => Vec::new(),
+            };
+            if let Some(previous) = &state.previous {
+                let flapped: Vec<String> = current
+                    .values()
+                    .filter(|interface| {
+                        previous
+                            .get(&interface.name)
+                            .is_some_and(|before| before.is_up != interface.is_up)
+                    })
+                    .map(|interface| interface.name.clone())
+                    .collect();
+                for name in flapped {
+                    let history = state.transitions.entry(name).or_default();
+                    if history.len() == TRANSITION_HISTORY_CAPACITY {
+                        history.pop_front();
+                    }
+                    history.push_back(now);
+                }
+            }
+            state.previous = Some(current);
+
+            let mut events = Vec::with_capacity(changes.len());
+            for (kind, interface_name, interface) in changes {
+                let event = InterfaceEvent {
+                    id: state.next_id,
+                    kind,
+                    interface_name,
+                    interface,
+                    timestamp: now,
+                };
+                state.next_id += 1;
+                if state.recent.len() == RECENT_EVENTS_CAPACITY {
+                    state.recent.pop_front();
+                }
+                state.recent.push_back(event.clone());
+                events.push(event);
+            }
+            events
+        };
+
+        for event in &events {
+            // Sending only fails when nobody is subscribed, which is fine
+            let _ = self.sender.send(event.clone());
+        }
+        events
+    }
+}
+
+fn diff_snapshots(
+    previous: &HashMap<String, NetworkInterface>,
+    current: &HashMap<String, NetworkInterface>,
+) -> Vec<(InterfaceEventKind, String, Option<NetworkInterface>)> {
+    let mut changes = Vec::new();
+
+    for (name, interface) in current {
+        match previous.get(name) {
+            None => changes.push((InterfaceEventKind::Added, name.clone(), Some(interface.clone()))),
+            Some(before) if has_changed(before, interface) => {
+                changes.push((InterfaceEventKind::Changed, name.clone(), Some(interface.clone())))
+            }
+            Some(_) => {}
+        }
+    }
+    for name in previous.keys() {
+        if !current.contains_key(name) {
+            changes.push((InterfaceEventKind::Removed, name.clone(), None));
+        }
+    }
+
+    changes.sort_by(|a, b| a.1.cmp(&b.1));
+    changes
+}
+
+// Only link state and addresses count; counters, MACs and the like would turn every poll into an
+// event. Addresses are compared as sets since the order they are listed in is not meaningful.
+fn has_changed(before: &NetworkInterface, after: &NetworkInterface) -> bool {
+    before.is_up != after.is_up
+        || !same_addresses(&before.ipv4_addresses, &after.ipv4_addresses)
+        || !same_addresses(&before.ipv6_addresses, &after.ipv6_addresses)
+}
+
+fn same_addresses(before: &[String], after: &[String]) -> bool {
+    let before: HashSet<&String> = before.iter().collect();
+    let after: HashSet<&String> = after.iter().collect();
+    before == after
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::simulation::SimulationFixture;
+    use crate::test_support::TestNetwork;
+
+    fn monitor() -> InterfaceMonitor {
+        InterfaceMonitor::new(Arc::new(TestNetwork::new().service()))
+    }
+
+    // The simulated interfaces, with eth0 up or down
+    fn snapshot(eth0_up: bool) -> HashMap<String, NetworkInterface> {
+        SimulationFixture::default()
+            .interfaces
+            .into_iter()
+            .map(|mut interface| {
+                if interface.name == "eth0" {
+                    interface.is_up = eth0_up;
+                }
+                (interface.name.clone(), interface)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flaps_count_transitions_inside_the_window() {
+        let monitor = monitor();
+        let now = chrono::Utc::now();
+        let ago = |secs| now - chrono::Duration::seconds(secs);
+        for (at, eth0_up) in [(ago(400), true), (ago(350), false), (ago(200), true), (ago(100), false), (ago(10), true)] {
+            monitor.record_snapshot(snapshot(eth0_up), at);
+        }
+
+        let flaps = monitor.flaps("eth0", 300).unwrap();
+        assert_eq!(flaps.transitions, 3);
+        assert_eq!(flaps.last_transition_at, Some(ago(10)));
+        assert_eq!(monitor.flaps("eth0", 3600).unwrap().transitions, 4);
+        // Seen but never changed, and never seen at all
+        assert_eq!(monitor.flaps("wlan0", 300).unwrap().transitions, 0);
+        assert!(monitor.flaps("eth9", 300).is_none());
+    }
+
+    #[test]
+    fn only_real_changes_emit_events() {
+        let monitor = monitor();
+        let now = chrono::Utc::now();
+        assert!(monitor.record_snapshot(snapshot(true), now).is_empty());
+        assert!(monitor.record_snapshot(snapshot(true), now).is_empty());
+
+        // The same addresses listed in another order are not a change
+        let mut reordered = snapshot(true);
+        let eth0 = reordered.get_mut("eth0").unwrap();
+        eth0.ipv4_addresses = vec!["192.168.1.50".to_string(), "10.0.0.50".to_string()];
+        monitor.record_snapshot(reordered.clone(), now);
+        reordered.get_mut("eth0").unwrap().ipv4_addresses.reverse();
+        assert!(monitor.record_snapshot(reordered, now).is_empty());
+
+        assert_eq!(monitor.record_snapshot(snapshot(false), now).len(), 1);
+    }
+}
@@ -4,4 +4,10 @@
 pub mod use_cases;
 pub mod dto;
 pub mod network_dto;
-pub mod network_use_cases;
\ No newline at end of file
+pub mod network_use_cases;
+pub mod netplan;
+pub mod interface_monitor;
+pub mod static_ip_safe_mode;
+pub mod wifi_scan_cache;
+pub mod system_dto;
+pub mod system_use_cases;
\ No newline at end of file
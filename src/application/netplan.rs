@@ -0,0 +1,139 @@
+// Netplan export - renders stored configs as a netplan YAML document for /etc/netplan/.
+// WiFi passwords are never exported; a placeholder marks where to fill them in.
+
+use crate::domain::network_entities::*;
+
+pub const PASSWORD_PLACEHOLDER: &str = "REPLACE_WITH_WIFI_PASSWORD";
+
+// Enabled static IP configs become ethernet stanzas, except on `wifi_interface`, whose
+// addressing goes into the wifis stanza next to the active WiFi network
+pub fn render_netplan(
+    static_ip_configs: &[StaticIpConfig],
+    active_wifi: Option<&WifiConfig>,
+    wifi_interface: &str,
+) -> Result<String, String> {
+    let mut enabled: Vec<&StaticIpConfig> = static_ip_configs.iter().filter(|config| config.is_enabled).collect();
+    enabled.sort_by(|a, b| a.interface_name.cmp(&b.interface_name));
+    let wifi_static = enabled.iter().find(|config| config.interface_name == wifi_interface).copied();
+    let ethernets: Vec<&StaticIpConfig> = enabled
+        .iter()
+        .filter(|config| config.interface_name != wifi_interface)
+        .copied()
+        .collect();
+
+    let mut yaml = String::from("# Generated by homelabme\nnetwork:\n  version: 2\n  renderer: networkd\n");
+
+    if !ethernets.is_empty() {
+        yaml.push_str("  ethernets:\n");
+        for config in ethernets {
+            yaml.push_str(&format!("    {}:\n", config.interface_name));
+            render_addressing(&mut yaml, Some(config));
+        }
+    }
+
+    if active_wifi.is_some() || wifi_static.is_some() {
+        yaml.push_str("  wifis:\n");
+        yaml.push_str(&format!("    {}:\n", wifi_interface));
+        render_addressing(&mut yaml, wifi_static);
+        match active_wifi {
+            Some(wifi) => {
+                yaml.push_str("      access-points:\n");
+                yaml.push_str(&format!("        {}:\n", quote(&wifi.ssid)));
+                render_access_point(&mut yaml, wifi);
+            }
+            None => yaml.push_str("      # No active WiFi config; add access-points before applying\n"),
+        }
+    }
+
+    Ok(yaml)
+}
+
+// Static addressing for one interface, or DHCP when there is no enabled static config
+fn render_addressing(yaml: &mut String, config: Option<&StaticIpConfig>) {
+    let Some(config) = config else {
+        yaml.push_str("      dhcp4: true\n");
+        return;
+    };
+
+    yaml.push_str("      dhcp4: false\n");
+    yaml.push_str(&format!("      addresses:\n        - {}/{}\n", config.ip_address, config.prefix_len));
+    yaml.push_str(&format!("      routes:\n        - to: default\n          via: {}\n", config.gateway));
+    yaml.push_str("      nameservers:\n");
+    let dns_servers: Vec<String> = config.dns_servers().iter().map(|server| server.to_string()).collect();
+    yaml.push_str(&format!("        addresses: [{}]\n", dns_servers.join(", ")));
+    if !config.search_domains.is_empty() {
+        yaml.push_str(&format!("        search: [{}]\n", config.search_domains.join(", ")));
+    }
+}
+
+fn render_access_point(yaml: &mut String, wifi: &WifiConfig) {
+    if wifi.hidden {
+        yaml.push_str("          hidden: true\n");
+    }
+    match wifi.security_type {
+        WifiSecurityType::Open => yaml.push_str("          auth:\n            key-management: none\n"),
+        // netplan has no WEP support
+        WifiSecurityType::WEP => yaml.push_str("          # WEP is not supported by netplan\n"),
+        WifiSecurityType::WPA | WifiSecurityType::WPA2 => {
+            yaml.push_str("          auth:\n            key-management: psk\n");
+            yaml.push_str(&format!("            password: {}\n", quote(PASSWORD_PLACEHOLDER)));
+        }
+        WifiSecurityType::WPA3 => {
+            yaml.push_str("          auth:\n            key-management: sae\n");
+            yaml.push_str(&format!("            password: {}\n", quote(PASSWORD_PLACEHOLDER)));
+        }
+    }
+}
+
+// Double-quoted YAML scalar, so SSIDs with ':' '#' or leading spaces survive
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled(interface_name: &str, ip_address: &str, subnet_mask: &str, gateway: &str) -> StaticIpConfig {
+        let mut config =
+            StaticIpConfig::new(interface_name.to_string(), ip_address, subnet_mask, gateway, "1.1.1.1", None, Vec::new()).unwrap();
+        config.is_enabled = true;
+        config
+    }
+
+    #[test]
+    fn enabled_configs_render_with_their_addresses() {
+        let eth0 = enabled("eth0", "192.168.1.60", "255.255.255.0", "192.168.1.1");
+        let wlan0 = enabled("wlan0", "10.0.0.5", "255.255.0.0", "10.0.0.1");
+        let wifi = WifiConfig::new("Home: 5G".to_string(), "correct horse".to_string(), WifiSecurityType::WPA2, false);
+
+        let yaml = render_netplan(&[eth0, wlan0], Some(&wifi), "wlan0").unwrap();
+        assert!(yaml.contains("  ethernets:\n    eth0:\n      dhcp4: false\n      addresses:\n        - 192.168.1.60/24\n"), "{}", yaml);
+        assert!(yaml.contains("  wifis:\n    wlan0:\n      dhcp4: false\n      addresses:\n        - 10.0.0.5/16\n"), "{}", yaml);
+        assert!(yaml.contains("        \"Home: 5G\":\n"), "{}", yaml);
+        assert!(yaml.contains(PASSWORD_PLACEHOLDER));
+        assert!(!yaml.contains("correct horse"));
+    }
+
+    #[test]
+    fn disabled_configs_are_left_out() {
+        let mut eth0 = enabled("eth0", "192.168.1.60", "255.255.255.0", "192.168.1.1");
+        eth0.is_enabled = false;
+        let yaml = render_netplan(&[eth0], None, "wlan0").unwrap();
+        assert!(!yaml.contains("eth0"), "{}", yaml);
+        assert!(!yaml.contains("wifis"), "{}", yaml);
+    }
+}
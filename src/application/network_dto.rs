@@ -2,14 +2,28 @@
 
 use serde::{Deserialize, Serialize};
 use crate::domain::network_entities::*;
+use crate::domain::validation::validate_interface_name;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WifiConfigDto {
     pub id: String,
     pub ssid: String,
+    // Never filled when serializing a stored config; only read back when importing
+    #[serde(default, skip_serializing)]
+    pub password: Option<String>,
     pub security_type: WifiSecurityType,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default = "default_auto_connect")]
+    pub auto_connect: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_band: Option<WifiBand>,
     pub is_active: bool,
     pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,16 +35,40 @@ pub struct StaticIpConfigDto {
     pub gateway: String,
     pub dns_primary: String,
     pub dns_secondary: Option<String>,
+    #[serde(default)]
+    pub search_domains: Vec<String>,
     pub is_enabled: bool,
+    #[serde(default)]
+    pub enabled_at: Option<String>,
+    #[serde(default)]
+    pub disabled_at: Option<String>,
     pub created_at: String,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NetworkInterfaceDto {
     pub name: String,
     pub interface_type: InterfaceType,
+    // Stable key ("ethernet", "wireless", "loopback", "bridge", "other") for picking icons
+    pub category: String,
+    pub display_name: String,
     pub mac_address: String,
     pub is_up: bool,
+    // Link detected; null when unknown, e.g. while the interface is down
+    pub has_carrier: Option<bool>,
+    pub ipv4_addresses: Vec<String>,
+    pub ipv6_addresses: Vec<String>,
+    // Addresses with their prefix length, e.g. 192.168.1.10/24
+    #[serde(default)]
+    pub ipv4_cidrs: Vec<String>,
+    #[serde(default)]
+    pub ipv6_cidrs: Vec<String>,
     pub current_ip: Option<String>,
 }
 
@@ -38,26 +76,318 @@ pub struct NetworkInterfaceDto {
 pub struct ScannedWifiNetworkDto {
     pub ssid: String,
     pub mac: String,
-    pub signal_level: String,
-    pub channel: String,
+    // dBm; null when the scanner's value could not be read
+    pub signal_level: Option<i32>,
+    pub signal_raw: String,
+    // null when the scanner reported no usable channel or frequency
+    pub channel: Option<u16>,
+    pub band: Option<WifiBand>,
+    pub channel_raw: String,
     pub security: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WifiScanResultDto {
+    pub scanned_at: String,
+    pub duration_ms: u64,
+    // True when the scan ran out of time and these are the networks of the last finished scan,
+    // which `scanned_at` then refers to
+    pub cached: bool,
+    pub networks: Vec<ScannedWifiNetworkDto>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScanWifiNetworksQuery {
+    // Response version: 2 wraps the networks with scan metadata, anything else keeps the bare array
+    #[serde(default)]
+    pub v: Option<u32>,
+    // Drop networks weaker than this many dBm, and those whose signal could not be read
+    #[serde(default)]
+    pub min_signal: Option<i32>,
+    // Drop networks that do not broadcast a name
+    #[serde(default)]
+    pub hide_hidden: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ScanWifiNetworksView {
+    Networks(Vec<ScannedWifiNetworkDto>),
+    Result(WifiScanResultDto),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InterfaceAdminStateDto {
+    pub interface_name: String,
+    pub desired_up: bool,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InterfaceEventDto {
+    pub id: u64,
+    pub kind: InterfaceEventKind,
+    pub interface_name: String,
+    pub interface: Option<NetworkInterfaceDto>,
+    pub timestamp: String,
+}
+
+// Raw byte counters since the interface came up
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InterfaceStatsDto {
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InterfaceRateDto {
+    pub interface_name: String,
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    pub interval_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InterfaceFlapsDto {
+    pub interface_name: String,
+    pub window_secs: u64,
+    pub transitions: usize,
+    pub last_transition_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventHistoryQuery {
+    // RFC 3339 timestamp; only events at or after it are returned
+    pub since: Option<String>,
+    // Comma-separated event types, e.g. "interface.changed,wifi.activated"
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoryEventDto {
+    #[serde(rename = "type")]
+    pub event_type: HistoryEventType,
+    pub subject: String,
+    pub detail: Option<String>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventHistoryResponse {
+    pub events: Vec<HistoryEventDto>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InterfaceFlapsQuery {
+    // Seconds to look back; defaults to five minutes
+    pub window: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DefaultRouteDto {
+    pub gateway: Option<String>,
+    pub interface: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WifiLinkStatusDto {
+    pub connected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bssid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freq_mhz: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal_dbm: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_bitrate: Option<String>,
+}
+
+// One reading of the live signal poll; only `connected` is sent once the link is gone
+#[derive(Debug, Serialize)]
+pub struct WifiSignalSampleDto {
+    pub connected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dbm: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality_percent: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateWifiConfigRequest {
+    // Optional caller-chosen id (409 if it is already taken)
+    #[serde(default)]
+    pub id: Option<String>,
     pub ssid: String,
     pub password: String,
     pub security_type: WifiSecurityType,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default = "default_auto_connect")]
+    pub auto_connect: bool,
+    // "2.4GHz", "5GHz" or "6GHz"; anything else is rejected
+    #[serde(default)]
+    pub preferred_band: Option<WifiBand>,
+    // Store `ssid` exactly as sent instead of trimming surrounding whitespace
+    #[serde(default)]
+    pub exact_ssid: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetWifiAutoConnectRequest {
+    pub auto_connect: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SwitchWifiRequest {
+    pub to_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SwitchWifiResponse {
+    pub config: WifiConfigDto,
+    // The new network was seen associated before it was marked active
+    pub verified: bool,
+    #[serde(flatten)]
+    pub result: ApplyResultResponse,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CreateStaticIpConfigRequest {
+    // Optional caller-chosen id (409 if it is already taken)
+    #[serde(default)]
+    pub id: Option<String>,
     pub interface_name: String,
     pub ip_address: String,
-    pub subnet_mask: String,
+    // At least one of subnet_mask / prefix_len; if both are sent they must agree
+    #[serde(default)]
+    pub subnet_mask: Option<String>,
+    #[serde(default)]
+    pub prefix_len: Option<u8>,
     pub gateway: String,
     pub dns_primary: String,
     pub dns_secondary: Option<String>,
+    #[serde(default)]
+    pub search_domains: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderDnsRequest {
+    pub dns_servers: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplaceDnsRequest {
+    pub dns_servers: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NetworkProfileDto {
+    pub id: String,
+    pub name: String,
+    pub wifi_config_id: String,
+    pub static_ip_config_id: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateNetworkProfileRequest {
+    pub name: String,
+    pub wifi_config_id: String,
+    #[serde(default)]
+    pub static_ip_config_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkProfileResponse {
+    pub profile: NetworkProfileDto,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkProfilesListResponse {
+    pub profiles: Vec<NetworkProfileDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivateProfileResponse {
+    pub profile: NetworkProfileDto,
+    pub wifi: ApplyResultResponse,
+    pub static_ip: Option<ApplyResultResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProbeIpRequest {
+    pub interface_name: String,
+    pub ip_address: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProbeIpResponse {
+    pub interface_name: String,
+    pub ip_address: String,
+    pub in_use: bool,
+    pub method: String,
+}
+
+// Checklist for a static IP config that has not been saved yet; `ok` only when every check passed
+#[derive(Debug, Serialize)]
+pub struct StaticIpPrecheckResponse {
+    pub ok: bool,
+    pub checks: Vec<PrecheckItemDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrecheckItemDto {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InterfaceListQuery {
+    // Loopback interfaces are left out unless this is set
+    #[serde(default)]
+    pub include_loopback: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchCreateQuery {
+    // Save every item or none of them; without it each valid item is saved on its own
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EnableStaticIpQuery {
+    // Disable another config already enabled on the same interface instead of refusing
+    #[serde(default)]
+    pub takeover: bool,
+    // Enable even if the subnet overlaps an enabled config on another interface
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteWifiConfigQuery {
+    // Required to delete the active config
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListWifiConfigsQuery {
+    // Comma-separated security types, e.g. "Open,WEP"
+    pub security: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetInterfaceAdminStateRequest {
+    pub up: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -65,6 +395,32 @@ pub struct WifiConfigResponse {
     pub config: WifiConfigDto,
 }
 
+// Admin tooling view of a saved network, including its password
+#[derive(Debug, Serialize)]
+pub struct WifiConfigWithSecretDto {
+    #[serde(flatten)]
+    pub config: WifiConfigDto,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum WifiConfigView {
+    Plain(WifiConfigDto),
+    WithSecret(WifiConfigWithSecretDto),
+}
+
+#[derive(Debug, Serialize)]
+pub struct WifiConfigDetailResponse {
+    pub config: WifiConfigView,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetWifiConfigQuery {
+    #[serde(default)]
+    pub reveal_secret: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct WifiConfigsListResponse {
     pub configs: Vec<WifiConfigDto>,
@@ -73,11 +429,21 @@ pub struct WifiConfigsListResponse {
 #[derive(Debug, Serialize)]
 pub struct StaticIpConfigResponse {
     pub config: StaticIpConfigDto,
+    // Non-fatal problems worth showing, e.g. the interface is not ready to use the address yet
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct StaticIpConfigsListResponse {
-    pub configs: Vec<StaticIpConfigDto>,
+    pub configs: Vec<StaticIpConfigListItemDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StaticIpConfigListItemDto {
+    #[serde(flatten)]
+    pub config: StaticIpConfigDto,
+    // Whether the config's interface exists on the system right now
+    pub interface_present: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -85,12 +451,311 @@ pub struct NetworkInterfacesResponse {
     pub interfaces: Vec<NetworkInterfaceDto>,
 }
 
+// Interfaces split into one list per type, in discovery order within each
+#[derive(Debug, Default, Serialize)]
+pub struct GroupedNetworkInterfacesResponse {
+    pub ethernet: Vec<NetworkInterfaceDto>,
+    pub wireless: Vec<NetworkInterfaceDto>,
+    pub loopback: Vec<NetworkInterfaceDto>,
+    pub bridge: Vec<NetworkInterfaceDto>,
+    pub other: Vec<NetworkInterfaceDto>,
+}
+
+impl From<Vec<NetworkInterface>> for GroupedNetworkInterfacesResponse {
+    fn from(interfaces: Vec<NetworkInterface>) -> Self {
+        let mut grouped = Self::default();
+        for interface in interfaces {
+            let group = match interface.interface_type {
+                InterfaceType::Ethernet => &mut grouped.ethernet,
+                InterfaceType::Wireless => &mut grouped.wireless,
+                InterfaceType::Loopback => &mut grouped.loopback,
+                InterfaceType::Bridge => &mut grouped.bridge,
+                InterfaceType::Other => &mut grouped.other,
+            };
+            group.push(interface.into());
+        }
+        grouped
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DnsLatencyQuery {
+    // Name to resolve; example.com when absent
+    pub hostname: Option<String>,
+    // Queries per server; 3 when absent
+    pub samples: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DnsLatencyResponse {
+    pub hostname: String,
+    pub samples: usize,
+    // Where the servers came from: "static_ip" (enabled configs) or "system" (resolv.conf)
+    pub source: String,
+    pub servers: Vec<DnsServerLatencyDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DnsServerLatencyDto {
+    pub server: String,
+    pub answered: usize,
+    pub failed: usize,
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl From<DnsLatency> for DnsServerLatencyDto {
+    fn from(latency: DnsLatency) -> Self {
+        // Microsecond precision is plenty for network round trips
+        let millis = |duration: std::time::Duration| (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0;
+        Self {
+            server: latency.server.to_string(),
+            answered: latency.answered,
+            failed: latency.failed,
+            min_ms: latency.min.map(millis),
+            avg_ms: latency.avg.map(millis),
+            max_ms: latency.max.map(millis),
+            last_error: latency.last_error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddressConflictsResponse {
+    pub conflicts: Vec<AddressConflictDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddressConflictDto {
+    pub kind: AddressConflictKind,
+    pub address: String,
+    pub interfaces: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub static_ip_config_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_interface: Option<String>,
+}
+
+impl From<AddressConflict> for AddressConflictDto {
+    fn from(conflict: AddressConflict) -> Self {
+        Self {
+            kind: conflict.kind,
+            address: conflict.address.to_string(),
+            interfaces: conflict.interfaces,
+            static_ip_config_id: conflict.static_ip_config_id,
+            config_interface: conflict.config_interface,
+        }
+    }
+}
+
+// One entry of the interface-centric view: live state joined with stored config
+#[derive(Debug, Serialize)]
+pub struct InterfaceOverviewDto {
+    pub interface: NetworkInterfaceDto,
+    pub static_config: Option<StaticIpConfigDto>,
+    pub is_active_wifi_radio: bool,
+}
+
+// User preferences for the status views; also the body of PUT /api/network/settings/preferences
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NetworkPreferencesDto {
+    // `null` clears the pin and falls back to guessing the primary interface
+    #[serde(default)]
+    pub preferred_interface: Option<String>,
+}
+
+// Summary of the primary interface for dashboards
+#[derive(Debug, Serialize)]
+pub struct CurrentNetworkDto {
+    pub interface: Option<NetworkInterfaceDto>,
+    pub selected_by: Option<PrimaryInterfaceSource>,
+    pub preferred_interface: Option<String>,
+    pub gateway: Option<String>,
+    pub active_wifi_ssid: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApplyResultResponse {
+    pub ok: bool,
+    pub applied: bool,
+    pub already_active: bool,
+    pub detail: Option<String>,
+    // Safe mode: the change is undone at this time unless confirmed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_at: Option<String>,
+    // Problems the change was made despite, e.g. a forced subnet overlap
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfirmStaticIpResponse {
+    pub confirmed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationResponse {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    // Static IP only: assignable addresses of the entered subnet, when the IP and mask parse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usable_range: Option<HostRangeDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HostRangeDto {
+    pub first: String,
+    pub last: String,
+}
+
+impl From<Vec<String>> for ValidationResponse {
+    fn from(errors: Vec<String>) -> Self {
+        Self {
+            valid: errors.is_empty(),
+            errors,
+            usable_range: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConnectBestWifiResponse {
+    pub ssid: String,
+    pub config_id: String,
+    pub signal_level: Option<i32>,
+    #[serde(flatten)]
+    pub result: ApplyResultResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkAuditResponse {
+    // Open and WEP networks
+    pub insecure_wifi: Vec<WifiConfigDto>,
+    // Static IP configs whose interface does not exist right now
+    pub orphaned_static_ip: Vec<StaticIpConfigDto>,
+    // Configs whose last apply failed
+    pub errored: Vec<AuditErroredConfigDto>,
+    pub duplicate_ssids: Vec<DuplicateSsidDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditErroredConfigDto {
+    // "wifi" or "static_ip"
+    pub kind: &'static str,
+    pub id: String,
+    pub last_error: String,
+    pub last_error_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateSsidDto {
+    pub ssid: String,
+    pub config_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportConfigsRequest {
+    // Shaped like the list endpoints' items, with WiFi `password` filled in
+    #[serde(default)]
+    pub wifi_configs: Vec<WifiConfigDto>,
+    #[serde(default)]
+    pub static_ip_configs: Vec<StaticIpConfigDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportConfigsResponse {
+    // False when any item failed; nothing is saved then
+    pub imported: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub errors: Vec<ImportItemErrorDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportItemErrorDto {
+    // "wifi" or "static_ip", with the item's position in that list
+    pub kind: &'static str,
+    pub index: usize,
+    pub id: String,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchCreateResponse {
+    pub created: usize,
+    // One entry per submitted item, in order
+    pub results: Vec<BatchItemResultDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchItemResultDto {
+    pub index: usize,
+    // Id of the saved config; absent when the item was not saved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestartNetworkingResponse {
+    // False when system apply is disabled and nothing was touched
+    pub performed: bool,
+    // Configs re-applied and services restarted
+    pub applied: Vec<String>,
+    pub restarted: Vec<String>,
+    pub failures: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InterfaceAdminStateResponse {
+    pub state: InterfaceAdminStateDto,
+    // Whether the link was really changed; false when system apply is compiled out
+    pub applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct NetworkSettingsPageData {
     pub wifi_configs: Vec<WifiConfigDto>,
     pub static_ip_configs: Vec<StaticIpConfigDto>,
     pub network_interfaces: Vec<NetworkInterfaceDto>,
     pub active_wifi: Option<WifiConfigDto>,
+    // Latest cached WiFi scan, background or client-started, for prefilling the SSID dropdown
+    pub recent_scan: Option<RecentWifiScanDto>,
+    pub form_defaults: FormDefaultsDto,
+    // Sections that could not be loaded and were left empty
+    pub warnings: Vec<String>,
+}
+
+// Initial values of the settings page forms, from the server configuration
+#[derive(Debug, Clone, Serialize)]
+pub struct FormDefaultsDto {
+    pub security_type: WifiSecurityType,
+    pub dns_primary: Option<String>,
+    pub dns_secondary: Option<String>,
+}
+
+impl Default for FormDefaultsDto {
+    fn default() -> Self {
+        Self {
+            security_type: WifiSecurityType::WPA2,
+            dns_primary: None,
+            dns_secondary: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecentWifiScanDto {
+    pub scanned_at: String,
+    pub networks: Vec<ScannedWifiNetworkDto>,
 }
 
 // Conversion implementations
@@ -99,9 +764,15 @@ impl From<WifiConfig> for WifiConfigDto {
         Self {
             id: config.id,
             ssid: config.ssid,
+            password: None,
             security_type: config.security_type,
+            hidden: config.hidden,
+            auto_connect: config.auto_connect,
+            preferred_band: config.preferred_band,
             is_active: config.is_active,
             created_at: config.created_at.to_rfc3339(),
+            last_error: config.last_error,
+            last_error_at: config.last_error_at.map(|at| at.to_rfc3339()),
         }
     }
 }
@@ -111,25 +782,38 @@ impl From<&WifiConfig> for WifiConfigDto {
         Self {
             id: config.id.clone(),
             ssid: config.ssid.clone(),
+            password: None,
             security_type: config.security_type.clone(),
+            hidden: config.hidden,
+            auto_connect: config.auto_connect,
+            preferred_band: config.preferred_band,
             is_active: config.is_active,
             created_at: config.created_at.to_rfc3339(),
+            last_error: config.last_error.clone(),
+            last_error_at: config.last_error_at.map(|at| at.to_rfc3339()),
         }
     }
 }
 
 impl From<StaticIpConfig> for StaticIpConfigDto {
     fn from(config: StaticIpConfig) -> Self {
+        let subnet_mask = config.subnet_mask().to_string();
         Self {
             id: config.id,
             interface_name: config.interface_name,
-            ip_address: config.ip_address,
-            subnet_mask: config.subnet_mask,
-            gateway: config.gateway,
-            dns_primary: config.dns_primary,
-            dns_secondary: config.dns_secondary,
+            ip_address: config.ip_address.to_string(),
+            subnet_mask,
+            gateway: config.gateway.to_string(),
+            dns_primary: config.dns_primary.to_string(),
+            dns_secondary: config.dns_secondary.map(|dns| dns.to_string()),
+            search_domains: config.search_domains,
             is_enabled: config.is_enabled,
+            enabled_at: config.enabled_at.map(|at| at.to_rfc3339()),
+            disabled_at: config.disabled_at.map(|at| at.to_rfc3339()),
             created_at: config.created_at.to_rfc3339(),
+            updated_at: config.updated_at.map(|at| at.to_rfc3339()),
+            last_error: config.last_error,
+            last_error_at: config.last_error_at.map(|at| at.to_rfc3339()),
         }
     }
 }
@@ -139,24 +823,127 @@ impl From<&StaticIpConfig> for StaticIpConfigDto {
         Self {
             id: config.id.clone(),
             interface_name: config.interface_name.clone(),
-            ip_address: config.ip_address.clone(),
-            subnet_mask: config.subnet_mask.clone(),
-            gateway: config.gateway.clone(),
-            dns_primary: config.dns_primary.clone(),
-            dns_secondary: config.dns_secondary.clone(),
+            ip_address: config.ip_address.to_string(),
+            subnet_mask: config.subnet_mask().to_string(),
+            gateway: config.gateway.to_string(),
+            dns_primary: config.dns_primary.to_string(),
+            dns_secondary: config.dns_secondary.map(|dns| dns.to_string()),
+            search_domains: config.search_domains.clone(),
             is_enabled: config.is_enabled,
+            enabled_at: config.enabled_at.map(|at| at.to_rfc3339()),
+            disabled_at: config.disabled_at.map(|at| at.to_rfc3339()),
             created_at: config.created_at.to_rfc3339(),
+            updated_at: config.updated_at.map(|at| at.to_rfc3339()),
+            last_error: config.last_error.clone(),
+            last_error_at: config.last_error_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+impl From<CreateWifiConfigRequest> for NewWifiConfig {
+    fn from(request: CreateWifiConfigRequest) -> Self {
+        Self {
+            id: request.id,
+            ssid: request.ssid,
+            password: request.password,
+            security_type: request.security_type,
+            hidden: request.hidden,
+            auto_connect: request.auto_connect,
+            preferred_band: request.preferred_band,
+            exact_ssid: request.exact_ssid,
+        }
+    }
+}
+
+impl From<CreateStaticIpConfigRequest> for NewStaticIpConfig {
+    fn from(request: CreateStaticIpConfigRequest) -> Self {
+        Self {
+            id: request.id,
+            interface_name: request.interface_name,
+            ip_address: request.ip_address,
+            subnet_mask: request.subnet_mask,
+            prefix_len: request.prefix_len,
+            gateway: request.gateway,
+            dns_primary: request.dns_primary,
+            dns_secondary: request.dns_secondary,
+            search_domains: request.search_domains,
         }
     }
 }
 
+fn parse_created_at(created_at: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    chrono::DateTime::parse_from_rfc3339(created_at)
+        .map(|timestamp| timestamp.with_timezone(&chrono::Utc))
+        .map_err(|e| format!("Invalid created_at timestamp '{}': {}", created_at, e))
+}
+
+impl TryFrom<WifiConfigDto> for WifiConfig {
+    type Error = String;
+
+    fn try_from(dto: WifiConfigDto) -> Result<Self, Self::Error> {
+        let created_at = parse_created_at(&dto.created_at)?;
+        let password = dto
+            .password
+            .ok_or_else(|| format!("Missing password for WiFi config {}", dto.id))?;
+
+        Ok(Self {
+            id: dto.id,
+            ssid: dto.ssid,
+            password,
+            security_type: dto.security_type,
+            hidden: dto.hidden,
+            auto_connect: dto.auto_connect,
+            preferred_band: dto.preferred_band,
+            is_active: dto.is_active,
+            created_at,
+            // Apply failures belong to the system they happened on
+            last_error: None,
+            last_error_at: None,
+        })
+    }
+}
+
+impl TryFrom<StaticIpConfigDto> for StaticIpConfig {
+    type Error = String;
+
+    fn try_from(dto: StaticIpConfigDto) -> Result<Self, Self::Error> {
+        let created_at = parse_created_at(&dto.created_at)?;
+        validate_interface_name(&dto.interface_name).map_err(|e| e.to_string())?;
+
+        let config = StaticIpConfig::new(
+            dto.interface_name,
+            &dto.ip_address,
+            &dto.subnet_mask,
+            &dto.gateway,
+            &dto.dns_primary,
+            dto.dns_secondary.as_deref(),
+            dto.search_domains,
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            id: dto.id,
+            is_enabled: dto.is_enabled,
+            created_at,
+            ..config
+        })
+    }
+}
+
 impl From<NetworkInterface> for NetworkInterfaceDto {
     fn from(interface: NetworkInterface) -> Self {
         Self {
             name: interface.name,
+            category: interface.interface_type.category().to_string(),
+            display_name: interface.interface_type.display_name().to_string(),
             interface_type: interface.interface_type,
-            mac_address: interface.mac_address,
+            mac_address: interface.mac_address.map(|mac| mac.to_string()).unwrap_or_else(|| "N/A".to_string()),
             is_up: interface.is_up,
+            has_carrier: interface.has_carrier,
+            ipv4_addresses: interface.ipv4_addresses,
+            ipv6_addresses: interface.ipv6_addresses,
+            ipv4_cidrs: interface.ipv4_cidrs,
+            ipv6_cidrs: interface.ipv6_cidrs,
             current_ip: interface.current_ip,
         }
     }
@@ -167,20 +954,141 @@ impl From<&NetworkInterface> for NetworkInterfaceDto {
         Self {
             name: interface.name.clone(),
             interface_type: interface.interface_type.clone(),
-            mac_address: interface.mac_address.clone(),
+            category: interface.interface_type.category().to_string(),
+            display_name: interface.interface_type.display_name().to_string(),
+            mac_address: interface.mac_address.as_ref().map(|mac| mac.to_string()).unwrap_or_else(|| "N/A".to_string()),
             is_up: interface.is_up,
+            has_carrier: interface.has_carrier,
+            ipv4_addresses: interface.ipv4_addresses.clone(),
+            ipv6_addresses: interface.ipv6_addresses.clone(),
+            ipv4_cidrs: interface.ipv4_cidrs.clone(),
+            ipv6_cidrs: interface.ipv6_cidrs.clone(),
             current_ip: interface.current_ip.clone(),
         }
     }
 }
 
+impl From<NetworkProfile> for NetworkProfileDto {
+    fn from(profile: NetworkProfile) -> Self {
+        Self {
+            id: profile.id,
+            name: profile.name,
+            wifi_config_id: profile.wifi_config_id,
+            static_ip_config_id: profile.static_ip_config_id,
+            created_at: profile.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<ApplyOutcome> for ApplyResultResponse {
+    fn from(outcome: ApplyOutcome) -> Self {
+        Self {
+            ok: true,
+            applied: outcome.applied,
+            already_active: outcome.already_active,
+            detail: outcome.detail,
+            revert_at: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl From<InterfaceAdminState> for InterfaceAdminStateDto {
+    fn from(state: InterfaceAdminState) -> Self {
+        Self {
+            interface_name: state.interface_name,
+            desired_up: state.desired_up,
+            updated_at: state.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<InterfaceEvent> for InterfaceEventDto {
+    fn from(event: InterfaceEvent) -> Self {
+        Self {
+            id: event.id,
+            kind: event.kind,
+            interface_name: event.interface_name,
+            interface: event.interface.map(|i| i.into()),
+            timestamp: event.timestamp.to_rfc3339(),
+        }
+    }
+}
+
+impl From<HistoryEvent> for HistoryEventDto {
+    fn from(event: HistoryEvent) -> Self {
+        Self {
+            event_type: event.event_type,
+            subject: event.subject,
+            detail: event.detail,
+            timestamp: event.timestamp.to_rfc3339(),
+        }
+    }
+}
+
+impl From<InterfaceFlaps> for InterfaceFlapsDto {
+    fn from(flaps: InterfaceFlaps) -> Self {
+        Self {
+            interface_name: flaps.interface_name,
+            window_secs: flaps.window_secs,
+            transitions: flaps.transitions,
+            last_transition_at: flaps.last_transition_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+impl From<InterfaceStats> for InterfaceStatsDto {
+    fn from(stats: InterfaceStats) -> Self {
+        Self {
+            rx_bytes: stats.rx_bytes,
+            tx_bytes: stats.tx_bytes,
+        }
+    }
+}
+
+impl From<InterfaceRate> for InterfaceRateDto {
+    fn from(rate: InterfaceRate) -> Self {
+        Self {
+            interface_name: rate.interface_name,
+            rx_bytes_per_sec: rate.rx_bytes_per_sec,
+            tx_bytes_per_sec: rate.tx_bytes_per_sec,
+            interval_ms: rate.interval_ms,
+        }
+    }
+}
+
+impl From<DefaultRoute> for DefaultRouteDto {
+    fn from(route: DefaultRoute) -> Self {
+        Self {
+            gateway: route.gateway,
+            interface: route.interface,
+        }
+    }
+}
+
+impl From<WifiLinkStatus> for WifiLinkStatusDto {
+    fn from(status: WifiLinkStatus) -> Self {
+        Self {
+            connected: status.connected,
+            ssid: status.ssid,
+            bssid: status.bssid.map(|bssid| bssid.to_string()),
+            freq_mhz: status.freq_mhz,
+            signal_dbm: status.signal_dbm,
+            tx_bitrate: status.tx_bitrate,
+        }
+    }
+}
+
 impl From<ScannedWifiNetwork> for ScannedWifiNetworkDto {
     fn from(network: ScannedWifiNetwork) -> Self {
         Self {
             ssid: network.ssid,
             mac: network.mac,
             signal_level: network.signal_level,
-            channel: network.channel,
+            signal_raw: network.signal_raw,
+            channel: network.channel_number,
+            band: network.band,
+            channel_raw: network.channel,
             security: network.security,
         }
     }
@@ -191,9 +1099,103 @@ impl From<&ScannedWifiNetwork> for ScannedWifiNetworkDto {
         Self {
             ssid: network.ssid.clone(),
             mac: network.mac.clone(),
-            signal_level: network.signal_level.clone(),
-            channel: network.channel.clone(),
+            signal_level: network.signal_level,
+            signal_raw: network.signal_raw.clone(),
+            channel: network.channel_number,
+            band: network.band,
+            channel_raw: network.channel.clone(),
             security: network.security.clone(),
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wifi_dto(created_at: &str) -> WifiConfigDto {
+        WifiConfigDto {
+            id: "home".to_string(),
+            ssid: "Home".to_string(),
+            password: Some("correct horse".to_string()),
+            security_type: WifiSecurityType::WPA2,
+            hidden: false,
+            auto_connect: true,
+            preferred_band: None,
+            is_active: false,
+            created_at: created_at.to_string(),
+            last_error: None,
+            last_error_at: None,
+        }
+    }
+
+    #[test]
+    fn created_at_is_parsed_and_converted_to_utc() {
+        let parsed = parse_created_at("2024-03-01T12:30:00+02:00").unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2024-03-01T10:30:00+00:00");
+
+        let config = WifiConfig::try_from(wifi_dto("2024-03-01T10:30:00Z")).unwrap();
+        assert_eq!(config.created_at, parsed);
+    }
+
+    #[test]
+    fn invalid_created_at_is_rejected() {
+        let error = parse_created_at("yesterday").unwrap_err();
+        assert!(error.contains("Invalid created_at timestamp 'yesterday'"));
+        assert!(WifiConfig::try_from(wifi_dto("2024-13-01T00:00:00Z")).is_err());
+    }
+
+    #[test]
+    fn each_interface_type_maps_to_its_category() {
+        let cases = [
+            (InterfaceType::Ethernet, "ethernet", "Ethernet"),
+            (InterfaceType::Wireless, "wireless", "Wi-Fi"),
+            (InterfaceType::Loopback, "loopback", "Loopback"),
+            (InterfaceType::Bridge, "bridge", "Bridge"),
+            (InterfaceType::Other, "other", "Other"),
+        ];
+        for (interface_type, category, display_name) in cases {
+            let interface = NetworkInterface {
+                name: "if0".to_string(),
+                interface_type,
+                mac_address: None,
+                is_up: true,
+                has_carrier: None,
+                ipv4_addresses: Vec::new(),
+                ipv6_addresses: Vec::new(),
+                ipv4_cidrs: Vec::new(),
+                ipv6_cidrs: Vec::new(),
+                current_ip: None,
+            };
+            let by_ref = NetworkInterfaceDto::from(&interface);
+            let owned = NetworkInterfaceDto::from(interface);
+            for dto in [by_ref, owned] {
+                assert_eq!((dto.category.as_str(), dto.display_name.as_str()), (category, display_name));
+                assert_eq!(dto.mac_address, "N/A");
+            }
+        }
+    }
+
+    #[test]
+    fn interfaces_are_grouped_by_type_in_discovery_order() {
+        let mut interfaces = crate::infrastructure::simulation::SimulationFixture::default().interfaces;
+        let like_eth0 = |name: &str, interface_type| NetworkInterface {
+            name: name.to_string(),
+            interface_type,
+            ..interfaces[1].clone()
+        };
+        let extra = [
+            like_eth0("br0", InterfaceType::Bridge),
+            like_eth0("eth1", InterfaceType::Ethernet),
+            like_eth0("tun0", InterfaceType::Other),
+        ];
+        interfaces.extend(extra);
+
+        let grouped = GroupedNetworkInterfacesResponse::from(interfaces);
+        let names = |group: &[NetworkInterfaceDto]| group.iter().map(|i| i.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&grouped.ethernet), vec!["eth0", "eth1"]);
+        assert_eq!(names(&grouped.wireless), vec!["wlan0"]);
+        assert_eq!(names(&grouped.loopback), vec!["lo"]);
+        assert_eq!(names(&grouped.bridge), vec!["br0"]);
+        assert_eq!(names(&grouped.other), vec!["tun0"]);
+    }
+}
@@ -1,43 +1,122 @@
 // Network configuration use cases
 
 use async_trait::async_trait;
+use std::net::Ipv4Addr;
 use std::sync::Arc;
-use crate::domain::network_services::NetworkConfigService;
+use crate::domain::network_entities::{
+    select_primary_interface, signal_quality_percent, AddressConflict, NewStaticIpConfig, DnsLatency, EventHistoryFilter, HistoryEventType, InterfaceType, NetworkInterface, StaticIpConfig, WifiConfig, WifiSecurityType, PREFERRED_INTERFACE_KEY,
+};
+use crate::domain::network_appliers::ServiceRestarter;
+use crate::domain::network_errors::NetworkError;
+use crate::domain::network_repositories::{DnsProber, EventHistoryRepository, IpProber, SettingsRepository};
+use crate::domain::network_services::{NetworkConfigService, NetworkProfileService};
+use crate::domain::validation::{
+    gateway_in_subnet, mask_to_prefix_len, network_address, subnets_overlap, validate_domain_name, validate_interface_name,
+};
+use crate::application::static_ip_safe_mode::StaticIpSafeMode;
+use crate::application::netplan::render_netplan;
+use crate::application::wifi_scan_cache::WifiScanCache;
 use crate::application::network_dto::*;
 
 #[async_trait]
 pub trait GetNetworkSettingsUseCase: Send + Sync {
-    async fn execute(&self) -> Result<NetworkSettingsPageData, String>;
+    async fn execute(&self, include_loopback: bool) -> Result<NetworkSettingsPageData, String>;
 }
 
 #[async_trait]
 pub trait CreateWifiConfigUseCase: Send + Sync {
-    async fn execute(&self, request: CreateWifiConfigRequest) -> Result<WifiConfigResponse, String>;
+    async fn execute(&self, request: CreateWifiConfigRequest) -> Result<WifiConfigResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ValidateWifiConfigUseCase: Send + Sync {
+    async fn execute(&self, request: CreateWifiConfigRequest) -> ValidationResponse;
+}
+
+#[async_trait]
+pub trait ListWifiConfigsUseCase: Send + Sync {
+    async fn execute(&self, query: ListWifiConfigsQuery) -> Result<WifiConfigsListResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetWifiConfigUseCase: Send + Sync {
+    // `authenticated` says whether the caller proved who they are; revealing the secret requires it
+    async fn execute(&self, config_id: String, query: GetWifiConfigQuery, authenticated: bool) -> Result<WifiConfigDetailResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ListStaticIpConfigsUseCase: Send + Sync {
+    async fn execute(&self) -> Result<StaticIpConfigsListResponse, NetworkError>;
 }
 
 #[async_trait]
 pub trait ActivateWifiConfigUseCase: Send + Sync {
-    async fn execute(&self, config_id: String) -> Result<(), String>;
+    async fn execute(&self, config_id: String) -> Result<ApplyResultResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait SwitchWifiUseCase: Send + Sync {
+    async fn execute(&self, request: SwitchWifiRequest) -> Result<SwitchWifiResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ConnectBestWifiUseCase: Send + Sync {
+    async fn execute(&self) -> Result<ConnectBestWifiResponse, NetworkError>;
 }
 
 #[async_trait]
 pub trait DeleteWifiConfigUseCase: Send + Sync {
-    async fn execute(&self, config_id: String) -> Result<(), String>;
+    async fn execute(&self, config_id: String, force: bool) -> Result<(), NetworkError>;
+}
+
+#[async_trait]
+pub trait SetWifiAutoConnectUseCase: Send + Sync {
+    async fn execute(&self, config_id: String, request: SetWifiAutoConnectRequest) -> Result<WifiConfigResponse, NetworkError>;
 }
 
 #[async_trait]
 pub trait CreateStaticIpConfigUseCase: Send + Sync {
-    async fn execute(&self, request: CreateStaticIpConfigRequest) -> Result<StaticIpConfigResponse, String>;
+    async fn execute(&self, request: CreateStaticIpConfigRequest) -> Result<StaticIpConfigResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ValidateStaticIpConfigUseCase: Send + Sync {
+    async fn execute(&self, request: CreateStaticIpConfigRequest) -> ValidationResponse;
+}
+
+#[async_trait]
+pub trait BatchCreateStaticIpConfigsUseCase: Send + Sync {
+    async fn execute(&self, requests: Vec<CreateStaticIpConfigRequest>, atomic: bool) -> Result<BatchCreateResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait PrecheckStaticIpConfigUseCase: Send + Sync {
+    async fn execute(&self, request: CreateStaticIpConfigRequest) -> StaticIpPrecheckResponse;
 }
 
 #[async_trait]
 pub trait EnableStaticIpConfigUseCase: Send + Sync {
-    async fn execute(&self, config_id: String) -> Result<(), String>;
+    async fn execute(&self, config_id: String, takeover: bool, force: bool) -> Result<ApplyResultResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ReorderStaticIpDnsUseCase: Send + Sync {
+    async fn execute(&self, config_id: String, request: ReorderDnsRequest) -> Result<StaticIpConfigResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ReplaceStaticIpDnsUseCase: Send + Sync {
+    async fn execute(&self, config_id: String, request: ReplaceDnsRequest) -> Result<StaticIpConfigResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ConfirmStaticIpConfigUseCase: Send + Sync {
+    async fn execute(&self, config_id: String) -> Result<ConfirmStaticIpResponse, NetworkError>;
 }
 
 #[async_trait]
 pub trait DisableStaticIpConfigUseCase: Send + Sync {
-    async fn execute(&self, config_id: String) -> Result<(), String>;
+    async fn execute(&self, config_id: String) -> Result<ApplyResultResponse, String>;
 }
 
 #[async_trait]
@@ -45,46 +124,203 @@ pub trait DeleteStaticIpConfigUseCase: Send + Sync {
     async fn execute(&self, config_id: String) -> Result<(), String>;
 }
 
+#[async_trait]
+pub trait SetInterfaceAdminStateUseCase: Send + Sync {
+    async fn execute(&self, interface_name: String, request: SetInterfaceAdminStateRequest) -> Result<InterfaceAdminStateResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetInterfaceRateUseCase: Send + Sync {
+    async fn execute(&self, interface_name: String) -> Result<InterfaceRateDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetDnsLatencyUseCase: Send + Sync {
+    async fn execute(&self, query: DnsLatencyQuery) -> Result<DnsLatencyResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetAddressConflictsUseCase: Send + Sync {
+    async fn execute(&self) -> Result<AddressConflictsResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetGroupedNetworkInterfacesUseCase: Send + Sync {
+    async fn execute(&self, include_loopback: bool) -> Result<GroupedNetworkInterfacesResponse, String>;
+}
+
+#[async_trait]
+pub trait GetNetworkInterfaceUseCase: Send + Sync {
+    async fn execute(&self, interface_name: String) -> Result<NetworkInterfaceDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetWifiLinkStatusUseCase: Send + Sync {
+    async fn execute(&self, interface_name: String) -> Result<WifiLinkStatusDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait SampleWifiSignalUseCase: Send + Sync {
+    async fn execute(&self) -> WifiSignalSampleDto;
+}
+
+#[async_trait]
+pub trait GetInterfaceOverviewUseCase: Send + Sync {
+    async fn execute(&self, include_loopback: bool) -> Result<Vec<InterfaceOverviewDto>, String>;
+}
+
+#[async_trait]
+pub trait ExportNetplanUseCase: Send + Sync {
+    async fn execute(&self) -> Result<String, NetworkError>;
+}
+
+#[async_trait]
+pub trait CreateNetworkProfileUseCase: Send + Sync {
+    async fn execute(&self, request: CreateNetworkProfileRequest) -> Result<NetworkProfileResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ListNetworkProfilesUseCase: Send + Sync {
+    async fn execute(&self) -> Result<NetworkProfilesListResponse, String>;
+}
+
+#[async_trait]
+pub trait ActivateProfileUseCase: Send + Sync {
+    async fn execute(&self, profile_id: String) -> Result<ActivateProfileResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ProbeIpUseCase: Send + Sync {
+    async fn execute(&self, request: ProbeIpRequest) -> Result<ProbeIpResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetDefaultRouteUseCase: Send + Sync {
+    async fn execute(&self) -> Result<DefaultRouteDto, String>;
+}
+
 #[async_trait]
 pub trait ScanWifiNetworksUseCase: Send + Sync {
-    async fn execute(&self) -> Result<Vec<ScannedWifiNetworkDto>, String>;
+    async fn execute(&self, query: &ScanWifiNetworksQuery) -> Result<WifiScanResultDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetAllInterfaceStatsUseCase: Send + Sync {
+    async fn execute(&self) -> Result<std::collections::BTreeMap<String, InterfaceStatsDto>, String>;
+}
+
+#[async_trait]
+pub trait GetNetworkPreferencesUseCase: Send + Sync {
+    async fn execute(&self) -> Result<NetworkPreferencesDto, String>;
+}
+
+#[async_trait]
+pub trait SetNetworkPreferencesUseCase: Send + Sync {
+    async fn execute(&self, request: NetworkPreferencesDto) -> Result<NetworkPreferencesDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetCurrentNetworkUseCase: Send + Sync {
+    async fn execute(&self) -> Result<CurrentNetworkDto, String>;
+}
+
+#[async_trait]
+pub trait GetNetworkAuditUseCase: Send + Sync {
+    async fn execute(&self) -> Result<NetworkAuditResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait ImportConfigsUseCase: Send + Sync {
+    async fn execute(&self, request: ImportConfigsRequest) -> Result<ImportConfigsResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait RestartNetworkingUseCase: Send + Sync {
+    async fn execute(&self) -> Result<RestartNetworkingResponse, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetEventHistoryUseCase: Send + Sync {
+    async fn execute(&self, query: EventHistoryQuery) -> Result<EventHistoryResponse, NetworkError>;
 }
 
 // Implementations
 pub struct GetNetworkSettingsUseCaseImpl {
     network_service: Arc<dyn NetworkConfigService>,
+    scan_cache: Option<Arc<WifiScanCache>>,
+    form_defaults: FormDefaultsDto,
 }
 
 impl GetNetworkSettingsUseCaseImpl {
     pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
-        Self { network_service }
+        Self { network_service, scan_cache: None, form_defaults: FormDefaultsDto::default() }
+    }
+
+    pub fn with_form_defaults(mut self, form_defaults: FormDefaultsDto) -> Self {
+        self.form_defaults = form_defaults;
+        self
+    }
+
+    // Include the latest cached WiFi scan in the page data
+    pub fn with_scan_cache(mut self, scan_cache: Arc<WifiScanCache>) -> Self {
+        self.scan_cache = Some(scan_cache);
+        self
     }
 }
 
 #[async_trait]
 impl GetNetworkSettingsUseCase for GetNetworkSettingsUseCaseImpl {
-    async fn execute(&self) -> Result<NetworkSettingsPageData, String> {
-        let wifi_configs = self.network_service.get_wifi_configs().await?
+    // Each section is fetched on its own so one failing source (say, interface enumeration)
+    // still leaves the rest of the page usable; what failed is listed in `warnings`
+    async fn execute(&self, include_loopback: bool) -> Result<NetworkSettingsPageData, String> {
+        let mut warnings = Vec::new();
+        let wifi_configs = settings_section(&mut warnings, "WiFi configurations", self.network_service.get_wifi_configs().await)
             .into_iter().map(|c| c.into()).collect();
         
-        let static_ip_configs = self.network_service.get_static_ip_configs().await?
+        let static_ip_configs = settings_section(&mut warnings, "static IP configurations", self.network_service.get_static_ip_configs().await)
             .into_iter().map(|c| c.into()).collect();
         
-        let network_interfaces = self.network_service.get_network_interfaces().await?
+        let network_interfaces = settings_section(&mut warnings, "network interfaces", self.network_service.get_network_interfaces().await);
+        let network_interfaces = without_loopback(network_interfaces, include_loopback)
             .into_iter().map(|i| i.into()).collect();
         
-        let active_wifi = self.network_service.get_active_wifi_config().await?
+        let active_wifi = settings_section(&mut warnings, "the active WiFi configuration", self.network_service.get_active_wifi_config().await)
             .map(|c| c.into());
+
+        let recent_scan = self.scan_cache.as_ref().and_then(|scan_cache| scan_cache.latest());
         
         Ok(NetworkSettingsPageData {
             wifi_configs,
             static_ip_configs,
             network_interfaces,
             active_wifi,
+            recent_scan,
+            form_defaults: self.form_defaults.clone(),
+            warnings,
         })
     }
 }
 
+// Interface lists leave out loopback unless asked for; it is rarely what anyone is looking for
+fn without_loopback(interfaces: Vec<NetworkInterface>, include_loopback: bool) -> Vec<NetworkInterface> {
+    if include_loopback {
+        return interfaces;
+    }
+    interfaces
+        .into_iter()
+        .filter(|interface| !matches!(interface.interface_type, InterfaceType::Loopback))
+        .collect()
+}
+
+// A settings page section, or an empty one plus a warning when it could not be loaded
+fn settings_section<T: Default>(warnings: &mut Vec<String>, name: &str, result: Result<T, String>) -> T {
+    result.unwrap_or_else(|e| {
+        tracing::warn!("Settings page: could not load {}: {}", name, e);
+        warnings.push(format!("Could not load {}: {}", name, e));
+        T::default()
+    })
+}
+
 pub struct CreateWifiConfigUseCaseImpl {
     network_service: Arc<dyn NetworkConfigService>,
 }
@@ -97,12 +333,8 @@ impl CreateWifiConfigUseCaseImpl {
 
 #[async_trait]
 impl CreateWifiConfigUseCase for CreateWifiConfigUseCaseImpl {
-    async fn execute(&self, request: CreateWifiConfigRequest) -> Result<WifiConfigResponse, String> {
-        let config = self.network_service.create_wifi_config(
-            request.ssid,
-            request.password,
-            request.security_type,
-        ).await?;
+    async fn execute(&self, request: CreateWifiConfigRequest) -> Result<WifiConfigResponse, NetworkError> {
+        let config = self.network_service.create_wifi_config(request.into()).await?;
         
         Ok(WifiConfigResponse {
             config: config.into(),
@@ -110,6 +342,23 @@ impl CreateWifiConfigUseCase for CreateWifiConfigUseCaseImpl {
     }
 }
 
+pub struct ValidateWifiConfigUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl ValidateWifiConfigUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl ValidateWifiConfigUseCase for ValidateWifiConfigUseCaseImpl {
+    async fn execute(&self, request: CreateWifiConfigRequest) -> ValidationResponse {
+        self.network_service.validate_wifi_config(&request.into()).into()
+    }
+}
+
 pub struct ActivateWifiConfigUseCaseImpl {
     network_service: Arc<dyn NetworkConfigService>,
 }
@@ -122,8 +371,150 @@ impl ActivateWifiConfigUseCaseImpl {
 
 #[async_trait]
 impl ActivateWifiConfigUseCase for ActivateWifiConfigUseCaseImpl {
-    async fn execute(&self, config_id: String) -> Result<(), String> {
-        self.network_service.activate_wifi_config(&config_id).await
+    async fn execute(&self, config_id: String) -> Result<ApplyResultResponse, NetworkError> {
+        let outcome = self.network_service.activate_wifi_config(&config_id).await?;
+        Ok(outcome.into())
+    }
+}
+
+// Move to another saved network, keeping the current one active until the new one has associated
+pub struct SwitchWifiUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    wifi_interface: String,
+    // Off when nothing is pushed to the system, so there is no association to wait for
+    verify: bool,
+    verify_timeout: std::time::Duration,
+}
+
+impl SwitchWifiUseCaseImpl {
+    pub const DEFAULT_VERIFY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, wifi_interface: String, verify: bool) -> Self {
+        Self {
+            network_service,
+            wifi_interface,
+            verify,
+            verify_timeout: Self::DEFAULT_VERIFY_TIMEOUT,
+        }
+    }
+
+    // Poll the link until it reports `ssid`, or give up after `verify_timeout`
+    async fn wait_for_association(&self, ssid: &str) -> bool {
+        let deadline = tokio::time::Instant::now() + self.verify_timeout;
+        loop {
+            if let Ok(link) = self.network_service.get_wifi_link_status(&self.wifi_interface).await
+                && link.connected
+                && link.ssid.as_deref() == Some(ssid)
+            {
+                return true;
+            }
+            if tokio::time::Instant::now() + Self::POLL_INTERVAL > deadline {
+                return false;
+            }
+            tokio::time::sleep(Self::POLL_INTERVAL).await;
+        }
+    }
+
+    // Put the previous network back on the air; it never lost its active flag
+    async fn revert(&self, previous: Option<&WifiConfig>) -> String {
+        let Some(previous) = previous else {
+            return "no previous network to return to".to_string();
+        };
+        match self.network_service.apply_wifi_config_unmarked(&previous.id).await {
+            Ok(_) => format!("reverted to {}", previous.ssid),
+            Err(e) => {
+                tracing::error!("Failed to revert WiFi to {}: {}", previous.ssid, e);
+                format!("reverting to {} also failed: {}", previous.ssid, e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SwitchWifiUseCase for SwitchWifiUseCaseImpl {
+    async fn execute(&self, request: SwitchWifiRequest) -> Result<SwitchWifiResponse, NetworkError> {
+        let configs = self.network_service.get_wifi_configs().await?;
+        let target = configs
+            .iter()
+            .find(|config| config.id == request.to_id)
+            .cloned()
+            .ok_or_else(|| NetworkError::NotFound(format!("WiFi config {} not found", request.to_id)))?;
+        let previous = configs.iter().find(|config| config.is_active);
+
+        if !self.verify || target.is_active {
+            let outcome = self.network_service.activate_wifi_config(&target.id).await?;
+            let mut config = target;
+            config.is_active = true;
+            return Ok(SwitchWifiResponse {
+                config: config.into(),
+                verified: false,
+                result: outcome.into(),
+            });
+        }
+
+        let outcome = match self.network_service.apply_wifi_config_unmarked(&target.id).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                let reverted = self.revert(previous).await;
+                return Err(NetworkError::Internal(format!("Failed to apply {}: {}; {}", target.ssid, e, reverted)));
+            }
+        };
+        if !self.wait_for_association(&target.ssid).await {
+            let reverted = self.revert(previous).await;
+            return Err(NetworkError::Timeout(format!(
+                "{} did not associate within {}s; {}",
+                target.ssid,
+                self.verify_timeout.as_secs(),
+                reverted
+            )));
+        }
+
+        let config = self.network_service.mark_wifi_config_active(&target.id).await?;
+        Ok(SwitchWifiResponse {
+            config: config.into(),
+            verified: true,
+            result: outcome.into(),
+        })
+    }
+}
+
+pub struct ConnectBestWifiUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl ConnectBestWifiUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl ConnectBestWifiUseCase for ConnectBestWifiUseCaseImpl {
+    // Activate the saved config whose network is in range with the strongest signal
+    async fn execute(&self) -> Result<ConnectBestWifiResponse, NetworkError> {
+        let networks = self.network_service.scan_wifi_networks().await?;
+        let configs = self.network_service.get_wifi_configs().await?;
+
+        let best = networks
+            .into_iter()
+            .filter_map(|network| {
+                let config = configs.iter().find(|config| config.ssid == network.ssid)?;
+                Some((network, config))
+            })
+            .max_by_key(|(network, _)| network.signal_level.unwrap_or(i32::MIN));
+
+        let (network, config) = best.ok_or_else(|| {
+            NetworkError::NotFound("No saved WiFi network is in range".to_string())
+        })?;
+
+        let outcome = self.network_service.activate_wifi_config(&config.id).await?;
+        Ok(ConnectBestWifiResponse {
+            ssid: network.ssid,
+            config_id: config.id.clone(),
+            signal_level: network.signal_level,
+            result: outcome.into(),
+        })
     }
 }
 
@@ -139,104 +530,1988 @@ impl DeleteWifiConfigUseCaseImpl {
 
 #[async_trait]
 impl DeleteWifiConfigUseCase for DeleteWifiConfigUseCaseImpl {
-    async fn execute(&self, config_id: String) -> Result<(), String> {
-        self.network_service.delete_wifi_config(&config_id).await
+    async fn execute(&self, config_id: String, force: bool) -> Result<(), NetworkError> {
+        self.network_service.delete_wifi_config(&config_id, force).await
     }
 }
 
-pub struct CreateStaticIpConfigUseCaseImpl {
+pub struct SetWifiAutoConnectUseCaseImpl {
     network_service: Arc<dyn NetworkConfigService>,
 }
 
-impl CreateStaticIpConfigUseCaseImpl {
+impl SetWifiAutoConnectUseCaseImpl {
     pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
         Self { network_service }
     }
 }
 
 #[async_trait]
-impl CreateStaticIpConfigUseCase for CreateStaticIpConfigUseCaseImpl {
-    async fn execute(&self, request: CreateStaticIpConfigRequest) -> Result<StaticIpConfigResponse, String> {
-        let config = self.network_service.create_static_ip_config(
-            request.interface_name,
-            request.ip_address,
-            request.subnet_mask,
-            request.gateway,
-            request.dns_primary,
-            request.dns_secondary,
-        ).await?;
-        
-        Ok(StaticIpConfigResponse {
-            config: config.into(),
-        })
+impl SetWifiAutoConnectUseCase for SetWifiAutoConnectUseCaseImpl {
+    async fn execute(&self, config_id: String, request: SetWifiAutoConnectRequest) -> Result<WifiConfigResponse, NetworkError> {
+        let config = self.network_service.set_wifi_auto_connect(&config_id, request.auto_connect).await?;
+        Ok(WifiConfigResponse { config: config.into() })
     }
 }
 
-pub struct EnableStaticIpConfigUseCaseImpl {
+pub struct GetWifiConfigUseCaseImpl {
     network_service: Arc<dyn NetworkConfigService>,
 }
 
-impl EnableStaticIpConfigUseCaseImpl {
+impl GetWifiConfigUseCaseImpl {
     pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
         Self { network_service }
     }
 }
 
 #[async_trait]
-impl EnableStaticIpConfigUseCase for EnableStaticIpConfigUseCaseImpl {
-    async fn execute(&self, config_id: String) -> Result<(), String> {
-        self.network_service.enable_static_ip(&config_id).await
+impl GetWifiConfigUseCase for GetWifiConfigUseCaseImpl {
+    async fn execute(&self, config_id: String, query: GetWifiConfigQuery, authenticated: bool) -> Result<WifiConfigDetailResponse, NetworkError> {
+        if query.reveal_secret && !authenticated {
+            tracing::warn!(target: "audit", config_id = %config_id, "Refused to reveal WiFi secret to an unauthenticated caller");
+            return Err(NetworkError::Forbidden(
+                "reveal_secret requires an authenticated caller".to_string(),
+            ));
+        }
+
+        let config = self.network_service.get_wifi_configs().await?
+            .into_iter()
+            .find(|config| config.id == config_id)
+            .ok_or_else(|| NetworkError::NotFound(format!("WiFi config {} not found", config_id)))?;
+
+        let view = if query.reveal_secret {
+            tracing::warn!(target: "audit", config_id = %config.id, ssid = %config.ssid, "Revealed WiFi secret");
+            let password = config.password.clone();
+            WifiConfigView::WithSecret(WifiConfigWithSecretDto {
+                config: config.into(),
+                password,
+            })
+        } else {
+            WifiConfigView::Plain(config.into())
+        };
+        Ok(WifiConfigDetailResponse { config: view })
     }
 }
 
-pub struct DisableStaticIpConfigUseCaseImpl {
+pub struct ListWifiConfigsUseCaseImpl {
     network_service: Arc<dyn NetworkConfigService>,
 }
 
-impl DisableStaticIpConfigUseCaseImpl {
+impl ListWifiConfigsUseCaseImpl {
     pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
         Self { network_service }
     }
 }
 
 #[async_trait]
-impl DisableStaticIpConfigUseCase for DisableStaticIpConfigUseCaseImpl {
-    async fn execute(&self, config_id: String) -> Result<(), String> {
-        self.network_service.disable_static_ip(&config_id).await
+impl ListWifiConfigsUseCase for ListWifiConfigsUseCaseImpl {
+    async fn execute(&self, query: ListWifiConfigsQuery) -> Result<WifiConfigsListResponse, NetworkError> {
+        let security_types = match query.security.as_deref() {
+            Some(security) => Some(
+                security
+                    .split(',')
+                    .filter(|value| !value.trim().is_empty())
+                    .map(|value| value.parse::<WifiSecurityType>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(NetworkError::Validation)?,
+            ),
+            None => None,
+        };
+        // `?security=` with nothing in it is no filter, not a filter that matches nothing
+        let security_types = security_types.filter(|types| !types.is_empty());
+
+        let configs = self.network_service.get_wifi_configs().await?
+            .into_iter()
+            .filter(|config| {
+                security_types
+                    .as_ref()
+                    .is_none_or(|types| types.contains(&config.security_type))
+            })
+            .map(|config| config.into())
+            .collect();
+
+        Ok(WifiConfigsListResponse { configs })
     }
 }
 
-pub struct DeleteStaticIpConfigUseCaseImpl {
+pub struct ListStaticIpConfigsUseCaseImpl {
     network_service: Arc<dyn NetworkConfigService>,
 }
 
-impl DeleteStaticIpConfigUseCaseImpl {
+impl ListStaticIpConfigsUseCaseImpl {
     pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
         Self { network_service }
     }
 }
 
 #[async_trait]
-impl DeleteStaticIpConfigUseCase for DeleteStaticIpConfigUseCaseImpl {
-    async fn execute(&self, config_id: String) -> Result<(), String> {
-        self.network_service.delete_static_ip_config(&config_id).await
+impl ListStaticIpConfigsUseCase for ListStaticIpConfigsUseCaseImpl {
+    async fn execute(&self) -> Result<StaticIpConfigsListResponse, NetworkError> {
+        let interface_names: std::collections::HashSet<String> = self.network_service.get_network_interfaces().await?
+            .into_iter()
+            .map(|interface| interface.name)
+            .collect();
+        let configs = self.network_service.get_static_ip_configs().await?
+            .into_iter()
+            .map(|config| StaticIpConfigListItemDto {
+                interface_present: interface_names.contains(&config.interface_name),
+                config: config.into(),
+            })
+            .collect();
+        Ok(StaticIpConfigsListResponse { configs })
     }
 }
 
-pub struct ScanWifiNetworksUseCaseImpl {
+pub struct CreateStaticIpConfigUseCaseImpl {
     network_service: Arc<dyn NetworkConfigService>,
 }
 
-impl ScanWifiNetworksUseCaseImpl {
+impl CreateStaticIpConfigUseCaseImpl {
     pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
         Self { network_service }
     }
 }
 
 #[async_trait]
-impl ScanWifiNetworksUseCase for ScanWifiNetworksUseCaseImpl {
-    async fn execute(&self) -> Result<Vec<ScannedWifiNetworkDto>, String> {
-        let networks = self.network_service.scan_wifi_networks().await?;
-        Ok(networks.into_iter().map(|n| n.into()).collect())
+impl CreateStaticIpConfigUseCase for CreateStaticIpConfigUseCaseImpl {
+    async fn execute(&self, request: CreateStaticIpConfigRequest) -> Result<StaticIpConfigResponse, NetworkError> {
+        let config = self.network_service.create_static_ip_config(request.into()).await?;
+        let warnings = interface_readiness_warnings(self.network_service.as_ref(), &config.interface_name).await;
+
+        Ok(StaticIpConfigResponse {
+            config: config.into(),
+            warnings,
+        })
+    }
+}
+
+// Creates many static IP configs in one call with the same checks as the single create. Every
+// item is checked before anything is saved; in atomic mode one failure stops the whole batch,
+// and a save that fails part way (e.g. on the config limit) deletes what the batch already saved.
+// Configs evicted to make room are not brought back.
+pub struct BatchCreateStaticIpConfigsUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl BatchCreateStaticIpConfigsUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+
+    // Problems of each item that can be found without saving anything
+    async fn check_items(&self, new_configs: &[NewStaticIpConfig]) -> Result<Vec<Vec<String>>, NetworkError> {
+        let stored: std::collections::HashSet<String> = self
+            .network_service
+            .get_static_ip_configs()
+            .await?
+            .into_iter()
+            .map(|config| config.id)
+            .collect();
+        let mut seen = std::collections::HashSet::new();
+        Ok(new_configs
+            .iter()
+            .map(|new_config| {
+                let mut errors = self.network_service.validate_static_ip_config(new_config);
+                if let Some(id) = &new_config.id {
+                    if stored.contains(id) {
+                        errors.push(format!("Static IP config {} already exists", id));
+                    } else if !seen.insert(id.clone()) {
+                        errors.push(format!("id {} appears more than once", id));
+                    }
+                }
+                errors
+            })
+            .collect())
+    }
+
+    async fn roll_back(&self, results: &mut [BatchItemResultDto]) {
+        for result in results {
+            if let Some(id) = result.id.take()
+                && let Err(e) = self.network_service.delete_static_ip_config(&id).await
+            {
+                tracing::error!("Failed to roll back static IP config {} from a batch: {}", id, e);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl BatchCreateStaticIpConfigsUseCase for BatchCreateStaticIpConfigsUseCaseImpl {
+    async fn execute(&self, requests: Vec<CreateStaticIpConfigRequest>, atomic: bool) -> Result<BatchCreateResponse, NetworkError> {
+        if requests.is_empty() {
+            return Err(NetworkError::Validation("batch must contain at least one config".to_string()));
+        }
+        let new_configs: Vec<NewStaticIpConfig> = requests.into_iter().map(NewStaticIpConfig::from).collect();
+        let checks = self.check_items(&new_configs).await?;
+        let mut results: Vec<BatchItemResultDto> = checks
+            .into_iter()
+            .enumerate()
+            .map(|(index, errors)| BatchItemResultDto { index, id: None, errors })
+            .collect();
+        if atomic && results.iter().any(|result| !result.errors.is_empty()) {
+            return Ok(BatchCreateResponse { created: 0, results });
+        }
+
+        for (index, new_config) in new_configs.into_iter().enumerate() {
+            if !results[index].errors.is_empty() {
+                continue;
+            }
+            match self.network_service.create_static_ip_config(new_config).await {
+                Ok(config) => results[index].id = Some(config.id),
+                Err(e) => {
+                    results[index].errors.push(e.to_string());
+                    if atomic {
+                        self.roll_back(&mut results).await;
+                        return Ok(BatchCreateResponse { created: 0, results });
+                    }
+                }
+            }
+        }
+        Ok(BatchCreateResponse {
+            created: results.iter().filter(|result| result.id.is_some()).count(),
+            results,
+        })
+    }
+}
+
+pub struct ReorderStaticIpDnsUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl ReorderStaticIpDnsUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl ReorderStaticIpDnsUseCase for ReorderStaticIpDnsUseCaseImpl {
+    async fn execute(&self, config_id: String, request: ReorderDnsRequest) -> Result<StaticIpConfigResponse, NetworkError> {
+        let config = self.network_service
+            .reorder_static_ip_dns(&config_id, request.dns_servers)
+            .await?;
+
+        Ok(StaticIpConfigResponse {
+            config: config.into(),
+            warnings: Vec::new(),
+        })
+    }
+}
+
+pub struct ReplaceStaticIpDnsUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl ReplaceStaticIpDnsUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl ReplaceStaticIpDnsUseCase for ReplaceStaticIpDnsUseCaseImpl {
+    async fn execute(&self, config_id: String, request: ReplaceDnsRequest) -> Result<StaticIpConfigResponse, NetworkError> {
+        let config = self.network_service
+            .replace_static_ip_dns(&config_id, request.dns_servers)
+            .await?;
+
+        Ok(StaticIpConfigResponse {
+            config: config.into(),
+            warnings: Vec::new(),
+        })
+    }
+}
+
+// A static IP on a wireless interface does nothing until it is up and associated. Only a
+// warning: the config may be provisioned ahead of time, and lookup failures stay silent.
+async fn interface_readiness_warnings(network_service: &dyn NetworkConfigService, interface_name: &str) -> Vec<String> {
+    let Ok(interface) = network_service.get_network_interface(interface_name).await else {
+        return Vec::new();
+    };
+    if !matches!(interface.interface_type, InterfaceType::Wireless) {
+        return Vec::new();
+    }
+
+    if !interface.is_up {
+        return vec![format!(
+            "Wireless interface {} is down; the static IP will not take effect until it is up and associated",
+            interface_name
+        )];
+    }
+    match network_service.get_wifi_link_status(interface_name).await {
+        Ok(link) if !link.connected => vec![format!(
+            "Wireless interface {} is not associated with a network; the static IP will not take effect until it connects",
+            interface_name
+        )],
+        _ => Vec::new(),
+    }
+}
+
+pub struct ValidateStaticIpConfigUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl ValidateStaticIpConfigUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl ValidateStaticIpConfigUseCase for ValidateStaticIpConfigUseCaseImpl {
+    async fn execute(&self, request: CreateStaticIpConfigRequest) -> ValidationResponse {
+        let new_config = request.into();
+        let mut response: ValidationResponse = self.network_service.validate_static_ip_config(&new_config).into();
+        response.usable_range = new_config.usable_host_range().map(|(first, last)| HostRangeDto {
+            first: first.to_string(),
+            last: last.to_string(),
+        });
+        response
+    }
+}
+
+// Everything that could go wrong when the config is saved and enabled, checked up front. The
+// checks do not depend on each other: one whose inputs do not parse fails with a pointer to the
+// field instead of hiding the rest.
+pub struct PrecheckStaticIpConfigUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    prober: Arc<dyn IpProber>,
+}
+
+impl PrecheckStaticIpConfigUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, prober: Arc<dyn IpProber>) -> Self {
+        Self { network_service, prober }
+    }
+
+    async fn interface_up(&self, interface_name: &str) -> Result<String, String> {
+        let interface = self
+            .network_service
+            .get_network_interface(interface_name)
+            .await
+            .map_err(|e| e.to_string())?;
+        if interface.is_up {
+            Ok(format!("{} is up", interface_name))
+        } else {
+            Err(format!("{} is down", interface_name))
+        }
+    }
+
+    async fn address_free(&self, interface_name: &str, ip_address: Option<Ipv4Addr>) -> Result<String, String> {
+        let ip_address = ip_address.ok_or("ip_address is not a valid IPv4 address")?;
+        let probe = tokio::time::timeout(
+            ProbeIpUseCaseImpl::PROBE_TIMEOUT,
+            self.prober.probe(interface_name, ip_address),
+        )
+        .await
+        .map_err(|_| format!("Probe of {} timed out", ip_address))?
+        .map_err(|e| e.to_string())?;
+        if probe {
+            Err(format!("{} already answers on {} ({})", ip_address, interface_name, self.prober.method()))
+        } else {
+            Ok(format!("No reply for {} ({})", ip_address, self.prober.method()))
+        }
+    }
+
+    async fn no_subnet_overlap(&self, new_config: &NewStaticIpConfig, subnet: Option<(Ipv4Addr, u8)>) -> Result<String, String> {
+        let (ip_address, prefix_len) = subnet.ok_or("ip_address or subnet mask is invalid")?;
+        let configs = self.network_service.get_static_ip_configs().await?;
+        // Same rule as enabling: configs on the same interface replace each other
+        let overlaps: Vec<String> = configs
+            .iter()
+            .filter(|config| config.is_enabled && config.interface_name != new_config.interface_name)
+            .filter(|config| subnets_overlap(ip_address, prefix_len, config.ip_address, config.prefix_len))
+            .map(|config| config.describe_overlap())
+            .collect();
+        if overlaps.is_empty() {
+            Ok(format!("{}/{} overlaps no enabled config", network_address(ip_address, prefix_len), prefix_len))
+        } else {
+            Err(format!("Subnet overlaps {}", overlaps.join(", ")))
+        }
+    }
+}
+
+fn gateway_check(subnet: Option<(Ipv4Addr, u8)>, gateway: Option<Ipv4Addr>) -> Result<String, String> {
+    let (ip_address, prefix_len) = subnet.ok_or("ip_address or subnet mask is invalid")?;
+    let gateway = gateway.ok_or("gateway is not a valid IPv4 address")?;
+    gateway_in_subnet(ip_address, prefix_len, gateway).map_err(|e| e.to_string())?;
+    Ok(format!("{} is in {}/{}", gateway, network_address(ip_address, prefix_len), prefix_len))
+}
+
+// /31 and /32 have no network or broadcast address, so any address in them is assignable
+fn host_address_check(new_config: &NewStaticIpConfig, subnet: Option<(Ipv4Addr, u8)>) -> Result<String, String> {
+    let (ip_address, prefix_len) = subnet.ok_or("ip_address or subnet mask is invalid")?;
+    match new_config.usable_host_range() {
+        Some((first, last)) if ip_address < first || ip_address > last => Err(format!(
+            "{} is the {} address of {}/{}",
+            ip_address,
+            if ip_address < first { "network" } else { "broadcast" },
+            network_address(ip_address, prefix_len),
+            prefix_len
+        )),
+        Some((first, last)) => Ok(format!("{} is between {} and {}", ip_address, first, last)),
+        None => Ok(format!("/{} has no network or broadcast address", prefix_len)),
+    }
+}
+
+fn precheck_item(name: &str, result: Result<String, String>) -> PrecheckItemDto {
+    let passed = result.is_ok();
+    PrecheckItemDto {
+        name: name.to_string(),
+        passed,
+        detail: result.unwrap_or_else(|e| e),
+    }
+}
+
+#[async_trait]
+impl PrecheckStaticIpConfigUseCase for PrecheckStaticIpConfigUseCaseImpl {
+    async fn execute(&self, request: CreateStaticIpConfigRequest) -> StaticIpPrecheckResponse {
+        let new_config: NewStaticIpConfig = request.into();
+        let ip_address: Option<Ipv4Addr> = new_config.ip_address.trim().parse().ok();
+        let prefix_len = new_config
+            .resolve_subnet_mask()
+            .ok()
+            .and_then(|mask| mask_to_prefix_len(&mask));
+        let subnet = ip_address.zip(prefix_len);
+        let gateway: Option<Ipv4Addr> = new_config.gateway.trim().parse().ok();
+
+        let errors = self.network_service.validate_static_ip_config(&new_config);
+        let fields = if errors.is_empty() { Ok("All fields are valid".to_string()) } else { Err(errors.join("; ")) };
+        let interface_name = new_config.interface_name.trim();
+        let (interface_up, address_free, no_subnet_overlap) = tokio::join!(
+            self.interface_up(interface_name),
+            self.address_free(interface_name, ip_address),
+            self.no_subnet_overlap(&new_config, subnet),
+        );
+
+        let checks = vec![
+            precheck_item("fields", fields),
+            precheck_item("gateway_in_subnet", gateway_check(subnet, gateway)),
+            precheck_item("host_address", host_address_check(&new_config, subnet)),
+            precheck_item("interface_up", interface_up),
+            precheck_item("address_free", address_free),
+            precheck_item("no_subnet_overlap", no_subnet_overlap),
+        ];
+        StaticIpPrecheckResponse {
+            ok: checks.iter().all(|check| check.passed),
+            checks,
+        }
+    }
+}
+
+pub struct EnableStaticIpConfigUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    safe_mode: Arc<StaticIpSafeMode>,
+}
+
+impl EnableStaticIpConfigUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, safe_mode: Arc<StaticIpSafeMode>) -> Self {
+        Self { network_service, safe_mode }
+    }
+}
+
+#[async_trait]
+impl EnableStaticIpConfigUseCase for EnableStaticIpConfigUseCaseImpl {
+    async fn execute(&self, config_id: String, takeover: bool, force: bool) -> Result<ApplyResultResponse, NetworkError> {
+        // Remember what was enabled on the interface so safe mode can put it back
+        let configs = self.network_service.get_static_ip_configs().await?;
+        let target = configs.iter().find(|config| config.id == config_id);
+        let interface_name = target.map(|config| config.interface_name.clone());
+        // Only reached with force=true; without it the service refuses the overlap
+        let warnings = target
+            .map(|target| target.overlapping_enabled(&configs))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|config| format!("Subnet overlaps {}", config.describe_overlap()))
+            .collect();
+        let previous_ids = configs
+            .iter()
+            .filter(|config| config.is_enabled && config.id != config_id)
+            .filter(|config| Some(&config.interface_name) == interface_name.as_ref())
+            .map(|config| config.id.clone())
+            .collect();
+
+        let outcome = self.network_service.enable_static_ip(&config_id, takeover, force).await?;
+        let mut response = ApplyResultResponse::from(outcome);
+        response.warnings = warnings;
+        response.revert_at = self
+            .safe_mode
+            .schedule_revert(config_id, previous_ids)
+            .map(|revert_at| revert_at.to_rfc3339());
+        Ok(response)
+    }
+}
+
+pub struct ConfirmStaticIpConfigUseCaseImpl {
+    safe_mode: Arc<StaticIpSafeMode>,
+}
+
+impl ConfirmStaticIpConfigUseCaseImpl {
+    pub fn new(safe_mode: Arc<StaticIpSafeMode>) -> Self {
+        Self { safe_mode }
+    }
+}
+
+#[async_trait]
+impl ConfirmStaticIpConfigUseCase for ConfirmStaticIpConfigUseCaseImpl {
+    async fn execute(&self, config_id: String) -> Result<ConfirmStaticIpResponse, NetworkError> {
+        if self.safe_mode.confirm(&config_id) {
+            Ok(ConfirmStaticIpResponse { confirmed: true })
+        } else {
+            Err(NetworkError::NotFound(format!(
+                "No pending revert for static IP config {}",
+                config_id
+            )))
+        }
+    }
+}
+
+pub struct DisableStaticIpConfigUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl DisableStaticIpConfigUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl DisableStaticIpConfigUseCase for DisableStaticIpConfigUseCaseImpl {
+    async fn execute(&self, config_id: String) -> Result<ApplyResultResponse, String> {
+        let outcome = self.network_service.disable_static_ip(&config_id).await?;
+        Ok(outcome.into())
+    }
+}
+
+pub struct DeleteStaticIpConfigUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl DeleteStaticIpConfigUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl DeleteStaticIpConfigUseCase for DeleteStaticIpConfigUseCaseImpl {
+    async fn execute(&self, config_id: String) -> Result<(), String> {
+        self.network_service.delete_static_ip_config(&config_id).await
+    }
+}
+
+pub struct SetInterfaceAdminStateUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl SetInterfaceAdminStateUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl SetInterfaceAdminStateUseCase for SetInterfaceAdminStateUseCaseImpl {
+    async fn execute(&self, interface_name: String, request: SetInterfaceAdminStateRequest) -> Result<InterfaceAdminStateResponse, NetworkError> {
+        let (state, outcome) = self.network_service.set_interface_admin_state(interface_name, request.up).await?;
+        Ok(InterfaceAdminStateResponse {
+            state: state.into(),
+            applied: outcome.applied,
+            detail: outcome.detail,
+        })
+    }
+}
+
+pub struct GetInterfaceRateUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl GetInterfaceRateUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl GetInterfaceRateUseCase for GetInterfaceRateUseCaseImpl {
+    async fn execute(&self, interface_name: String) -> Result<InterfaceRateDto, NetworkError> {
+        // Two samples roughly a second apart
+        let rate = self.network_service
+            .get_interface_rate(&interface_name, std::time::Duration::from_secs(1))
+            .await?;
+        Ok(rate.into())
+    }
+}
+
+pub struct GetAllInterfaceStatsUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl GetAllInterfaceStatsUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl GetAllInterfaceStatsUseCase for GetAllInterfaceStatsUseCaseImpl {
+    async fn execute(&self) -> Result<std::collections::BTreeMap<String, InterfaceStatsDto>, String> {
+        let stats = self.network_service.get_all_interface_stats().await?;
+        Ok(stats.into_iter().map(|(name, stats)| (name, stats.into())).collect())
+    }
+}
+
+pub struct GetGroupedNetworkInterfacesUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl GetGroupedNetworkInterfacesUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl GetGroupedNetworkInterfacesUseCase for GetGroupedNetworkInterfacesUseCaseImpl {
+    async fn execute(&self, include_loopback: bool) -> Result<GroupedNetworkInterfacesResponse, String> {
+        let interfaces = self.network_service.get_network_interfaces().await?;
+        Ok(without_loopback(interfaces, include_loopback).into())
+    }
+}
+
+pub struct GetNetworkInterfaceUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl GetNetworkInterfaceUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl GetNetworkInterfaceUseCase for GetNetworkInterfaceUseCaseImpl {
+    async fn execute(&self, interface_name: String) -> Result<NetworkInterfaceDto, NetworkError> {
+        let interface = self.network_service.get_network_interface(&interface_name).await?;
+        Ok(interface.into())
+    }
+}
+
+pub struct GetWifiLinkStatusUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl GetWifiLinkStatusUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl GetWifiLinkStatusUseCase for GetWifiLinkStatusUseCaseImpl {
+    async fn execute(&self, interface_name: String) -> Result<WifiLinkStatusDto, NetworkError> {
+        let status = self.network_service.get_wifi_link_status(&interface_name).await?;
+        Ok(status.into())
+    }
+}
+
+// Signal of the configured wireless interface, read through the same `iw dev <name> link` parsing as wifi-link
+pub struct SampleWifiSignalUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    wifi_interface: String,
+}
+
+impl SampleWifiSignalUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, wifi_interface: String) -> Self {
+        Self { network_service, wifi_interface }
+    }
+}
+
+#[async_trait]
+impl SampleWifiSignalUseCase for SampleWifiSignalUseCaseImpl {
+    async fn execute(&self) -> WifiSignalSampleDto {
+        // A failed read is reported like a dropped link so pollers stop instead of spinning on errors
+        let link = self
+            .network_service
+            .get_wifi_link_status(&self.wifi_interface)
+            .await
+            .unwrap_or_default();
+        if !link.connected {
+            return WifiSignalSampleDto { connected: false, dbm: None, quality_percent: None, ts: None };
+        }
+        WifiSignalSampleDto {
+            connected: true,
+            dbm: link.signal_dbm,
+            quality_percent: link.signal_dbm.map(signal_quality_percent),
+            ts: Some(chrono::Utc::now()),
+        }
+    }
+}
+
+pub struct ExportNetplanUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    wifi_interface: String,
+}
+
+impl ExportNetplanUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, wifi_interface: String) -> Self {
+        Self { network_service, wifi_interface }
+    }
+}
+
+#[async_trait]
+impl ExportNetplanUseCase for ExportNetplanUseCaseImpl {
+    async fn execute(&self) -> Result<String, NetworkError> {
+        let static_configs = self.network_service.get_static_ip_configs().await?;
+        let active_wifi = self.network_service.get_active_wifi_config().await?;
+        Ok(render_netplan(&static_configs, active_wifi.as_ref(), &self.wifi_interface)?)
+    }
+}
+
+pub struct GetInterfaceOverviewUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    wifi_interface: String,
+}
+
+impl GetInterfaceOverviewUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, wifi_interface: String) -> Self {
+        Self { network_service, wifi_interface }
+    }
+}
+
+#[async_trait]
+impl GetInterfaceOverviewUseCase for GetInterfaceOverviewUseCaseImpl {
+    async fn execute(&self, include_loopback: bool) -> Result<Vec<InterfaceOverviewDto>, String> {
+        let interfaces = without_loopback(self.network_service.get_network_interfaces().await?, include_loopback);
+        let static_configs = self.network_service.get_static_ip_configs().await?;
+        let wifi_active = self.network_service.get_active_wifi_config().await?.is_some();
+
+        Ok(interfaces
+            .into_iter()
+            .map(|interface| {
+                // Prefer the enabled config when several target the same interface
+                let mut matching = static_configs.iter().filter(|c| c.interface_name == interface.name);
+                let static_config = matching
+                    .clone()
+                    .find(|c| c.is_enabled)
+                    .or_else(|| matching.next())
+                    .map(StaticIpConfigDto::from);
+                InterfaceOverviewDto {
+                    is_active_wifi_radio: wifi_active && interface.name == self.wifi_interface,
+                    static_config,
+                    interface: interface.into(),
+                }
+            })
+            .collect())
+    }
+}
+
+pub struct CreateNetworkProfileUseCaseImpl {
+    profile_service: Arc<dyn NetworkProfileService>,
+}
+
+impl CreateNetworkProfileUseCaseImpl {
+    pub fn new(profile_service: Arc<dyn NetworkProfileService>) -> Self {
+        Self { profile_service }
+    }
+}
+
+#[async_trait]
+impl CreateNetworkProfileUseCase for CreateNetworkProfileUseCaseImpl {
+    async fn execute(&self, request: CreateNetworkProfileRequest) -> Result<NetworkProfileResponse, NetworkError> {
+        let profile = self.profile_service
+            .create_profile(request.name, request.wifi_config_id, request.static_ip_config_id)
+            .await?;
+        Ok(NetworkProfileResponse {
+            profile: profile.into(),
+        })
+    }
+}
+
+pub struct ListNetworkProfilesUseCaseImpl {
+    profile_service: Arc<dyn NetworkProfileService>,
+}
+
+impl ListNetworkProfilesUseCaseImpl {
+    pub fn new(profile_service: Arc<dyn NetworkProfileService>) -> Self {
+        Self { profile_service }
+    }
+}
+
+#[async_trait]
+impl ListNetworkProfilesUseCase for ListNetworkProfilesUseCaseImpl {
+    async fn execute(&self) -> Result<NetworkProfilesListResponse, String> {
+        let mut profiles = self.profile_service.get_profiles().await?;
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(NetworkProfilesListResponse {
+            profiles: profiles.into_iter().map(|p| p.into()).collect(),
+        })
+    }
+}
+
+pub struct ActivateProfileUseCaseImpl {
+    profile_service: Arc<dyn NetworkProfileService>,
+}
+
+impl ActivateProfileUseCaseImpl {
+    pub fn new(profile_service: Arc<dyn NetworkProfileService>) -> Self {
+        Self { profile_service }
+    }
+}
+
+#[async_trait]
+impl ActivateProfileUseCase for ActivateProfileUseCaseImpl {
+    async fn execute(&self, profile_id: String) -> Result<ActivateProfileResponse, NetworkError> {
+        let activation = self.profile_service.activate_profile(&profile_id).await?;
+        Ok(ActivateProfileResponse {
+            profile: activation.profile.into(),
+            wifi: activation.wifi.into(),
+            static_ip: activation.static_ip.map(|outcome| outcome.into()),
+        })
+    }
+}
+
+pub struct ProbeIpUseCaseImpl {
+    prober: Arc<dyn IpProber>,
+}
+
+impl ProbeIpUseCaseImpl {
+    const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+    pub fn new(prober: Arc<dyn IpProber>) -> Self {
+        Self { prober }
+    }
+}
+
+#[async_trait]
+impl ProbeIpUseCase for ProbeIpUseCaseImpl {
+    async fn execute(&self, request: ProbeIpRequest) -> Result<ProbeIpResponse, NetworkError> {
+        if request.interface_name.trim().is_empty() {
+            return Err(NetworkError::Validation("interface_name is required".to_string()));
+        }
+        validate_interface_name(&request.interface_name)?;
+        let ip_address: std::net::Ipv4Addr = request.ip_address.parse().map_err(|_| {
+            NetworkError::Validation(format!("Invalid IPv4 address: {}", request.ip_address))
+        })?;
+
+        let in_use = tokio::time::timeout(
+            Self::PROBE_TIMEOUT,
+            self.prober.probe(&request.interface_name, ip_address),
+        )
+        .await
+        .map_err(|_| NetworkError::Timeout(format!("Probe of {} timed out", ip_address)))??;
+
+        Ok(ProbeIpResponse {
+            interface_name: request.interface_name,
+            ip_address: ip_address.to_string(),
+            in_use,
+            method: self.prober.method().to_string(),
+        })
+    }
+}
+
+pub struct GetAddressConflictsUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl GetAddressConflictsUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl GetAddressConflictsUseCase for GetAddressConflictsUseCaseImpl {
+    async fn execute(&self) -> Result<AddressConflictsResponse, NetworkError> {
+        let interfaces = self.network_service.get_network_interfaces().await?;
+        let static_configs = self.network_service.get_static_ip_configs().await?;
+        Ok(AddressConflictsResponse {
+            conflicts: AddressConflict::detect(&interfaces, &static_configs)
+                .into_iter()
+                .map(AddressConflictDto::from)
+                .collect(),
+        })
+    }
+}
+
+pub struct GetDnsLatencyUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    prober: Arc<dyn DnsProber>,
+}
+
+impl GetDnsLatencyUseCaseImpl {
+    pub const DEFAULT_HOSTNAME: &'static str = "example.com";
+    pub const DEFAULT_SAMPLES: usize = 3;
+    // Each sample can take up to QUERY_TIMEOUT, so this bounds how long a request runs
+    pub const MAX_SAMPLES: usize = 10;
+    const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, prober: Arc<dyn DnsProber>) -> Self {
+        Self { network_service, prober }
+    }
+
+    // DNS servers of the enabled static IP configs in resolver order, or the system's if none is enabled
+    async fn servers(&self) -> Result<(&'static str, Vec<std::net::IpAddr>), NetworkError> {
+        let mut servers = Vec::new();
+        for config in self.network_service.get_static_ip_configs().await? {
+            if config.is_enabled {
+                for server in config.dns_servers() {
+                    if !servers.contains(&server) {
+                        servers.push(server);
+                    }
+                }
+            }
+        }
+        if !servers.is_empty() {
+            return Ok(("static_ip", servers));
+        }
+        Ok(("system", self.prober.system_resolvers().await?))
+    }
+
+    // Samples of one server run one after another, so they don't compete with each other
+    async fn measure(&self, server: std::net::IpAddr, hostname: &str, samples: usize) -> DnsLatency {
+        let mut results = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            results.push(self.prober.query(server, hostname, Self::QUERY_TIMEOUT).await);
+        }
+        DnsLatency::from_samples(server, results)
+    }
+}
+
+#[async_trait]
+impl GetDnsLatencyUseCase for GetDnsLatencyUseCaseImpl {
+    async fn execute(&self, query: DnsLatencyQuery) -> Result<DnsLatencyResponse, NetworkError> {
+        let hostname = query.hostname.unwrap_or_else(|| Self::DEFAULT_HOSTNAME.to_string());
+        validate_domain_name(&hostname)
+            .map_err(|e| NetworkError::Validation(format!("Invalid hostname {:?}: {}", hostname, e)))?;
+        let samples = query.samples.unwrap_or(Self::DEFAULT_SAMPLES);
+        if samples == 0 || samples > Self::MAX_SAMPLES {
+            return Err(NetworkError::Validation(format!("samples must be between 1 and {}", Self::MAX_SAMPLES)));
+        }
+
+        let (source, servers) = self.servers().await?;
+        let latencies = futures_util::future::join_all(
+            servers.into_iter().map(|server| self.measure(server, &hostname, samples)),
+        )
+        .await;
+        Ok(DnsLatencyResponse {
+            hostname,
+            samples,
+            source: source.to_string(),
+            servers: latencies.into_iter().map(DnsServerLatencyDto::from).collect(),
+        })
+    }
+}
+
+pub struct GetDefaultRouteUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl GetDefaultRouteUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+#[async_trait]
+impl GetDefaultRouteUseCase for GetDefaultRouteUseCaseImpl {
+    async fn execute(&self) -> Result<DefaultRouteDto, String> {
+        let route = self.network_service.get_default_route().await?;
+        Ok(route.into())
+    }
+}
+
+pub struct ScanWifiNetworksUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    scan_cache: Option<Arc<WifiScanCache>>,
+}
+
+impl ScanWifiNetworksUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service, scan_cache: None }
+    }
+
+    // Also keep each result for the settings page, so a manual scan counts as the latest one
+    pub fn with_scan_cache(mut self, scan_cache: Arc<WifiScanCache>) -> Self {
+        self.scan_cache = Some(scan_cache);
+        self
+    }
+}
+
+#[async_trait]
+impl ScanWifiNetworksUseCase for ScanWifiNetworksUseCaseImpl {
+    // The cache keeps the whole scan; the query's filters only shape this response
+    async fn execute(&self, query: &ScanWifiNetworksQuery) -> Result<WifiScanResultDto, NetworkError> {
+        let started_at = chrono::Utc::now();
+        let started = std::time::Instant::now();
+        let (scanned_at, networks, cached) = match self.network_service.scan_wifi_networks().await {
+            Ok(networks) => {
+                if let Some(scan_cache) = &self.scan_cache {
+                    scan_cache.store(started_at, networks.clone());
+                }
+                (started_at, networks, false)
+            }
+            // A scan that ran out of time falls back to the last one that finished, if any
+            Err(NetworkError::Timeout(message)) => {
+                match self.scan_cache.as_ref().and_then(|scan_cache| scan_cache.latest_scan()) {
+                    Some((scanned_at, networks)) => {
+                        tracing::warn!("{}; returning the scan from {}", message, scanned_at.to_rfc3339());
+                        (scanned_at, networks, true)
+                    }
+                    None => return Err(NetworkError::Timeout(message)),
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(WifiScanResultDto {
+            scanned_at: scanned_at.to_rfc3339(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            cached,
+            networks: networks
+                .into_iter()
+                .filter(|n| !(query.hide_hidden && n.is_hidden()))
+                .filter(|n| query.min_signal.is_none_or(|min| n.signal_level.is_some_and(|level| level >= min)))
+                .map(|n| n.into())
+                .collect(),
+        })
+    }
+}
+
+// Everything worth an operator's attention in one report, built from the same lists the
+// WiFi and static IP endpoints return
+pub struct GetNetworkAuditUseCaseImpl {
+    list_wifi_configs: Arc<dyn ListWifiConfigsUseCase>,
+    list_static_ip_configs: Arc<dyn ListStaticIpConfigsUseCase>,
+}
+
+impl GetNetworkAuditUseCaseImpl {
+    pub fn new(
+        list_wifi_configs: Arc<dyn ListWifiConfigsUseCase>,
+        list_static_ip_configs: Arc<dyn ListStaticIpConfigsUseCase>,
+    ) -> Self {
+        Self { list_wifi_configs, list_static_ip_configs }
+    }
+}
+
+#[async_trait]
+impl GetNetworkAuditUseCase for GetNetworkAuditUseCaseImpl {
+    async fn execute(&self) -> Result<NetworkAuditResponse, NetworkError> {
+        let wifi_configs = self.list_wifi_configs.execute(ListWifiConfigsQuery { security: None }).await?.configs;
+        let static_ip_configs = self.list_static_ip_configs.execute().await?.configs;
+
+        let mut errored = Vec::new();
+        for config in &wifi_configs {
+            if let Some(last_error) = &config.last_error {
+                errored.push(AuditErroredConfigDto {
+                    kind: "wifi",
+                    id: config.id.clone(),
+                    last_error: last_error.clone(),
+                    last_error_at: config.last_error_at.clone(),
+                });
+            }
+        }
+        for item in &static_ip_configs {
+            if let Some(last_error) = &item.config.last_error {
+                errored.push(AuditErroredConfigDto {
+                    kind: "static_ip",
+                    id: item.config.id.clone(),
+                    last_error: last_error.clone(),
+                    last_error_at: item.config.last_error_at.clone(),
+                });
+            }
+        }
+
+        let mut by_ssid: std::collections::BTreeMap<&str, Vec<String>> = std::collections::BTreeMap::new();
+        for config in &wifi_configs {
+            by_ssid.entry(config.ssid.as_str()).or_default().push(config.id.clone());
+        }
+        let duplicate_ssids = by_ssid
+            .into_iter()
+            .filter(|(_, config_ids)| config_ids.len() > 1)
+            .map(|(ssid, config_ids)| DuplicateSsidDto { ssid: ssid.to_string(), config_ids })
+            .collect();
+
+        Ok(NetworkAuditResponse {
+            duplicate_ssids,
+            errored,
+            insecure_wifi: wifi_configs
+                .into_iter()
+                .filter(|config| matches!(config.security_type, WifiSecurityType::Open | WifiSecurityType::WEP))
+                .collect(),
+            orphaned_static_ip: static_ip_configs
+                .into_iter()
+                .filter(|item| !item.interface_present)
+                .map(|item| item.config)
+                .collect(),
+        })
+    }
+}
+
+// Two phases: every item is parsed and checked first, and only a batch without a single error is
+// handed to the service to save, so a bad item never leaves the import half-applied
+pub struct ImportConfigsUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+}
+
+impl ImportConfigsUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self { network_service }
+    }
+}
+
+// Parse and check each item of one list; an id repeated within the list is an error on the later item
+fn check_import_items<D, T>(
+    kind: &'static str,
+    items: Vec<D>,
+    id_of: impl Fn(&D) -> String,
+    parse: impl Fn(D) -> Result<T, String>,
+    validate: impl Fn(&T) -> Vec<String>,
+    errors: &mut Vec<ImportItemErrorDto>,
+) -> Vec<T> {
+    let mut seen = std::collections::HashSet::new();
+    let mut parsed = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let id = id_of(&item);
+        let mut item_errors = Vec::new();
+        if !seen.insert(id.clone()) {
+            item_errors.push(format!("id {} appears more than once", id));
+        }
+        match parse(item) {
+            Ok(config) => {
+                item_errors.extend(validate(&config));
+                parsed.push(config);
+            }
+            Err(e) => item_errors.push(e),
+        }
+        if !item_errors.is_empty() {
+            errors.push(ImportItemErrorDto { kind, index, id, errors: item_errors });
+        }
+    }
+    parsed
+}
+
+#[async_trait]
+impl ImportConfigsUseCase for ImportConfigsUseCaseImpl {
+    async fn execute(&self, request: ImportConfigsRequest) -> Result<ImportConfigsResponse, NetworkError> {
+        let mut errors = Vec::new();
+        let wifi_configs = check_import_items(
+            "wifi",
+            request.wifi_configs,
+            |dto| dto.id.clone(),
+            WifiConfig::try_from,
+            WifiConfig::validate,
+            &mut errors,
+        );
+        let static_ip_configs = check_import_items(
+            "static_ip",
+            request.static_ip_configs,
+            |dto| dto.id.clone(),
+            StaticIpConfig::try_from,
+            StaticIpConfig::validate,
+            &mut errors,
+        );
+        if !errors.is_empty() {
+            return Ok(ImportConfigsResponse {
+                imported: false,
+                created: 0,
+                updated: 0,
+                errors,
+            });
+        }
+
+        let report = self.network_service.import_configs(wifi_configs, static_ip_configs).await?;
+        Ok(ImportConfigsResponse {
+            imported: true,
+            created: report.created,
+            updated: report.updated,
+            errors,
+        })
+    }
+}
+
+// Re-apply every stored config, then restart the networking services so they start from the
+// written files. One restart at a time; a second request while one runs is refused.
+pub struct RestartNetworkingUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    restarter: Arc<dyn ServiceRestarter>,
+    units: Vec<String>,
+    // Off when nothing is pushed to the system, so the call is reported as a no-op
+    enabled: bool,
+    running: tokio::sync::Mutex<()>,
+}
+
+impl RestartNetworkingUseCaseImpl {
+    pub fn new(
+        network_service: Arc<dyn NetworkConfigService>,
+        restarter: Arc<dyn ServiceRestarter>,
+        wifi_interface: &str,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            network_service,
+            restarter,
+            units: vec![
+                "systemd-networkd".to_string(),
+                format!("wpa_supplicant@{}", wifi_interface),
+            ],
+            enabled,
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl RestartNetworkingUseCase for RestartNetworkingUseCaseImpl {
+    async fn execute(&self) -> Result<RestartNetworkingResponse, NetworkError> {
+        if !self.enabled {
+            return Ok(RestartNetworkingResponse {
+                performed: false,
+                applied: Vec::new(),
+                restarted: Vec::new(),
+                failures: Vec::new(),
+                detail: Some("system apply disabled; nothing was re-applied or restarted".to_string()),
+            });
+        }
+        let Ok(_running) = self.running.try_lock() else {
+            return Err(NetworkError::Conflict("A networking restart is already in progress".to_string()));
+        };
+
+        let mut failures = self.network_service.reconcile_interface_admin_states().await?;
+        let report = self.network_service.reconcile_configs().await?;
+        failures.extend(report.failures);
+
+        let mut restarted = Vec::new();
+        for unit in &self.units {
+            match self.restarter.restart(unit).await {
+                Ok(_) => restarted.push(unit.clone()),
+                Err(e) => failures.push(format!("restarting {}: {}", unit, e)),
+            }
+        }
+        for failure in &failures {
+            tracing::warn!("Networking restart: {}", failure);
+        }
+
+        Ok(RestartNetworkingResponse {
+            performed: true,
+            applied: report.applied,
+            restarted,
+            failures,
+            detail: None,
+        })
+    }
+}
+
+pub struct GetEventHistoryUseCaseImpl {
+    event_history: Arc<dyn EventHistoryRepository>,
+}
+
+impl GetEventHistoryUseCaseImpl {
+    pub const DEFAULT_LIMIT: usize = 100;
+    pub const MAX_LIMIT: usize = 1000;
+
+    pub fn new(event_history: Arc<dyn EventHistoryRepository>) -> Self {
+        Self { event_history }
+    }
+}
+
+#[async_trait]
+impl GetEventHistoryUseCase for GetEventHistoryUseCaseImpl {
+    async fn execute(&self, query: EventHistoryQuery) -> Result<EventHistoryResponse, NetworkError> {
+        let since = match query.since.as_deref() {
+            Some(since) => Some(
+                chrono::DateTime::parse_from_rfc3339(since)
+                    .map_err(|_| NetworkError::Validation(format!("Invalid since timestamp: {}", since)))?
+                    .with_timezone(&chrono::Utc),
+            ),
+            None => None,
+        };
+        let event_types = match query.event_type.as_deref() {
+            Some(event_type) => Some(
+                event_type
+                    .split(',')
+                    .filter(|value| !value.trim().is_empty())
+                    .map(|value| value.parse::<HistoryEventType>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(NetworkError::Validation)?,
+            ),
+            None => None,
+        };
+        let limit = query.limit.unwrap_or(Self::DEFAULT_LIMIT);
+        if limit == 0 || limit > Self::MAX_LIMIT {
+            return Err(NetworkError::Validation(format!(
+                "limit must be between 1 and {}",
+                Self::MAX_LIMIT
+            )));
+        }
+
+        let filter = EventHistoryFilter { since, event_types, limit };
+        let events = self.event_history.query(&filter).await?;
+        Ok(EventHistoryResponse {
+            events: events.into_iter().map(|event| event.into()).collect(),
+        })
+    }
+}
+
+pub struct GetNetworkPreferencesUseCaseImpl {
+    settings_repository: Arc<dyn SettingsRepository>,
+}
+
+impl GetNetworkPreferencesUseCaseImpl {
+    pub fn new(settings_repository: Arc<dyn SettingsRepository>) -> Self {
+        Self { settings_repository }
+    }
+}
+
+#[async_trait]
+impl GetNetworkPreferencesUseCase for GetNetworkPreferencesUseCaseImpl {
+    async fn execute(&self) -> Result<NetworkPreferencesDto, String> {
+        Ok(NetworkPreferencesDto {
+            preferred_interface: self.settings_repository.get(PREFERRED_INTERFACE_KEY).await?,
+        })
+    }
+}
+
+pub struct SetNetworkPreferencesUseCaseImpl {
+    settings_repository: Arc<dyn SettingsRepository>,
+}
+
+impl SetNetworkPreferencesUseCaseImpl {
+    pub fn new(settings_repository: Arc<dyn SettingsRepository>) -> Self {
+        Self { settings_repository }
+    }
+}
+
+#[async_trait]
+impl SetNetworkPreferencesUseCase for SetNetworkPreferencesUseCaseImpl {
+    // The interface does not have to exist yet, e.g. a USB adapter that is unplugged right now
+    async fn execute(&self, request: NetworkPreferencesDto) -> Result<NetworkPreferencesDto, NetworkError> {
+        let preferred_interface = request
+            .preferred_interface
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+        if let Some(name) = &preferred_interface {
+            validate_interface_name(name)?;
+        }
+        self.settings_repository
+            .set(PREFERRED_INTERFACE_KEY, preferred_interface.clone())
+            .await?;
+        Ok(NetworkPreferencesDto { preferred_interface })
+    }
+}
+
+pub struct GetCurrentNetworkUseCaseImpl {
+    network_service: Arc<dyn NetworkConfigService>,
+    settings_repository: Arc<dyn SettingsRepository>,
+}
+
+impl GetCurrentNetworkUseCaseImpl {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, settings_repository: Arc<dyn SettingsRepository>) -> Self {
+        Self { network_service, settings_repository }
+    }
+}
+
+#[async_trait]
+impl GetCurrentNetworkUseCase for GetCurrentNetworkUseCaseImpl {
+    async fn execute(&self) -> Result<CurrentNetworkDto, String> {
+        let preferred_interface = self.settings_repository.get(PREFERRED_INTERFACE_KEY).await?;
+        let interfaces = self.network_service.get_network_interfaces().await?;
+        let default_route = self.network_service.get_default_route().await?;
+        let active_wifi = self.network_service.get_active_wifi_config().await?;
+
+        let primary = select_primary_interface(&interfaces, preferred_interface.as_deref(), &default_route);
+        // The route's gateway only describes the primary interface when it is the route's interface
+        let gateway = primary
+            .filter(|(interface, _)| default_route.interface.as_deref() == Some(interface.name.as_str()))
+            .and_then(|_| default_route.gateway.clone());
+        Ok(CurrentNetworkDto {
+            selected_by: primary.map(|(_, source)| source),
+            interface: primary.map(|(interface, _)| interface.clone().into()),
+            preferred_interface,
+            gateway,
+            active_wifi_ssid: active_wifi.map(|config| config.ssid),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::network_entities::{ConfigLimits, NewWifiConfig, ScannedWifiNetwork, WifiLinkStatus};
+    use crate::domain::network_repositories::{StaticIpConfigRepository, WifiScanner};
+    use crate::domain::network_services::NetworkConfigServiceImpl;
+    use crate::infrastructure::simulation::SimulationFixture;
+    use crate::test_support::*;
+
+    async fn create_wifi(service: &Arc<NetworkConfigServiceImpl>, ssid: &str, security_type: WifiSecurityType, password: &str) -> WifiConfig {
+        service
+            .create_wifi_config(NewWifiConfig {
+                security_type,
+                password: password.to_string(),
+                ..wifi(ssid)
+            })
+            .await
+            .unwrap()
+    }
+
+    fn ssids(response: &WifiConfigsListResponse) -> Vec<&str> {
+        let mut ssids: Vec<&str> = response.configs.iter().map(|config| config.ssid.as_str()).collect();
+        ssids.sort();
+        ssids
+    }
+
+    #[tokio::test]
+    async fn wifi_configs_are_filtered_by_security_type() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        create_wifi(&service, "Cafe", WifiSecurityType::Open, "").await;
+        create_wifi(&service, "Legacy", WifiSecurityType::WEP, "abcde").await;
+        create_wifi(&service, "Home", WifiSecurityType::WPA2, "correct horse").await;
+        let use_case = ListWifiConfigsUseCaseImpl::new(service);
+
+        let query = |security: Option<&str>| ListWifiConfigsQuery { security: security.map(str::to_string) };
+        assert_eq!(ssids(&use_case.execute(query(Some("Open,WEP"))).await.unwrap()), vec!["Cafe", "Legacy"]);
+        assert_eq!(ssids(&use_case.execute(query(Some("WPA2"))).await.unwrap()), vec!["Home"]);
+        assert_eq!(ssids(&use_case.execute(query(None)).await.unwrap()), vec!["Cafe", "Home", "Legacy"]);
+        assert_eq!(ssids(&use_case.execute(query(Some(""))).await.unwrap()), vec!["Cafe", "Home", "Legacy"]);
+        assert_eq!(ssids(&use_case.execute(query(Some(" , "))).await.unwrap()), vec!["Cafe", "Home", "Legacy"]);
+    }
+
+    #[tokio::test]
+    async fn connect_best_picks_the_saved_network_in_range() {
+        // In range: HomeNetwork (-45 dBm, not saved) and Neighbor (-71 dBm)
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let neighbor = service.create_wifi_config(wifi("Neighbor")).await.unwrap();
+        service.create_wifi_config(wifi("Office")).await.unwrap();
+
+        let response = ConnectBestWifiUseCaseImpl::new(service.clone()).execute().await.unwrap();
+        assert_eq!(response.ssid, "Neighbor");
+        assert_eq!(response.config_id, neighbor.id);
+        assert_eq!(response.signal_level, Some(-71));
+        let active = service.get_active_wifi_config().await.unwrap().unwrap();
+        assert_eq!(active.id, neighbor.id);
+    }
+
+    #[tokio::test]
+    async fn connect_best_without_a_saved_network_in_range_is_not_found() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        service.create_wifi_config(wifi("Office")).await.unwrap();
+
+        let result = ConnectBestWifiUseCaseImpl::new(service.clone()).execute().await;
+        assert!(matches!(result, Err(NetworkError::NotFound(_))));
+        assert!(network.applier.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unknown_security_type_is_a_validation_error() {
+        let use_case = ListWifiConfigsUseCaseImpl::new(Arc::new(TestNetwork::new().service()));
+        let result = use_case.execute(ListWifiConfigsQuery { security: Some("WPA2,WPA9".to_string()) }).await;
+        assert!(matches!(result, Err(NetworkError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn interface_overview_joins_static_configs_and_the_wifi_radio() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        let enabled = service.create_static_ip_config(static_ip("eth0", "192.168.1.61")).await.unwrap();
+        service.enable_static_ip(&enabled.id, false, false).await.unwrap();
+        let home = create_wifi(&service, "Home", WifiSecurityType::WPA2, "correct horse").await;
+        service.activate_wifi_config(&home.id).await.unwrap();
+
+        let overview = GetInterfaceOverviewUseCaseImpl::new(service, "wlan0".to_string())
+            .execute(false)
+            .await
+            .unwrap();
+        let mut rows: Vec<(&str, Option<&str>, bool)> = overview
+            .iter()
+            .map(|row| {
+                (row.interface.name.as_str(), row.static_config.as_ref().map(|config| config.id.as_str()), row.is_active_wifi_radio)
+            })
+            .collect();
+        // The enabled config wins over the other one for eth0; wlan0 has none; lo is left out
+        rows.sort();
+        assert_eq!(rows, vec![("eth0", Some(enabled.id.as_str()), false), ("wlan0", None, true)]);
+    }
+
+    // Finds `networks` after `delay`, which tests can change between scans
+    struct SlowScanner {
+        delay: std::sync::Mutex<std::time::Duration>,
+        networks: Vec<ScannedWifiNetwork>,
+    }
+
+    #[async_trait]
+    impl WifiScanner for SlowScanner {
+        async fn scan(&self) -> Result<Vec<ScannedWifiNetwork>, String> {
+            let delay = *self.delay.lock().unwrap();
+            tokio::time::sleep(delay).await;
+            Ok(self.networks.clone())
+        }
+    }
+
+    fn scan_query() -> ScanWifiNetworksQuery {
+        serde_json::from_value(serde_json::json!({})).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_scan_times_out_and_falls_back_to_the_last_scan() {
+        let scanner = Arc::new(SlowScanner {
+            delay: std::sync::Mutex::new(std::time::Duration::from_secs(1)),
+            networks: SimulationFixture::default().networks,
+        });
+        let mut network = TestNetwork::new();
+        network.scanner = scanner.clone();
+        let service: Arc<dyn NetworkConfigService> =
+            Arc::new(network.service().with_scan_timeout(std::time::Duration::from_secs(10)));
+        let scan_cache = Arc::new(WifiScanCache::new(service.clone()));
+        let use_case = ScanWifiNetworksUseCaseImpl::new(service.clone()).with_scan_cache(scan_cache);
+
+        let first = use_case.execute(&scan_query()).await.unwrap();
+        assert!(!first.cached);
+        assert_eq!(first.networks.len(), 3);
+
+        // A hung adapter: the timeout ends the wait and the previous scan comes back instead
+        *scanner.delay.lock().unwrap() = std::time::Duration::from_secs(30);
+        let started = tokio::time::Instant::now();
+        let fallback = use_case.execute(&scan_query()).await.unwrap();
+        assert_eq!(started.elapsed(), std::time::Duration::from_secs(10));
+        assert!(fallback.cached);
+        assert_eq!(fallback.scanned_at, first.scanned_at);
+        assert_eq!(fallback.networks.len(), 3);
+
+        // With nothing to fall back on the timeout is the answer
+        let uncached = ScanWifiNetworksUseCaseImpl::new(service).execute(&scan_query()).await;
+        assert!(matches!(uncached, Err(NetworkError::Timeout(_))));
+    }
+
+    // Answers for `reachable`; never answers at all when `hang` is set
+    struct ScriptedProber {
+        reachable: Vec<std::net::Ipv4Addr>,
+        hang: bool,
+    }
+
+    #[async_trait]
+    impl IpProber for ScriptedProber {
+        fn method(&self) -> &'static str {
+            "scripted"
+        }
+
+        async fn probe(&self, _interface_name: &str, ip_address: std::net::Ipv4Addr) -> Result<bool, String> {
+            if self.hang {
+                std::future::pending::<()>().await;
+            }
+            Ok(self.reachable.contains(&ip_address))
+        }
+    }
+
+    fn probe_request(ip_address: &str) -> ProbeIpRequest {
+        ProbeIpRequest { interface_name: "eth0".to_string(), ip_address: ip_address.to_string() }
+    }
+
+    #[tokio::test]
+    async fn probe_reports_whether_the_address_answers() {
+        let prober = ScriptedProber { reachable: vec!["192.168.1.20".parse().unwrap()], hang: false };
+        let use_case = ProbeIpUseCaseImpl::new(Arc::new(prober));
+
+        let reachable = use_case.execute(probe_request("192.168.1.20")).await.unwrap();
+        assert!(reachable.in_use);
+        assert_eq!(reachable.method, "scripted");
+        assert!(!use_case.execute(probe_request("192.168.1.21")).await.unwrap().in_use);
+        assert!(matches!(use_case.execute(probe_request("192.168.1")).await, Err(NetworkError::Validation(_))));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn probe_without_an_answer_times_out() {
+        let use_case = ProbeIpUseCaseImpl::new(Arc::new(ScriptedProber { reachable: Vec::new(), hang: true }));
+        let started = tokio::time::Instant::now();
+        assert!(matches!(use_case.execute(probe_request("192.168.1.20")).await, Err(NetworkError::Timeout(_))));
+        assert_eq!(started.elapsed(), ProbeIpUseCaseImpl::PROBE_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn static_ip_on_a_down_wireless_interface_is_saved_with_a_warning() {
+        use crate::domain::network_repositories::{NetworkInterfaceRepository, StaticIpConfigRepository};
+
+        let network = TestNetwork::new();
+        network.interfaces.set_link_state("wlan0", false).await.unwrap();
+        let use_case = CreateStaticIpConfigUseCaseImpl::new(Arc::new(network.service()));
+        let request = |interface_name: &str, ip_address: &str| -> CreateStaticIpConfigRequest {
+            serde_json::from_value(serde_json::json!({
+                "interface_name": interface_name,
+                "ip_address": ip_address,
+                "prefix_len": 24,
+                "gateway": "192.168.1.1",
+                "dns_primary": "1.1.1.1",
+                "dns_secondary": null,
+            }))
+            .unwrap()
+        };
+
+        let response = use_case.execute(request("wlan0", "192.168.1.70")).await.unwrap();
+        assert_eq!(response.warnings.len(), 1);
+        assert!(response.warnings[0].contains("wlan0 is down"), "{}", response.warnings[0]);
+        assert!(network.static_ip_repository.find_by_id(&response.config.id).await.unwrap().is_some());
+
+        let response = use_case.execute(request("eth0", "192.168.1.71")).await.unwrap();
+        assert!(response.warnings.is_empty());
+    }
+
+    fn associated_with(ssid: &str) -> TestNetwork {
+        TestNetwork::with_fixture(SimulationFixture {
+            wifi_link: WifiLinkStatus { connected: true, ssid: Some(ssid.to_string()), ..Default::default() },
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn switch_marks_the_new_network_active_once_it_associates() {
+        let network = associated_with("Home");
+        let service = Arc::new(network.service());
+        let old = create_wifi(&service, "Old", WifiSecurityType::WPA2, "correct horse").await;
+        service.activate_wifi_config(&old.id).await.unwrap();
+        let home = create_wifi(&service, "Home", WifiSecurityType::WPA2, "correct horse").await;
+
+        let use_case = SwitchWifiUseCaseImpl::new(service.clone(), "wlan0".to_string(), true);
+        let response = use_case.execute(SwitchWifiRequest { to_id: home.id.clone() }).await.unwrap();
+        assert!(response.verified);
+        assert_eq!(service.get_active_wifi_config().await.unwrap().unwrap().id, home.id);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn switch_reverts_when_the_new_network_does_not_associate() {
+        let network = associated_with("Old");
+        let service = Arc::new(network.service());
+        let old = create_wifi(&service, "Old", WifiSecurityType::WPA2, "correct horse").await;
+        service.activate_wifi_config(&old.id).await.unwrap();
+        let other = create_wifi(&service, "Other", WifiSecurityType::WPA2, "correct horse").await;
+
+        let use_case = SwitchWifiUseCaseImpl::new(service.clone(), "wlan0".to_string(), true);
+        match use_case.execute(SwitchWifiRequest { to_id: other.id.clone() }).await {
+            Err(NetworkError::Timeout(message)) => assert!(message.contains("reverted to Old"), "{}", message),
+            other => panic!("expected a timeout, got {:?}", other),
+        }
+        let calls = network.applier.calls();
+        assert_eq!(calls[calls.len() - 2..], [format!("apply wifi {}", other.id), format!("apply wifi {}", old.id)]);
+        assert_eq!(service.get_active_wifi_config().await.unwrap().unwrap().id, old.id);
+    }
+
+    #[tokio::test]
+    async fn static_ip_list_reports_whether_each_interface_exists() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let present = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        let missing = service.create_static_ip_config(static_ip("usb0", "192.168.7.60")).await.unwrap();
+
+        let response = ListStaticIpConfigsUseCaseImpl::new(service).execute().await.unwrap();
+        let mut flags: Vec<(String, bool)> =
+            response.configs.iter().map(|item| (item.config.id.clone(), item.interface_present)).collect();
+        flags.sort();
+        let mut expected = vec![(present.id, true), (missing.id, false)];
+        expected.sort();
+        assert_eq!(flags, expected);
+    }
+
+    #[tokio::test]
+    async fn restart_reapplies_configs_then_restarts_the_units() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let config = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        service.enable_static_ip(&config.id, false, false).await.unwrap();
+        let before = network.applier.calls().len();
+
+        let use_case = RestartNetworkingUseCaseImpl::new(service, network.applier.clone(), "wlan0", true);
+        let response = use_case.execute().await.unwrap();
+        assert!(response.performed);
+        assert!(response.failures.is_empty(), "{:?}", response.failures);
+        assert_eq!(
+            network.applier.calls()[before..],
+            [
+                format!("apply static {}", config.id),
+                "restart systemd-networkd".to_string(),
+                "restart wpa_supplicant@wlan0".to_string(),
+            ]
+        );
+        assert_eq!(response.restarted, vec!["systemd-networkd", "wpa_supplicant@wlan0"]);
+    }
+
+    #[tokio::test]
+    async fn restart_without_system_apply_does_nothing() {
+        let network = TestNetwork::new();
+        let use_case = RestartNetworkingUseCaseImpl::new(Arc::new(network.service()), network.applier.clone(), "wlan0", false);
+        assert!(!use_case.execute().await.unwrap().performed);
+        assert!(network.applier.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn audit_sorts_configs_into_categories() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let cafe = create_wifi(&service, "Cafe", WifiSecurityType::Open, "").await;
+        let legacy = create_wifi(&service, "Legacy", WifiSecurityType::WEP, "abcde").await;
+        let home = create_wifi(&service, "Home", WifiSecurityType::WPA2, "correct horse").await;
+        let home_again = create_wifi(&service, "Home", WifiSecurityType::WPA3, "correct horse").await;
+        let orphan = service.create_static_ip_config(static_ip("usb0", "192.168.7.60")).await.unwrap();
+        let failing = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        network.applier.set_failure(Some("ip addr add failed"));
+        assert!(service.enable_static_ip(&failing.id, false, false).await.is_err());
+
+        let use_case = GetNetworkAuditUseCaseImpl::new(
+            Arc::new(ListWifiConfigsUseCaseImpl::new(service.clone())),
+            Arc::new(ListStaticIpConfigsUseCaseImpl::new(service.clone())),
+        );
+        let audit = use_case.execute().await.unwrap();
+        let mut insecure: Vec<&str> = audit.insecure_wifi.iter().map(|config| config.id.as_str()).collect();
+        insecure.sort();
+        let mut expected = vec![cafe.id.as_str(), legacy.id.as_str()];
+        expected.sort();
+        assert_eq!(insecure, expected);
+        let orphaned: Vec<&str> = audit.orphaned_static_ip.iter().map(|config| config.id.as_str()).collect();
+        assert_eq!(orphaned, vec![orphan.id.as_str()]);
+        let errored: Vec<(&str, &str, &str)> = audit
+            .errored
+            .iter()
+            .map(|config| (config.kind, config.id.as_str(), config.last_error.as_str()))
+            .collect();
+        assert_eq!(errored, vec![("static_ip", failing.id.as_str(), "ip addr add failed")]);
+        assert_eq!(audit.duplicate_ssids.len(), 1);
+        assert_eq!(audit.duplicate_ssids[0].ssid, "Home");
+        let mut duplicates = audit.duplicate_ssids[0].config_ids.clone();
+        duplicates.sort();
+        let mut expected = vec![home.id, home_again.id];
+        expected.sort();
+        assert_eq!(duplicates, expected);
+    }
+
+    #[tokio::test]
+    async fn scan_filters_drop_weak_and_hidden_networks() {
+        let mut fixture = SimulationFixture::default();
+        let extra = |ssid: &str, signal_raw: &str| ScannedWifiNetwork {
+            ssid: ssid.to_string(),
+            signal_raw: signal_raw.to_string(),
+            ..fixture.networks[0].clone()
+        };
+        let (hidden, unreadable) = (extra("\\x00\\x00", "-60"), extra("Flaky", "n/a"));
+        fixture.networks.extend([hidden, unreadable]);
+        let service: Arc<dyn NetworkConfigService> = Arc::new(TestNetwork::with_fixture(fixture).service());
+        let use_case = ScanWifiNetworksUseCaseImpl::new(service);
+        let scan = |query: serde_json::Value| {
+            let use_case = &use_case;
+            async move {
+                let query: ScanWifiNetworksQuery = serde_json::from_value(query).unwrap();
+                let result = use_case.execute(&query).await.unwrap();
+                result.networks.into_iter().map(|n| n.ssid).collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(scan(serde_json::json!({})).await, vec!["HomeNetwork", "Neighbor", "CoffeeShop", "\\x00\\x00", "Flaky"]);
+        // -89 dBm for CoffeeShop's 22% is below the threshold, and an unreadable signal never passes it
+        assert_eq!(scan(serde_json::json!({"min_signal": -80})).await, vec!["HomeNetwork", "Neighbor", "\\x00\\x00"]);
+        assert_eq!(scan(serde_json::json!({"min_signal": -45})).await, vec!["HomeNetwork"]);
+        assert_eq!(scan(serde_json::json!({"hide_hidden": true})).await, vec!["HomeNetwork", "Neighbor", "CoffeeShop", "Flaky"]);
+        assert_eq!(scan(serde_json::json!({"min_signal": -80, "hide_hidden": true})).await, vec!["HomeNetwork", "Neighbor"]);
+    }
+
+    // Answers each server from its own queue of scripted results, in order
+    struct StubDnsProber {
+        system: Vec<std::net::IpAddr>,
+        answers: std::sync::Mutex<std::collections::HashMap<std::net::IpAddr, Vec<Result<u64, &'static str>>>>,
+    }
+
+    #[async_trait]
+    impl DnsProber for StubDnsProber {
+        async fn system_resolvers(&self) -> Result<Vec<std::net::IpAddr>, String> {
+            Ok(self.system.clone())
+        }
+
+        async fn query(&self, server: std::net::IpAddr, _hostname: &str, _timeout: std::time::Duration) -> Result<std::time::Duration, String> {
+            let answer = self.answers.lock().unwrap().get_mut(&server).unwrap().remove(0);
+            answer.map(std::time::Duration::from_micros).map_err(str::to_string)
+        }
+    }
+
+    #[tokio::test]
+    async fn dns_latency_aggregates_each_servers_samples() {
+        let ip = |s: &str| s.parse::<std::net::IpAddr>().unwrap();
+        let prober = Arc::new(StubDnsProber {
+            system: vec![ip("192.168.1.1")],
+            answers: std::sync::Mutex::new(
+                [
+                    (ip("1.1.1.1"), vec![Ok(12_000), Ok(8_500), Ok(10_000)]),
+                    (ip("8.8.8.8"), vec![Err("timed out"), Ok(20_250), Err("connection refused")]),
+                    (ip("192.168.1.1"), vec![Err("timed out"), Err("timed out")]),
+                ]
+                .into(),
+            ),
+        });
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let use_case = GetDnsLatencyUseCaseImpl::new(service.clone(), prober);
+        let query = |samples| DnsLatencyQuery { hostname: None, samples: Some(samples) };
+
+        // Nothing enabled yet: the system's resolvers are measured
+        let system = use_case.execute(query(2)).await.unwrap();
+        assert_eq!((system.source.as_str(), system.hostname.as_str()), ("system", "example.com"));
+        let router = &system.servers[0];
+        assert_eq!((router.answered, router.failed), (0, 2));
+        assert_eq!((router.min_ms, router.avg_ms, router.max_ms), (None, None, None));
+        assert_eq!(router.last_error.as_deref(), Some("timed out"));
+
+        let config = service
+            .create_static_ip_config(NewStaticIpConfig {
+                dns_secondary: Some("8.8.8.8".to_string()),
+                ..static_ip("eth0", "192.168.1.60")
+            })
+            .await
+            .unwrap();
+        service.enable_static_ip(&config.id, false, false).await.unwrap();
+        let measured = use_case.execute(query(3)).await.unwrap();
+        assert_eq!(measured.source, "static_ip");
+        let servers: Vec<&str> = measured.servers.iter().map(|s| s.server.as_str()).collect();
+        assert_eq!(servers, vec!["1.1.1.1", "8.8.8.8"]);
+        let cloudflare = &measured.servers[0];
+        assert_eq!((cloudflare.answered, cloudflare.failed), (3, 0));
+        assert_eq!((cloudflare.min_ms, cloudflare.avg_ms, cloudflare.max_ms), (Some(8.5), Some(10.167), Some(12.0)));
+        assert_eq!(cloudflare.last_error, None);
+        let google = &measured.servers[1];
+        assert_eq!((google.answered, google.failed), (1, 2));
+        assert_eq!((google.min_ms, google.avg_ms, google.max_ms), (Some(20.25), Some(20.25), Some(20.25)));
+        assert_eq!(google.last_error.as_deref(), Some("connection refused"));
+
+        for samples in [0, GetDnsLatencyUseCaseImpl::MAX_SAMPLES + 1] {
+            assert!(matches!(use_case.execute(query(samples)).await, Err(NetworkError::Validation(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn wifi_signal_samples_carry_the_quality_percentage() {
+        let sample = |wifi_link: WifiLinkStatus| async move {
+            let network = TestNetwork::with_fixture(SimulationFixture { wifi_link, ..SimulationFixture::default() });
+            SampleWifiSignalUseCaseImpl::new(Arc::new(network.service()), "wlan0".to_string()).execute().await
+        };
+        let connected = |dbm| WifiLinkStatus { connected: true, signal_dbm: Some(dbm), ..WifiLinkStatus::default() };
+
+        for (dbm, percent) in [(-45, 100), (-50, 100), (-60, 80), (-75, 50), (-100, 0), (-110, 0)] {
+            let reading = sample(connected(dbm)).await;
+            assert!(reading.connected);
+            assert_eq!((reading.dbm, reading.quality_percent), (Some(dbm), Some(percent)));
+            assert!(reading.ts.is_some());
+        }
+
+        let dropped = sample(WifiLinkStatus::default()).await;
+        assert!(!dropped.connected);
+        assert_eq!((dropped.dbm, dropped.quality_percent, dropped.ts), (None, None, None));
+    }
+
+    #[tokio::test]
+    async fn precheck_reports_each_check_on_its_own() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let enabled = service.create_static_ip_config(static_ip("wlan0", "10.0.0.60")).await.unwrap();
+        service.enable_static_ip(&enabled.id, false, false).await.unwrap();
+        let prober = ScriptedProber { reachable: vec!["192.168.1.20".parse().unwrap()], hang: false };
+        let use_case = PrecheckStaticIpConfigUseCaseImpl::new(service, Arc::new(prober));
+        let precheck = |request: serde_json::Value| {
+            let use_case = &use_case;
+            async move {
+                let result = use_case.execute(serde_json::from_value(request).unwrap()).await;
+                let failed: Vec<String> =
+                    result.checks.iter().filter(|check| !check.passed).map(|check| check.name.clone()).collect();
+                (result, failed)
+            }
+        };
+        let request = |interface: &str, ip: &str, prefix_len: u8, gateway: &str| {
+            serde_json::json!({
+                "interface_name": interface,
+                "ip_address": ip,
+                "prefix_len": prefix_len,
+                "gateway": gateway,
+                "dns_primary": "1.1.1.1",
+            })
+        };
+
+        let (ok, failed) = precheck(request("eth0", "192.168.1.60", 24, "192.168.1.1")).await;
+        assert!(ok.ok && failed.is_empty(), "{:?}", ok.checks);
+        let names: Vec<&str> = ok.checks.iter().map(|check| check.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["fields", "gateway_in_subnet", "host_address", "interface_up", "address_free", "no_subnet_overlap"]
+        );
+
+        // An address already in use and a subnet enabled elsewhere fail only their own checks
+        let (taken, failed) = precheck(request("eth0", "192.168.1.20", 24, "192.168.1.1")).await;
+        assert!(!taken.ok);
+        assert_eq!(failed, vec!["address_free"]);
+        let (_, failed) = precheck(request("eth0", "10.0.0.70", 24, "10.0.0.1")).await;
+        assert_eq!(failed, vec!["no_subnet_overlap"]);
+
+        // The broadcast address with a gateway outside the subnet on a missing interface
+        let (broken, failed) = precheck(request("eth9", "192.168.1.255", 24, "192.168.2.1")).await;
+        assert_eq!(failed, vec!["fields", "gateway_in_subnet", "host_address", "interface_up"]);
+        let host = broken.checks.iter().find(|check| check.name == "host_address").unwrap();
+        assert_eq!(host.detail, "192.168.1.255 is the broadcast address of 192.168.1.0/24");
+    }
+
+    fn static_ip_request(interface_name: &str, ip_address: &str) -> CreateStaticIpConfigRequest {
+        let new_config = static_ip(interface_name, ip_address);
+        CreateStaticIpConfigRequest {
+            id: None,
+            interface_name: new_config.interface_name,
+            ip_address: new_config.ip_address,
+            subnet_mask: None,
+            prefix_len: new_config.prefix_len,
+            gateway: new_config.gateway,
+            dns_primary: new_config.dns_primary,
+            dns_secondary: None,
+            search_domains: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_create_is_all_or_nothing_only_when_atomic() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let use_case = BatchCreateStaticIpConfigsUseCaseImpl::new(service.clone());
+        let batch = || {
+            vec![
+                static_ip_request("eth0", "192.168.1.60"),
+                static_ip_request("eth0", "192.168.1.300"),
+                static_ip_request("wlan0", "10.0.0.60"),
+            ]
+        };
+
+        let atomic = use_case.execute(batch(), true).await.unwrap();
+        assert_eq!(atomic.created, 0);
+        assert!(atomic.results.iter().all(|result| result.id.is_none()));
+        assert!(atomic.results[0].errors.is_empty() && atomic.results[2].errors.is_empty());
+        assert!(!atomic.results[1].errors.is_empty());
+        assert!(network.static_ip_repository.find_all().await.unwrap().is_empty());
+
+        let partial = use_case.execute(batch(), false).await.unwrap();
+        assert_eq!(partial.created, 2);
+        assert!(partial.results[0].id.is_some() && partial.results[2].id.is_some());
+        assert!(partial.results[1].id.is_none() && !partial.results[1].errors.is_empty());
+        assert_eq!(network.static_ip_repository.find_all().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn atomic_batch_rolls_back_when_a_save_fails() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service().with_config_limits(ConfigLimits {
+            max_static_ip_configs: 2,
+            ..ConfigLimits::default()
+        }));
+        let use_case = BatchCreateStaticIpConfigsUseCaseImpl::new(service);
+        let batch = vec![
+            static_ip_request("eth0", "192.168.1.60"),
+            static_ip_request("eth0", "192.168.1.61"),
+            static_ip_request("eth0", "192.168.1.62"),
+        ];
+
+        // Every item passes the checks; the limit only bites on the third save
+        let response = use_case.execute(batch, true).await.unwrap();
+        assert_eq!(response.created, 0);
+        assert!(response.results.iter().all(|result| result.id.is_none()));
+        assert!(!response.results[2].errors.is_empty());
+        assert!(network.static_ip_repository.find_all().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+// Static IP safe mode - enabling a static IP is reverted automatically unless it is confirmed in time
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::domain::network_services::NetworkConfigService;
+
+pub struct StaticIpSafeMode {
+    network_service: Arc<dyn NetworkConfigService>,
+    // `None` turns safe mode off
+    revert_after: Option<Duration>,
+    // Revert task per enabled config id that is still waiting for confirmation, with the token
+    // that task was started with
+    pending: Mutex<HashMap<String, (u64, tokio::task::JoinHandle<()>)>>,
+    next_token: AtomicU64,
+}
+
+impl StaticIpSafeMode {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>, revert_after: Option<Duration>) -> Self {
+        Self {
+            network_service,
+            revert_after,
+            pending: Mutex::new(HashMap::new()),
+            next_token: AtomicU64::new(0),
+        }
+    }
+
+    // Start the revert timer for a just-enabled config. `previous_ids` are the configs that were
+    // enabled on the same interface before it and get re-applied on revert.
+    // Returns when the revert will happen, or `None` when safe mode is off.
+    pub fn schedule_revert(
+        self: &Arc<Self>,
+        config_id: String,
+        previous_ids: Vec<String>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let revert_after = self.revert_after?;
+        let revert_at = chrono::Utc::now() + chrono::Duration::from_std(revert_after).ok()?;
+
+        let token = self.next_token.fetch_add(1, Ordering::Relaxed);
+        let safe_mode = Arc::clone(self);
+        let task_config_id = config_id.clone();
+
+        // The task only reverts if its entry is still the pending one when the timer fires. The
+        // check and the removal happen under the same lock `confirm` takes, so a confirmation
+        // either cancels the revert completely or comes too late and reports false.
+        let mut pending = self.pending.lock().unwrap();
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(revert_after).await;
+            {
+                let mut pending = safe_mode.pending.lock().unwrap();
+                match pending.get(&task_config_id) {
+                    Some((pending_token, _)) if *pending_token == token => {
+                        pending.remove(&task_config_id);
+                    }
+                    _ => return,
+                }
+            }
+            safe_mode.revert(&task_config_id, &previous_ids).await;
+        });
+
+        // Re-enabling while a revert is pending restarts the timer
+        if let Some((_, previous)) = pending.insert(config_id, (token, handle)) {
+            previous.abort();
+        }
+        Some(revert_at)
+    }
+
+    // Keep the change; false when there was nothing pending for this config
+    pub fn confirm(&self, config_id: &str) -> bool {
+        match self.pending.lock().unwrap().remove(config_id) {
+            Some((_, handle)) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn revert(&self, config_id: &str, previous_ids: &[String]) {
+        // Nothing to undo if the config was disabled or deleted in the meantime
+        let still_enabled = match self.network_service.get_static_ip_configs().await {
+            Ok(configs) => configs.iter().any(|config| config.id == config_id && config.is_enabled),
+            Err(e) => {
+                tracing::error!("Safe mode revert of static IP config {} failed: {}", config_id, e);
+                return;
+            }
+        };
+        if !still_enabled {
+            return;
+        }
+
+        tracing::warn!(
+            "Static IP config {} was not confirmed in time, reverting to the previous addressing",
+            config_id
+        );
+        if let Err(e) = self.network_service.disable_static_ip(config_id).await {
+            tracing::error!("Safe mode revert of static IP config {} failed: {}", config_id, e);
+        }
+        for previous_id in previous_ids {
+            if let Err(e) = self.network_service.enable_static_ip(previous_id, true, true).await {
+                tracing::error!("Safe mode could not re-apply static IP config {}: {}", previous_id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{static_ip, TestNetwork};
+
+    const REVERT_AFTER: Duration = Duration::from_secs(60);
+
+    // A safe mode over a recording applier, with `previous` enabled on eth0 and then `risky`
+    // taking it over
+    async fn enabled_pair(network: &TestNetwork) -> (Arc<StaticIpSafeMode>, Arc<dyn NetworkConfigService>, String, String) {
+        let service: Arc<dyn NetworkConfigService> = Arc::new(network.service());
+        let previous = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        let risky = service.create_static_ip_config(static_ip("eth0", "192.168.1.61")).await.unwrap();
+        service.enable_static_ip(&previous.id, false, false).await.unwrap();
+        service.enable_static_ip(&risky.id, true, false).await.unwrap();
+        let safe_mode = Arc::new(StaticIpSafeMode::new(service.clone(), Some(REVERT_AFTER)));
+        (safe_mode, service, previous.id, risky.id)
+    }
+
+    async fn enabled_ids(service: &Arc<dyn NetworkConfigService>) -> Vec<String> {
+        let configs = service.get_static_ip_configs().await.unwrap();
+        configs.into_iter().filter(|config| config.is_enabled).map(|config| config.id).collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unconfirmed_change_is_reverted() {
+        let network = TestNetwork::new();
+        let (safe_mode, service, previous_id, risky_id) = enabled_pair(&network).await;
+        safe_mode.schedule_revert(risky_id.clone(), vec![previous_id.clone()]).unwrap();
+
+        tokio::time::sleep(REVERT_AFTER - Duration::from_secs(1)).await;
+        assert_eq!(enabled_ids(&service).await, vec![risky_id.clone()]);
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(enabled_ids(&service).await, vec![previous_id.clone()]);
+        let calls = network.applier.calls();
+        assert!(calls.ends_with(&[format!("remove static {}", risky_id), format!("apply static {}", previous_id)]), "{:?}", calls);
+        // Too late to confirm now
+        assert!(!safe_mode.confirm(&risky_id));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn confirmed_change_is_kept() {
+        let network = TestNetwork::new();
+        let (safe_mode, service, previous_id, risky_id) = enabled_pair(&network).await;
+        safe_mode.schedule_revert(risky_id.clone(), vec![previous_id]).unwrap();
+        let calls_before = network.applier.calls();
+
+        tokio::time::sleep(REVERT_AFTER / 2).await;
+        assert!(safe_mode.confirm(&risky_id));
+        tokio::time::sleep(REVERT_AFTER * 2).await;
+
+        assert_eq!(enabled_ids(&service).await, vec![risky_id.clone()]);
+        assert_eq!(network.applier.calls(), calls_before);
+        assert!(!safe_mode.confirm(&risky_id));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rescheduling_restarts_the_timer() {
+        let network = TestNetwork::new();
+        let (safe_mode, service, previous_id, risky_id) = enabled_pair(&network).await;
+        safe_mode.schedule_revert(risky_id.clone(), vec![previous_id.clone()]).unwrap();
+        tokio::time::sleep(REVERT_AFTER / 2).await;
+        safe_mode.schedule_revert(risky_id.clone(), vec![previous_id]).unwrap();
+
+        // The first timer's deadline passes without a revert
+        tokio::time::sleep(REVERT_AFTER * 3 / 4).await;
+        assert_eq!(enabled_ids(&service).await, vec![risky_id.clone()]);
+        assert!(safe_mode.confirm(&risky_id));
+    }
+}
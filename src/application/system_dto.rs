@@ -0,0 +1,40 @@
+// System settings DTOs
+
+use serde::{Deserialize, Serialize};
+use crate::domain::system_entities::IpForwarding;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HostnameDto {
+    pub hostname: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IpForwardingDto {
+    pub ipv4: bool,
+    pub ipv6: bool,
+}
+
+impl From<IpForwarding> for IpForwardingDto {
+    fn from(forwarding: IpForwarding) -> Self {
+        Self {
+            ipv4: forwarding.ipv4,
+            ipv6: forwarding.ipv6,
+        }
+    }
+}
+
+impl From<IpForwardingDto> for IpForwarding {
+    fn from(dto: IpForwardingDto) -> Self {
+        Self {
+            ipv4: dto.ipv4,
+            ipv6: dto.ipv6,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CapabilitiesDto {
+    pub can_apply_network: bool,
+    pub apply_feature_compiled: bool,
+    pub running_as_root: bool,
+}
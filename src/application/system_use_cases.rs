@@ -0,0 +1,136 @@
+// System use cases - host-wide settings
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use crate::domain::network_errors::NetworkError;
+use crate::domain::system_repositories::PrivilegeProbe;
+use crate::domain::system_services::{HostnameService, IpForwardingService};
+use crate::application::system_dto::*;
+
+#[async_trait]
+pub trait GetHostnameUseCase: Send + Sync {
+    async fn execute(&self) -> Result<HostnameDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait SetHostnameUseCase: Send + Sync {
+    async fn execute(&self, request: HostnameDto) -> Result<HostnameDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetIpForwardingUseCase: Send + Sync {
+    async fn execute(&self) -> Result<IpForwardingDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait SetIpForwardingUseCase: Send + Sync {
+    async fn execute(&self, request: IpForwardingDto) -> Result<IpForwardingDto, NetworkError>;
+}
+
+#[async_trait]
+pub trait GetCapabilitiesUseCase: Send + Sync {
+    async fn execute(&self) -> Result<CapabilitiesDto, NetworkError>;
+}
+
+pub struct GetHostnameUseCaseImpl {
+    hostname_service: Arc<dyn HostnameService>,
+}
+
+impl GetHostnameUseCaseImpl {
+    pub fn new(hostname_service: Arc<dyn HostnameService>) -> Self {
+        Self { hostname_service }
+    }
+}
+
+#[async_trait]
+impl GetHostnameUseCase for GetHostnameUseCaseImpl {
+    async fn execute(&self) -> Result<HostnameDto, NetworkError> {
+        Ok(HostnameDto {
+            hostname: self.hostname_service.get().await?,
+        })
+    }
+}
+
+pub struct SetHostnameUseCaseImpl {
+    hostname_service: Arc<dyn HostnameService>,
+}
+
+impl SetHostnameUseCaseImpl {
+    pub fn new(hostname_service: Arc<dyn HostnameService>) -> Self {
+        Self { hostname_service }
+    }
+}
+
+#[async_trait]
+impl SetHostnameUseCase for SetHostnameUseCaseImpl {
+    async fn execute(&self, request: HostnameDto) -> Result<HostnameDto, NetworkError> {
+        Ok(HostnameDto {
+            hostname: self.hostname_service.set(request.hostname.trim()).await?,
+        })
+    }
+}
+
+pub struct GetIpForwardingUseCaseImpl {
+    ip_forwarding_service: Arc<dyn IpForwardingService>,
+}
+
+impl GetIpForwardingUseCaseImpl {
+    pub fn new(ip_forwarding_service: Arc<dyn IpForwardingService>) -> Self {
+        Self { ip_forwarding_service }
+    }
+}
+
+#[async_trait]
+impl GetIpForwardingUseCase for GetIpForwardingUseCaseImpl {
+    async fn execute(&self) -> Result<IpForwardingDto, NetworkError> {
+        Ok(self.ip_forwarding_service.get().await?.into())
+    }
+}
+
+pub struct SetIpForwardingUseCaseImpl {
+    ip_forwarding_service: Arc<dyn IpForwardingService>,
+}
+
+impl SetIpForwardingUseCaseImpl {
+    pub fn new(ip_forwarding_service: Arc<dyn IpForwardingService>) -> Self {
+        Self { ip_forwarding_service }
+    }
+}
+
+#[async_trait]
+impl SetIpForwardingUseCase for SetIpForwardingUseCaseImpl {
+    async fn execute(&self, request: IpForwardingDto) -> Result<IpForwardingDto, NetworkError> {
+        Ok(self.ip_forwarding_service.set(request.into()).await?.into())
+    }
+}
+
+pub struct GetCapabilitiesUseCaseImpl {
+    privilege_probe: Arc<dyn PrivilegeProbe>,
+    // Whether appliers push changes to the system at all (the feature, or simulation)
+    applies_changes: bool,
+}
+
+impl GetCapabilitiesUseCaseImpl {
+    pub fn new(privilege_probe: Arc<dyn PrivilegeProbe>, applies_changes: bool) -> Self {
+        Self {
+            privilege_probe,
+            applies_changes,
+        }
+    }
+}
+
+#[async_trait]
+impl GetCapabilitiesUseCase for GetCapabilitiesUseCaseImpl {
+    async fn execute(&self) -> Result<CapabilitiesDto, NetworkError> {
+        // An unreadable probe is reported as unprivileged rather than failing the page that asks
+        let privileges = self.privilege_probe.probe().await.unwrap_or_else(|e| {
+            tracing::warn!("Could not determine process privileges: {}", e);
+            Default::default()
+        });
+        Ok(CapabilitiesDto {
+            can_apply_network: self.applies_changes && privileges.can_manage_network(),
+            apply_feature_compiled: cfg!(feature = "apply-system"),
+            running_as_root: privileges.running_as_root,
+        })
+    }
+}
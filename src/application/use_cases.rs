@@ -2,6 +2,7 @@
 
 use async_trait::async_trait;
 use std::sync::Arc;
+use crate::domain::errors::GreetingError;
 use crate::domain::services::GreetingService;
 use crate::application::dto::*;
 
@@ -12,12 +13,17 @@ pub trait GetDefaultGreetingUseCase: Send + Sync {
 
 #[async_trait]
 pub trait CreateGreetingUseCase: Send + Sync {
-    async fn execute(&self, request: CreateGreetingRequest) -> Result<GreetingResponse, String>;
+    async fn execute(&self, request: CreateGreetingRequest) -> Result<GreetingResponse, GreetingError>;
 }
 
 #[async_trait]
 pub trait ListGreetingsUseCase: Send + Sync {
-    async fn execute(&self) -> Result<GreetingsListResponse, String>;
+    async fn execute(&self, query: ListGreetingsQuery) -> Result<GreetingsListResponse, GreetingError>;
+}
+
+#[async_trait]
+pub trait RenderGreetingUseCase: Send + Sync {
+    async fn execute(&self, id: String, request: RenderGreetingRequest) -> Result<RenderGreetingResponse, GreetingError>;
 }
 
 pub struct GetDefaultGreetingUseCaseImpl {
@@ -52,9 +58,9 @@ impl CreateGreetingUseCaseImpl {
 
 #[async_trait]
 impl CreateGreetingUseCase for CreateGreetingUseCaseImpl {
-    async fn execute(&self, request: CreateGreetingRequest) -> Result<GreetingResponse, String> {
+    async fn execute(&self, request: CreateGreetingRequest) -> Result<GreetingResponse, GreetingError> {
         let language = request.language.unwrap_or_else(|| "en".to_string());
-        let greeting = self.greeting_service.create_greeting(request.message, language).await?;
+        let greeting = self.greeting_service.create_greeting(request.message, language, request.dedup).await?;
         Ok(GreetingResponse {
             greeting: greeting.into(),
         })
@@ -73,10 +79,97 @@ impl ListGreetingsUseCaseImpl {
 
 #[async_trait]
 impl ListGreetingsUseCase for ListGreetingsUseCaseImpl {
-    async fn execute(&self) -> Result<GreetingsListResponse, String> {
-        let greetings = self.greeting_service.list_greetings().await?;
+    async fn execute(&self, query: ListGreetingsQuery) -> Result<GreetingsListResponse, GreetingError> {
+        let created_after = parse_timestamp_param("created_after", query.created_after.as_deref())?;
+        let created_before = parse_timestamp_param("created_before", query.created_before.as_deref())?;
+        let greetings = self.greeting_service.list_greetings(created_after, created_before).await?;
         Ok(GreetingsListResponse {
             greetings: greetings.iter().map(|g| g.into()).collect(),
         })
     }
-}
\ No newline at end of file
+}
+
+pub struct RenderGreetingUseCaseImpl {
+    greeting_service: Arc<dyn GreetingService>,
+}
+
+impl RenderGreetingUseCaseImpl {
+    pub fn new(greeting_service: Arc<dyn GreetingService>) -> Self {
+        Self { greeting_service }
+    }
+}
+
+#[async_trait]
+impl RenderGreetingUseCase for RenderGreetingUseCaseImpl {
+    async fn execute(&self, id: String, request: RenderGreetingRequest) -> Result<RenderGreetingResponse, GreetingError> {
+        let rendered = self.greeting_service.render(&id, &request.vars).await?;
+        Ok(RenderGreetingResponse { id, rendered })
+    }
+}
+
+fn parse_timestamp_param(
+    name: &str,
+    value: Option<&str>,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, GreetingError> {
+    value
+        .map(|value| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .map(|timestamp| timestamp.with_timezone(&chrono::Utc))
+                .map_err(|_| GreetingError::Validation(format!("{} must be an RFC3339 timestamp: {}", name, value)))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Greeting;
+    use crate::domain::repositories::GreetingRepository;
+    use crate::domain::services::GreetingServiceImpl;
+    use crate::infrastructure::repositories::InMemoryGreetingRepository;
+
+    fn query(created_after: Option<&str>, created_before: Option<&str>) -> ListGreetingsQuery {
+        ListGreetingsQuery {
+            created_after: created_after.map(str::to_string),
+            created_before: created_before.map(str::to_string),
+        }
+    }
+
+    // Greetings created at noon on each of the given days of January 2026
+    async fn list_use_case(days: &[u32]) -> ListGreetingsUseCaseImpl {
+        let repository = Arc::new(InMemoryGreetingRepository::new());
+        for day in days {
+            let mut greeting = Greeting::new(format!("day {}", day), "en".to_string());
+            greeting.created_at = format!("2026-01-{:02}T12:00:00Z", day).parse().unwrap();
+            repository.save(&greeting).await.unwrap();
+        }
+        ListGreetingsUseCaseImpl::new(Arc::new(GreetingServiceImpl::new(repository)))
+    }
+
+    #[tokio::test]
+    async fn greetings_are_filtered_to_the_time_window() {
+        let use_case = list_use_case(&[1, 2, 3, 4]).await;
+        let response = use_case
+            .execute(query(Some("2026-01-01T12:00:00Z"), Some("2026-01-04T00:00:00+02:00")))
+            .await
+            .unwrap();
+        let mut messages: Vec<String> = response.greetings.into_iter().map(|g| g.message).collect();
+        messages.sort();
+        // The bounds are exclusive: day 1 is exactly `created_after`, day 4 is after `created_before`
+        assert_eq!(messages, vec!["day 2", "day 3"]);
+
+        let open_ended = use_case.execute(query(Some("2026-01-03T00:00:00Z"), None)).await.unwrap();
+        assert_eq!(open_ended.greetings.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn malformed_timestamps_are_validation_errors() {
+        let use_case = list_use_case(&[1]).await;
+        match use_case.execute(query(None, Some("yesterday"))).await {
+            Err(GreetingError::Validation(message)) => {
+                assert_eq!(message, "created_before must be an RFC3339 timestamp: yesterday");
+            }
+            other => panic!("expected a validation error, got {:?}", other.map(|r| r.greetings.len())),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// WiFi scan cache - keeps the latest scan so the settings page can fill its SSID dropdown
+// without a click, optionally refreshed by a background scan on an interval
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::application::network_dto::RecentWifiScanDto;
+use crate::domain::network_entities::ScannedWifiNetwork;
+use crate::domain::network_errors::NetworkError;
+use crate::domain::network_services::NetworkConfigService;
+
+// Scanning takes the radio off-channel for a moment, so background scans are never more frequent
+pub const MIN_BACKGROUND_SCAN_INTERVAL: Duration = Duration::from_secs(30);
+
+struct CachedScan {
+    scanned_at: chrono::DateTime<chrono::Utc>,
+    networks: Vec<ScannedWifiNetwork>,
+}
+
+impl CachedScan {
+    fn is_younger_than(&self, max_age: Duration) -> bool {
+        (chrono::Utc::now() - self.scanned_at)
+            .to_std()
+            .is_ok_and(|age| age < max_age)
+    }
+}
+
+pub struct WifiScanCache {
+    network_service: Arc<dyn NetworkConfigService>,
+    latest: Mutex<Option<CachedScan>>,
+    // Older scans are treated as gone; `None` keeps the latest one indefinitely
+    ttl: Option<Duration>,
+}
+
+impl WifiScanCache {
+    pub fn new(network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self {
+            network_service,
+            latest: Mutex::new(None),
+            ttl: None,
+        }
+    }
+
+    // Stop offering a scan once it is `ttl` old; zero keeps it until the next scan
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = (!ttl.is_zero()).then_some(ttl);
+        self
+    }
+
+    fn is_live(&self, scan: &CachedScan) -> bool {
+        self.ttl.is_none_or(|ttl| scan.is_younger_than(ttl))
+    }
+
+    // Remember a finished scan, whether it was started in the background or by a client
+    pub fn store(&self, scanned_at: chrono::DateTime<chrono::Utc>, networks: Vec<ScannedWifiNetwork>) {
+        *self.latest.lock().unwrap() = Some(CachedScan { scanned_at, networks });
+    }
+
+    // When the latest scan finished, and what it found
+    pub fn latest_scan(&self) -> Option<(chrono::DateTime<chrono::Utc>, Vec<ScannedWifiNetwork>)> {
+        self.latest
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|scan| self.is_live(scan))
+            .map(|scan| (scan.scanned_at, scan.networks.clone()))
+    }
+
+    pub fn latest(&self) -> Option<RecentWifiScanDto> {
+        self.latest.lock().unwrap().as_ref().filter(|scan| self.is_live(scan)).map(|scan| RecentWifiScanDto {
+            scanned_at: scan.scanned_at.to_rfc3339(),
+            networks: scan.networks.iter().cloned().map(|network| network.into()).collect(),
+        })
+    }
+
+    // Scan every `interval` (at least MIN_BACKGROUND_SCAN_INTERVAL)
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let interval = interval.max(MIN_BACKGROUND_SCAN_INTERVAL);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.refresh(interval).await {
+                    tracing::warn!("Background WiFi scan failed: {}", e);
+                }
+            }
+        })
+    }
+
+    // Scan unless the cached result is younger than `max_age`, e.g. because a client just scanned
+    pub async fn refresh(&self, max_age: Duration) -> Result<(), NetworkError> {
+        let fresh = self.latest.lock().unwrap().as_ref().is_some_and(|scan| scan.is_younger_than(max_age));
+        if fresh {
+            return Ok(());
+        }
+
+        let scanned_at = chrono::Utc::now();
+        let networks = self.network_service.scan_wifi_networks().await?;
+        self.store(scanned_at, networks);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    fn scanned(age_secs: i64) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now() - chrono::Duration::seconds(age_secs)
+    }
+
+    #[test]
+    fn scans_older_than_the_ttl_are_not_offered() {
+        let network = TestNetwork::new();
+        let cache = WifiScanCache::new(Arc::new(network.service())).with_ttl(Duration::from_secs(60));
+
+        cache.store(scanned(10), Vec::new());
+        assert!(cache.latest().is_some());
+        assert!(cache.latest_scan().is_some());
+
+        cache.store(scanned(90), Vec::new());
+        assert!(cache.latest().is_none());
+        assert!(cache.latest_scan().is_none());
+    }
+
+    #[test]
+    fn a_zero_ttl_keeps_the_latest_scan() {
+        let network = TestNetwork::new();
+        let cache = WifiScanCache::new(Arc::new(network.service())).with_ttl(Duration::ZERO);
+        cache.store(scanned(86_400), Vec::new());
+        assert!(cache.latest().is_some());
+    }
+}
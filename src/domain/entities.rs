@@ -1,6 +1,8 @@
 // Domain entities - core business objects
 
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Greeting {
     pub id: String,
     pub message: String,
@@ -18,10 +20,66 @@ impl Greeting {
         }
     }
 
+    pub fn with_created_at(mut self, created_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    // Substitute `{key}` placeholders in the message. Braces around anything other than a name of
+    // letters, digits and '_' are kept as they are; a placeholder missing from `vars` is an error.
+    pub fn render(&self, vars: &std::collections::HashMap<String, String>) -> Result<String, String> {
+        let mut rendered = String::with_capacity(self.message.len());
+        let mut unknown = Vec::new();
+        let mut rest = self.message.as_str();
+        while let Some(open) = rest.find('{') {
+            rendered.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            let name = after.find('}').map(|close| &after[..close]).filter(|name| {
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+            match name {
+                Some(name) => {
+                    match vars.get(name) {
+                        Some(value) => rendered.push_str(value),
+                        None if !unknown.contains(&name) => unknown.push(name),
+                        None => {}
+                    }
+                    rest = &after[name.len() + 1..];
+                }
+                None => {
+                    rendered.push('{');
+                    rest = after;
+                }
+            }
+        }
+        rendered.push_str(rest);
+
+        if unknown.is_empty() {
+            Ok(rendered)
+        } else {
+            Err(format!("No value for placeholder(s): {}", unknown.join(", ")))
+        }
+    }
+
     pub fn default_hello_world() -> Self {
         Self::new(
             "Hello, World!".to_string(),
             "en".to_string(),
         )
     }
+}
+
+// A plausible language tag: an ISO 639 code of 2-3 lowercase letters, optionally followed by a
+// region of 2 letters or 3 digits, e.g. "en", "fil", "en-US" or "es-419"
+pub fn is_valid_language_tag(language: &str) -> bool {
+    let (primary, region) = match language.split_once('-') {
+        Some((primary, region)) => (primary, Some(region)),
+        None => (language, None),
+    };
+    let primary_ok = (2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_lowercase());
+    let region_ok = region.is_none_or(|region| {
+        (region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()))
+            || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit()))
+    });
+    primary_ok && region_ok
 }
\ No newline at end of file
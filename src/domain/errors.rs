@@ -0,0 +1,35 @@
+// Greeting domain errors - let the web layer tell bad input apart from server failures
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GreetingError {
+    Validation(String),
+    NotFound(String),
+    Internal(String),
+}
+
+impl GreetingError {
+    pub fn message(&self) -> &str {
+        match self {
+            GreetingError::Validation(message) | GreetingError::NotFound(message) | GreetingError::Internal(message) => {
+                message
+            }
+        }
+    }
+}
+
+impl fmt::Display for GreetingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for GreetingError {}
+
+// Repository failures are reported as plain strings
+impl From<String> for GreetingError {
+    fn from(message: String) -> Self {
+        GreetingError::Internal(message)
+    }
+}
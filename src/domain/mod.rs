@@ -2,8 +2,18 @@
 // This layer should have no dependencies on external frameworks
 
 pub mod entities;
+pub mod errors;
 pub mod network_entities;
+pub mod network_errors;
 pub mod repositories;
 pub mod network_repositories;
+pub mod network_appliers;
 pub mod services;
-pub mod network_services;
\ No newline at end of file
+pub mod network_services;
+pub mod validation;
+pub mod system_repositories;
+pub mod system_services;
+pub mod network_events;
+pub mod system_entities;
+pub mod clock;
+pub mod notifications;
\ No newline at end of file
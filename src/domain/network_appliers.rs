@@ -0,0 +1,29 @@
+// Network applier traits - push stored configuration onto the live system
+// Implemented in the infrastructure layer; the real appliers are only compiled with `apply-system`
+//
+// Cancellation: callers may drop these futures at any point (the HTTP request they serve can be
+// abandoned), and an apply dropped halfway leaves the system half-configured. The implementations
+// themselves need not guard against that; they are always called through `DetachedApplier`, which
+// runs every call to completion on its own task whatever happens to the caller.
+
+use async_trait::async_trait;
+use crate::domain::network_entities::*;
+
+#[async_trait]
+pub trait StaticIpApplier: Send + Sync {
+    async fn apply(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String>;
+    async fn remove(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String>;
+}
+
+#[async_trait]
+pub trait WifiApplier: Send + Sync {
+    async fn apply(&self, config: &WifiConfig) -> Result<ApplyOutcome, String>;
+    // Leave the network `config` is connected to
+    async fn disconnect(&self, config: &WifiConfig) -> Result<ApplyOutcome, String>;
+}
+
+#[async_trait]
+pub trait ServiceRestarter: Send + Sync {
+    // Restart a system service such as systemd-networkd
+    async fn restart(&self, unit: &str) -> Result<ApplyOutcome, String>;
+}
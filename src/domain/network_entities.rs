@@ -1,6 +1,11 @@
 // Network configuration entities
 
 use serde::{Deserialize, Serialize};
+use crate::domain::network_errors::NetworkError;
+use crate::domain::validation::{
+    gateway_in_subnet, mask_to_prefix_len, network_address, parse_ip, parse_ipv4, parse_subnet_mask, validate_config_id,
+    subnets_overlap, validate_domain_name, validate_interface_name, validate_psk, validate_ssid,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WifiConfig {
@@ -8,11 +13,27 @@ pub struct WifiConfig {
     pub ssid: String,
     pub password: String,
     pub security_type: WifiSecurityType,
+    #[serde(default)]
+    pub hidden: bool,
+    // Whether wpa_supplicant may join this network on its own; `is_active` is what was last
+    // connected to on request
+    #[serde(default = "default_auto_connect")]
+    pub auto_connect: bool,
+    // Only associate on this band, e.g. to keep off a congested 2.4GHz; any band when unset
+    #[serde(default)]
+    pub preferred_band: Option<WifiBand>,
     pub is_active: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    // Why the last attempt to apply this config to the system failed; cleared on the next success
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub last_error_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Variant names are the wire format, so they keep the usual acronym spelling
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WifiSecurityType {
     Open,
     WEP,
@@ -21,79 +42,1403 @@ pub enum WifiSecurityType {
     WPA3,
 }
 
+impl WifiSecurityType {
+    // The serialized name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WifiSecurityType::Open => "Open",
+            WifiSecurityType::WEP => "WEP",
+            WifiSecurityType::WPA => "WPA",
+            WifiSecurityType::WPA2 => "WPA2",
+            WifiSecurityType::WPA3 => "WPA3",
+        }
+    }
+}
+
+impl std::str::FromStr for WifiSecurityType {
+    type Err = String;
+
+    // Accepts the serialized names in any case, e.g. "WPA2" or "wpa2"
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "OPEN" => Ok(WifiSecurityType::Open),
+            "WEP" => Ok(WifiSecurityType::WEP),
+            "WPA" => Ok(WifiSecurityType::WPA),
+            "WPA2" => Ok(WifiSecurityType::WPA2),
+            "WPA3" => Ok(WifiSecurityType::WPA3),
+            _ => Err(format!("Unknown security type: {}", value)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StaticIpConfig {
     pub id: String,
     pub interface_name: String,
+    pub ip_address: std::net::Ipv4Addr,
+    // Kept as a prefix length, but stored as a dotted `subnet_mask` so existing data files still load
+    #[serde(rename = "subnet_mask", with = "dotted_subnet_mask")]
+    pub prefix_len: u8,
+    pub gateway: std::net::Ipv4Addr,
+    pub dns_primary: std::net::IpAddr,
+    pub dns_secondary: Option<std::net::IpAddr>,
+    #[serde(default)]
+    pub search_domains: Vec<String>,
+    pub is_enabled: bool,
+    // When the config was last enabled / disabled; each keeps its value when the other is set
+    #[serde(default)]
+    pub enabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    // Last in-place edit of the settings, such as replacing the DNS servers
+    #[serde(default)]
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub last_error_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// Input for creating a WiFi config
+#[derive(Debug, Clone)]
+pub struct NewWifiConfig {
+    // Caller-chosen id for declarative setups; a UUID is generated when absent
+    pub id: Option<String>,
+    pub ssid: String,
+    pub password: String,
+    pub security_type: WifiSecurityType,
+    pub hidden: bool,
+    pub auto_connect: bool,
+    pub preferred_band: Option<WifiBand>,
+    // Keep leading/trailing spaces in `ssid`, for networks whose name really has them
+    pub exact_ssid: bool,
+}
+
+pub fn default_auto_connect() -> bool {
+    true
+}
+
+// Input for creating a static IP config; the netmask may be given as a dotted mask, a prefix length or both
+#[derive(Debug, Clone)]
+pub struct NewStaticIpConfig {
+    pub id: Option<String>,
+    pub interface_name: String,
     pub ip_address: String,
-    pub subnet_mask: String,
+    pub subnet_mask: Option<String>,
+    pub prefix_len: Option<u8>,
     pub gateway: String,
     pub dns_primary: String,
     pub dns_secondary: Option<String>,
-    pub is_enabled: bool,
-    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub search_domains: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkInterface {
     pub name: String,
     pub interface_type: InterfaceType,
-    pub mac_address: String,
+    pub mac_address: Option<MacAddress>,
     pub is_up: bool,
+    // Link detected (e.g. a cable plugged in), independent of `is_up`; `None` when the driver
+    // can't tell, which includes every interface that is administratively down
+    #[serde(default)]
+    pub has_carrier: Option<bool>,
     pub ipv4_addresses: Vec<String>,
     pub ipv6_addresses: Vec<String>,
+    // The same addresses with their prefix length (192.168.1.10/24), in the same order; an
+    // address whose netmask is unknown appears bare
+    #[serde(default)]
+    pub ipv4_cidrs: Vec<String>,
+    #[serde(default)]
+    pub ipv6_cidrs: Vec<String>,
     pub current_ip: Option<String>, // Keep for backward compatibility
 }
 
+// MAC address normalized to lowercase, colon-separated form (aa:bb:cc:dd:ee:ff)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct MacAddress(String);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum InterfaceType {
     Ethernet,
     Wireless,
     Loopback,
+    Bridge,
     Other,
 }
 
+impl InterfaceType {
+    // Stable lowercase key for clients to map icons to, independent of the serde form
+    pub fn category(&self) -> &'static str {
+        match self {
+            InterfaceType::Ethernet => "ethernet",
+            InterfaceType::Wireless => "wireless",
+            InterfaceType::Loopback => "loopback",
+            InterfaceType::Bridge => "bridge",
+            InterfaceType::Other => "other",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            InterfaceType::Ethernet => "Ethernet",
+            InterfaceType::Wireless => "Wi-Fi",
+            InterfaceType::Loopback => "Loopback",
+            InterfaceType::Bridge => "Bridge",
+            InterfaceType::Other => "Other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceAdminState {
+    pub interface_name: String,
+    pub desired_up: bool,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceEvent {
+    pub id: u64,
+    pub kind: InterfaceEventKind,
+    pub interface_name: String,
+    pub interface: Option<NetworkInterface>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum InterfaceEventKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+// Kind of an entry in the persisted event history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryEventType {
+    #[serde(rename = "interface.added")]
+    InterfaceAdded,
+    #[serde(rename = "interface.removed")]
+    InterfaceRemoved,
+    #[serde(rename = "interface.changed")]
+    InterfaceChanged,
+    #[serde(rename = "wifi.created")]
+    WifiCreated,
+    #[serde(rename = "wifi.activated")]
+    WifiActivated,
+    #[serde(rename = "wifi.deleted")]
+    WifiDeleted,
+    #[serde(rename = "static_ip.created")]
+    StaticIpCreated,
+    #[serde(rename = "static_ip.enabled")]
+    StaticIpEnabled,
+    #[serde(rename = "static_ip.disabled")]
+    StaticIpDisabled,
+    #[serde(rename = "static_ip.deleted")]
+    StaticIpDeleted,
+}
+
+impl HistoryEventType {
+    pub const ALL: [HistoryEventType; 10] = [
+        HistoryEventType::InterfaceAdded,
+        HistoryEventType::InterfaceRemoved,
+        HistoryEventType::InterfaceChanged,
+        HistoryEventType::WifiCreated,
+        HistoryEventType::WifiActivated,
+        HistoryEventType::WifiDeleted,
+        HistoryEventType::StaticIpCreated,
+        HistoryEventType::StaticIpEnabled,
+        HistoryEventType::StaticIpDisabled,
+        HistoryEventType::StaticIpDeleted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryEventType::InterfaceAdded => "interface.added",
+            HistoryEventType::InterfaceRemoved => "interface.removed",
+            HistoryEventType::InterfaceChanged => "interface.changed",
+            HistoryEventType::WifiCreated => "wifi.created",
+            HistoryEventType::WifiActivated => "wifi.activated",
+            HistoryEventType::WifiDeleted => "wifi.deleted",
+            HistoryEventType::StaticIpCreated => "static_ip.created",
+            HistoryEventType::StaticIpEnabled => "static_ip.enabled",
+            HistoryEventType::StaticIpDisabled => "static_ip.disabled",
+            HistoryEventType::StaticIpDeleted => "static_ip.deleted",
+        }
+    }
+}
+
+impl std::str::FromStr for HistoryEventType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        HistoryEventType::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == value)
+            .ok_or_else(|| format!("Unknown event type: {}", value))
+    }
+}
+
+// One entry in the persisted event history. `subject` is the interface name for interface
+// events and the config id for config changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEvent {
+    pub event_type: HistoryEventType,
+    pub subject: String,
+    pub detail: Option<String>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl HistoryEvent {
+    pub fn new(event_type: HistoryEventType, subject: impl Into<String>, detail: Option<String>) -> Self {
+        Self {
+            event_type,
+            subject: subject.into(),
+            detail,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+}
+
+impl From<&InterfaceEvent> for HistoryEvent {
+    fn from(event: &InterfaceEvent) -> Self {
+        let event_type = match event.kind {
+            InterfaceEventKind::Added => HistoryEventType::InterfaceAdded,
+            InterfaceEventKind::Removed => HistoryEventType::InterfaceRemoved,
+            InterfaceEventKind::Changed => HistoryEventType::InterfaceChanged,
+        };
+        // Enough to answer "when did it go down?" without storing the whole snapshot
+        let detail = event.interface.as_ref().map(|interface| {
+            let state = if interface.is_up { "up" } else { "down" };
+            if interface.ipv4_addresses.is_empty() {
+                state.to_string()
+            } else {
+                format!("{} {}", state, interface.ipv4_addresses.join(", "))
+            }
+        });
+        Self {
+            event_type,
+            subject: event.interface_name.clone(),
+            detail,
+            timestamp: event.timestamp,
+        }
+    }
+}
+
+// Which history entries to return: newest first, at most `limit`
+#[derive(Debug, Clone)]
+pub struct EventHistoryFilter {
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub event_types: Option<Vec<HistoryEventType>>,
+    pub limit: usize,
+}
+
+impl EventHistoryFilter {
+    pub fn matches(&self, event: &HistoryEvent) -> bool {
+        self.since.is_none_or(|since| event.timestamp >= since)
+            && self
+                .event_types
+                .as_ref()
+                .is_none_or(|types| types.contains(&event.event_type))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceStats {
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceRate {
+    pub interface_name: String,
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    pub interval_ms: u64,
+}
+
+// Up/down transitions of one interface seen by the monitor within a recent window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceFlaps {
+    pub interface_name: String,
+    pub window_secs: u64,
+    pub transitions: usize,
+    pub last_transition_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// The kernel's default IPv4 route; both fields are `None` when there is no default route
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DefaultRoute {
+    pub gateway: Option<String>,
+    pub interface: Option<String>,
+}
+
+// Latency of one DNS server over a series of queries. min/avg/max cover the answered queries
+// and are `None` when none was answered.
+#[derive(Debug, Clone)]
+pub struct DnsLatency {
+    pub server: std::net::IpAddr,
+    pub answered: usize,
+    pub failed: usize,
+    pub min: Option<std::time::Duration>,
+    pub avg: Option<std::time::Duration>,
+    pub max: Option<std::time::Duration>,
+    // Error of the last failed query
+    pub last_error: Option<String>,
+}
+
+impl DnsLatency {
+    pub fn from_samples(server: std::net::IpAddr, samples: Vec<Result<std::time::Duration, String>>) -> Self {
+        let mut answered = Vec::new();
+        let mut failed = 0;
+        let mut last_error = None;
+        for sample in samples {
+            match sample {
+                Ok(latency) => answered.push(latency),
+                Err(e) => {
+                    failed += 1;
+                    last_error = Some(e);
+                }
+            }
+        }
+        let avg = (!answered.is_empty())
+            .then(|| answered.iter().sum::<std::time::Duration>() / answered.len() as u32);
+        Self {
+            server,
+            answered: answered.len(),
+            failed,
+            min: answered.iter().min().copied(),
+            avg,
+            max: answered.iter().max().copied(),
+            last_error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressConflictKind {
+    // The same address is live on more than one interface
+    DuplicateLive,
+    // A stored static IP config uses an address that is live on a different interface
+    StaticConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddressConflict {
+    pub kind: AddressConflictKind,
+    pub address: std::net::IpAddr,
+    // Up interfaces holding the address; for StaticConfig, only those other than the config's
+    pub interfaces: Vec<String>,
+    pub static_ip_config_id: Option<String>,
+    pub config_interface: Option<String>,
+}
+
+impl AddressConflict {
+    // Only interfaces that are up count. Loopback and link-local addresses are scoped to their
+    // interface, so the same one on several interfaces is normal and not reported.
+    pub fn detect(interfaces: &[NetworkInterface], static_configs: &[StaticIpConfig]) -> Vec<Self> {
+        let mut holders: std::collections::BTreeMap<std::net::IpAddr, Vec<String>> = std::collections::BTreeMap::new();
+        for interface in interfaces.iter().filter(|interface| interface.is_up) {
+            for address in interface.ipv4_addresses.iter().chain(&interface.ipv6_addresses) {
+                // Addresses may carry a prefix length, e.g. 192.168.1.10/24
+                let Ok(address) = address.split('/').next().unwrap_or_default().parse::<std::net::IpAddr>() else {
+                    continue;
+                };
+                if is_interface_scoped(address) {
+                    continue;
+                }
+                let names = holders.entry(address).or_default();
+                if !names.contains(&interface.name) {
+                    names.push(interface.name.clone());
+                }
+            }
+        }
+
+        let mut conflicts: Vec<Self> = holders
+            .iter()
+            .filter(|(_, names)| names.len() > 1)
+            .map(|(address, names)| Self {
+                kind: AddressConflictKind::DuplicateLive,
+                address: *address,
+                interfaces: names.clone(),
+                static_ip_config_id: None,
+                config_interface: None,
+            })
+            .collect();
+        for config in static_configs {
+            let address = std::net::IpAddr::V4(config.ip_address);
+            let others: Vec<String> = holders
+                .get(&address)
+                .map(|names| names.iter().filter(|name| **name != config.interface_name).cloned().collect())
+                .unwrap_or_default();
+            if !others.is_empty() {
+                conflicts.push(Self {
+                    kind: AddressConflictKind::StaticConfig,
+                    address,
+                    interfaces: others,
+                    static_ip_config_id: Some(config.id.clone()),
+                    config_interface: Some(config.interface_name.clone()),
+                });
+            }
+        }
+        conflicts
+    }
+}
+
+fn is_interface_scoped(address: std::net::IpAddr) -> bool {
+    match address {
+        std::net::IpAddr::V4(v4) => v4.is_loopback() || v4.is_link_local() || v4.is_unspecified(),
+        std::net::IpAddr::V6(v6) => v6.is_loopback() || v6.is_unspecified() || (v6.segments()[0] & 0xffc0) == 0xfe80,
+    }
+}
+
+// Settings key holding the interface that status views treat as primary
+pub const PREFERRED_INTERFACE_KEY: &str = "preferred_interface";
+
+// How the primary interface of the current-network view was chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PrimaryInterfaceSource {
+    // The user's pinned preference
+    Preferred,
+    // The interface carrying the default route
+    DefaultRoute,
+    // The first interface that is up with an IPv4 address
+    FirstUp,
+}
+
+// The pinned interface when it exists, otherwise the default route's interface, otherwise the
+// first non-loopback interface that is up and addressed
+pub fn select_primary_interface<'a>(
+    interfaces: &'a [NetworkInterface],
+    preferred: Option<&str>,
+    default_route: &DefaultRoute,
+) -> Option<(&'a NetworkInterface, PrimaryInterfaceSource)> {
+    let by_name = |name: &str| interfaces.iter().find(|interface| interface.name == name);
+
+    if let Some(interface) = preferred.and_then(by_name) {
+        return Some((interface, PrimaryInterfaceSource::Preferred));
+    }
+    if let Some(interface) = default_route.interface.as_deref().and_then(by_name) {
+        return Some((interface, PrimaryInterfaceSource::DefaultRoute));
+    }
+    let mut candidates: Vec<&NetworkInterface> = interfaces
+        .iter()
+        .filter(|interface| {
+            interface.is_up
+                && !matches!(interface.interface_type, InterfaceType::Loopback)
+                && !interface.ipv4_addresses.is_empty()
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.name.cmp(&b.name));
+    candidates
+        .first()
+        .map(|interface| (*interface, PrimaryInterfaceSource::FirstUp))
+}
+
+// Current association of a wireless interface; every detail is `None` when not connected
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WifiLinkStatus {
+    pub connected: bool,
+    pub ssid: Option<String>,
+    pub bssid: Option<MacAddress>,
+    pub freq_mhz: Option<u32>,
+    pub signal_dbm: Option<i32>,
+    pub tx_bitrate: Option<String>,
+}
+
+// A named site setup: the WiFi network to join plus, optionally, static addressing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    pub id: String,
+    pub name: String,
+    pub wifi_config_id: String,
+    pub static_ip_config_id: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl NetworkProfile {
+    pub fn new(name: String, wifi_config_id: String, static_ip_config_id: Option<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            wifi_config_id,
+            static_ip_config_id,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    pub fn with_created_at(mut self, created_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyOutcome {
+    pub applied: bool,
+    // The requested state was already in place, so nothing was written or applied
+    #[serde(default)]
+    pub already_active: bool,
+    pub detail: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScannedWifiNetwork {
     pub ssid: String,
     pub mac: String,
-    pub signal_level: String,
+    // Signal in dBm, normalized from `signal_raw` after the scan; None when it could not be read
+    #[serde(default)]
+    pub signal_level: Option<i32>,
+    // Signal exactly as the scanner backend reported it
+    pub signal_raw: String,
+    // Channel exactly as the scanner backend reported it: a channel number, a frequency in MHz or empty
     pub channel: String,
+    // Parsed from `channel` after the scan
+    #[serde(default)]
+    pub channel_number: Option<u16>,
+    #[serde(default)]
+    pub band: Option<WifiBand>,
     pub security: String,
 }
 
+impl ScannedWifiNetwork {
+    // A network that hides its name: the scan only drops empty SSIDs, but some drivers report
+    // hidden networks as whitespace or NUL bytes (raw, or escaped as "\x00")
+    pub fn is_hidden(&self) -> bool {
+        self.ssid.replace("\\x00", "").chars().all(|c| c.is_whitespace() || c == '\0')
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WifiBand {
+    #[serde(rename = "2.4GHz")]
+    TwoPointFour,
+    #[serde(rename = "5GHz")]
+    Five,
+    #[serde(rename = "6GHz")]
+    Six,
+}
+
+impl WifiBand {
+    // Centre frequencies (MHz) of the band's 20MHz channels, as wpa_supplicant's freq_list wants them
+    pub fn channel_frequencies(self) -> Vec<u32> {
+        match self {
+            // Channels 1-13, plus Japan's channel 14
+            WifiBand::TwoPointFour => (2412..=2472).step_by(5).chain([2484]).collect(),
+            // UNII-1/2 (channels 36-64), UNII-2e (100-144) and UNII-3/4 (149-177)
+            WifiBand::Five => (5180..=5320)
+                .step_by(20)
+                .chain((5500..=5720).step_by(20))
+                .chain((5745..=5885).step_by(20))
+                .collect(),
+            // Channels 1-233
+            WifiBand::Six => (5955..=7115).step_by(20).collect(),
+        }
+    }
+}
+
+impl std::str::FromStr for MacAddress {
+    type Err = String;
+
+    // Accepts aa:bb:cc:dd:ee:ff, AA-BB-CC-DD-EE-FF and aabb.ccdd.eeff
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid MAC address: {}", input);
+        let value = input.trim();
+
+        let (separator, group_len) = if value.contains(':') {
+            (':', 2)
+        } else if value.contains('-') {
+            ('-', 2)
+        } else if value.contains('.') {
+            ('.', 4)
+        } else {
+            return Err(invalid());
+        };
+
+        let groups: Vec<&str> = value.split(separator).collect();
+        if groups.len() != 12 / group_len
+            || groups.iter().any(|group| group.len() != group_len || !group.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            return Err(invalid());
+        }
+
+        let hex = groups.concat().to_ascii_lowercase();
+        let octets: Vec<&str> = (0..6).map(|i| &hex[i * 2..i * 2 + 2]).collect();
+        Ok(Self(octets.join(":")))
+    }
+}
+
+impl std::fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for MacAddress {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<MacAddress> for String {
+    fn from(mac: MacAddress) -> Self {
+        mac.0
+    }
+}
+
 impl WifiConfig {
-    pub fn new(ssid: String, password: String, security_type: WifiSecurityType) -> Self {
+    pub fn new(ssid: String, password: String, security_type: WifiSecurityType, hidden: bool) -> Self {
         Self {
             id: uuid::Uuid::new_v4().to_string(),
             ssid,
             password,
             security_type,
+            hidden,
+            auto_connect: true,
+            preferred_band: None,
             is_active: false,
             created_at: chrono::Utc::now(),
+            last_error: None,
+            last_error_at: None,
         }
     }
+
+    // Same config with a caller-chosen id instead of the generated one
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    // Checks for a config built elsewhere (e.g. imported) rather than through `NewWifiConfig`
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Err(e) = validate_config_id(&self.id) {
+            errors.push(e.to_string());
+        }
+        if let Err(e) = validate_ssid(&self.ssid) {
+            errors.push(e.to_string());
+        }
+        if let Err(e) = validate_psk(&self.security_type, &self.password) {
+            errors.push(e.to_string());
+        }
+        errors
+    }
+
+    pub fn with_auto_connect(mut self, auto_connect: bool) -> Self {
+        self.auto_connect = auto_connect;
+        self
+    }
+
+    pub fn with_preferred_band(mut self, preferred_band: Option<WifiBand>) -> Self {
+        self.preferred_band = preferred_band;
+        self
+    }
+
+    // Stamp (at `now`) or clear `last_error`; returns whether anything changed
+    pub fn record_apply_error(&mut self, error: Option<&String>, now: chrono::DateTime<chrono::Utc>) -> bool {
+        record_apply_error(&mut self.last_error, &mut self.last_error_at, error, now)
+    }
 }
 
 impl StaticIpConfig {
+    // Parses every address up front, so a config that exists is always usable
     pub fn new(
         interface_name: String,
-        ip_address: String,
-        subnet_mask: String,
-        gateway: String,
-        dns_primary: String,
-        dns_secondary: Option<String>,
-    ) -> Self {
-        Self {
+        ip_address: &str,
+        subnet_mask: &str,
+        gateway: &str,
+        dns_primary: &str,
+        dns_secondary: Option<&str>,
+        search_domains: Vec<String>,
+    ) -> Result<Self, NetworkError> {
+        let ip_address = parse_ipv4("ip_address", ip_address)?;
+        let prefix_len = parse_subnet_mask(subnet_mask)?;
+        let gateway = parse_ipv4("gateway", gateway)?;
+        let dns_primary = parse_ip("dns_primary", dns_primary)?;
+        let dns_secondary = dns_secondary
+            .map(|dns_secondary| parse_ip("dns_secondary", dns_secondary))
+            .transpose()?;
+
+        Ok(Self {
             id: uuid::Uuid::new_v4().to_string(),
             interface_name,
             ip_address,
-            subnet_mask,
+            prefix_len,
             gateway,
             dns_primary,
             dns_secondary,
+            search_domains,
             is_enabled: false,
+            enabled_at: None,
+            disabled_at: None,
             created_at: chrono::Utc::now(),
+            updated_at: None,
+            last_error: None,
+            last_error_at: None,
+        })
+    }
+
+    // Same config with a caller-chosen id instead of the generated one
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    // What `new` can't check on its own: the id and how the parsed values fit together
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Err(e) = validate_config_id(&self.id) {
+            errors.push(e.to_string());
+        }
+        if let Err(e) = validate_interface_name(&self.interface_name) {
+            errors.push(e.to_string());
+        }
+        if let Err(e) = gateway_in_subnet(self.ip_address, self.prefix_len, self.gateway) {
+            errors.push(e.to_string());
+        }
+        for domain in &self.search_domains {
+            if let Err(e) = validate_domain_name(domain) {
+                errors.push(format!("search domain {}: {}", domain, e));
+            }
+        }
+        errors
+    }
+
+    // Stamp (at `now`) or clear `last_error`; returns whether anything changed
+    pub fn record_apply_error(&mut self, error: Option<&String>, now: chrono::DateTime<chrono::Utc>) -> bool {
+        record_apply_error(&mut self.last_error, &mut self.last_error_at, error, now)
+    }
+
+    // DNS servers in resolver order
+    pub fn dns_servers(&self) -> Vec<std::net::IpAddr> {
+        std::iter::once(self.dns_primary).chain(self.dns_secondary).collect()
+    }
+
+    // Put the existing DNS servers in a new order; `order` must be a permutation of them
+    pub fn reorder_dns_servers(&mut self, order: Vec<String>) -> Result<(), NetworkError> {
+        let not_a_reordering = || {
+            let current: Vec<String> = self.dns_servers().iter().map(|server| server.to_string()).collect();
+            NetworkError::Validation(format!(
+                "dns_servers must be a reordering of the current servers: {}",
+                current.join(", ")
+            ))
+        };
+        let order: Vec<std::net::IpAddr> = order
+            .iter()
+            .map(|server| server.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| not_a_reordering())?;
+        let mut current = self.dns_servers();
+        let mut requested = order.clone();
+        current.sort();
+        requested.sort();
+        if current != requested {
+            return Err(not_a_reordering());
+        }
+
+        self.dns_primary = order[0];
+        self.dns_secondary = order.get(1).copied();
+        Ok(())
+    }
+
+    // Swap in a new set of DNS servers, in resolver order; the addressing is left alone
+    pub fn replace_dns_servers(&mut self, servers: Vec<String>) -> Result<(), NetworkError> {
+        let servers: Vec<std::net::IpAddr> = servers
+            .iter()
+            .map(|server| parse_ip("dns_servers", server.trim()))
+            .collect::<Result<_, _>>()?;
+        match servers.as_slice() {
+            [] => Err(NetworkError::Validation("dns_servers must not be empty".to_string())),
+            [primary, secondary] if primary == secondary => Err(NetworkError::Validation(format!(
+                "dns_servers lists {} twice",
+                primary
+            ))),
+            [primary] | [primary, _] => {
+                self.dns_primary = *primary;
+                self.dns_secondary = servers.get(1).copied();
+                Ok(())
+            }
+            _ => Err(NetworkError::Validation("dns_servers takes at most 2 servers".to_string())),
+        }
+    }
+
+    pub fn subnet_mask(&self) -> std::net::Ipv4Addr {
+        prefix_len_to_ipv4_mask(self.prefix_len)
+    }
+
+    // The subnet the address is in, in CIDR notation, e.g. 192.168.1.0/24
+    pub fn subnet(&self) -> String {
+        format!("{}/{}", network_address(self.ip_address, self.prefix_len), self.prefix_len)
+    }
+
+    // Enabled configs on other interfaces whose subnet overlaps this one's. Enabling both makes
+    // routing to that range ambiguous. Configs on the same interface replace each other instead.
+    pub fn overlapping_enabled<'a>(&self, configs: &'a [StaticIpConfig]) -> Vec<&'a StaticIpConfig> {
+        configs
+            .iter()
+            .filter(|config| config.is_enabled && config.id != self.id && config.interface_name != self.interface_name)
+            .filter(|config| subnets_overlap(self.ip_address, self.prefix_len, config.ip_address, config.prefix_len))
+            .collect()
+    }
+
+    // How an overlapping config is named in conflicts and warnings
+    pub fn describe_overlap(&self) -> String {
+        format!("{} on {} (config {})", self.subnet(), self.interface_name, self.id)
+    }
+}
+
+// Serde for a prefix length that is stored as a dotted subnet mask
+mod dotted_subnet_mask {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(prefix_len: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::prefix_len_to_ipv4_mask(*prefix_len).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+        let subnet_mask = String::deserialize(deserializer)?;
+        super::mask_to_prefix_len(&subnet_mask)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid subnet mask: {}", subnet_mask)))
+    }
+}
+
+impl NewWifiConfig {
+    // The SSID as it will be stored: surrounding whitespace is almost always a copy-paste or
+    // typing slip, so it is trimmed unless `exact_ssid` asks to keep it
+    pub fn effective_ssid(&self) -> &str {
+        if self.exact_ssid || self.ssid.trim().is_empty() { &self.ssid } else { self.ssid.trim() }
+    }
+
+    // Every problem with the input, empty when it can be saved
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if let Some(id) = &self.id
+            && let Err(e) = validate_config_id(id)
+        {
+            errors.push(e.to_string());
+        }
+        if let Err(e) = validate_ssid(self.effective_ssid()) {
+            errors.push(e.to_string());
+        }
+        if let Err(e) = validate_psk(&self.security_type, &self.password) {
+            errors.push(e.to_string());
+        }
+
+        errors
+    }
+}
+
+impl NewStaticIpConfig {
+    // Every problem with the input, empty when it can be saved
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if let Some(id) = &self.id
+            && let Err(e) = validate_config_id(id)
+        {
+            errors.push(e.to_string());
+        }
+        if self.interface_name.trim().is_empty() {
+            errors.push("interface_name is required".to_string());
+        } else if validate_interface_name(&self.interface_name).is_err() {
+            errors.push(format!("interface_name is not a valid interface name: {}", self.interface_name));
+        }
+        let ip_address = parse_ipv4("ip_address", &self.ip_address);
+        if let Err(e) = &ip_address {
+            errors.push(e.to_string());
+        }
+        let prefix_len = self
+            .resolve_subnet_mask()
+            .and_then(|mask| parse_subnet_mask(&mask).map_err(NetworkError::from));
+        if let Err(e) = &prefix_len {
+            errors.push(e.to_string());
+        }
+        let gateway = parse_ipv4("gateway", &self.gateway);
+        if let Err(e) = &gateway {
+            errors.push(e.to_string());
+        }
+        if let (Ok(ip_address), Ok(prefix_len), Ok(gateway)) = (ip_address, prefix_len, gateway)
+            && let Err(e) = gateway_in_subnet(ip_address, prefix_len, gateway)
+        {
+            errors.push(e.to_string());
+        }
+        if let Err(e) = parse_ip("dns_primary", &self.dns_primary) {
+            errors.push(e.to_string());
+        }
+        if let Some(dns_secondary) = &self.dns_secondary
+            && let Err(e) = parse_ip("dns_secondary", dns_secondary)
+        {
+            errors.push(e.to_string());
+        }
+        for domain in &self.search_domains {
+            if let Err(e) = validate_domain_name(domain) {
+                errors.push(format!("search domain {}: {}", domain, e));
+            }
+        }
+
+        errors
+    }
+
+    // First and last assignable address of the subnet the entered IP and mask describe; `None`
+    // while either is invalid or for /31 and /32
+    pub fn usable_host_range(&self) -> Option<(std::net::Ipv4Addr, std::net::Ipv4Addr)> {
+        let ip_address = self.ip_address.trim().parse().ok()?;
+        let subnet_mask = self.resolve_subnet_mask().ok()?.parse().ok()?;
+        usable_host_range(ip_address, subnet_mask)
+    }
+
+    // Dotted subnet mask to store. When both forms are supplied they must describe the same netmask.
+    pub fn resolve_subnet_mask(&self) -> Result<String, NetworkError> {
+        let from_prefix = match self.prefix_len {
+            Some(prefix_len) => Some(prefix_len_to_mask(prefix_len).ok_or_else(|| {
+                NetworkError::Validation(format!("prefix /{} is out of range (0-32)", prefix_len))
+            })?),
+            None => None,
+        };
+
+        match (&self.subnet_mask, from_prefix) {
+            (Some(subnet_mask), Some(from_prefix)) => {
+                parse_subnet_mask(subnet_mask)?;
+                if *subnet_mask != from_prefix {
+                    return Err(NetworkError::Validation(format!(
+                        "subnet_mask {} does not match prefix /{}",
+                        subnet_mask,
+                        self.prefix_len.unwrap_or_default()
+                    )));
+                }
+                Ok(from_prefix)
+            }
+            (Some(subnet_mask), None) => {
+                parse_subnet_mask(subnet_mask)?;
+                Ok(subnet_mask.clone())
+            }
+            (None, Some(from_prefix)) => Ok(from_prefix),
+            (None, None) => Err(NetworkError::Validation(
+                "Either subnet_mask or prefix_len is required".to_string(),
+            )),
+        }
+    }
+}
+
+fn record_apply_error(
+    last_error: &mut Option<String>,
+    last_error_at: &mut Option<chrono::DateTime<chrono::Utc>>,
+    error: Option<&String>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> bool {
+    match error {
+        Some(error) => {
+            *last_error = Some(error.clone());
+            *last_error_at = Some(now);
+            true
+        }
+        None if last_error.is_some() => {
+            *last_error = None;
+            *last_error_at = None;
+            true
+        }
+        None => false,
+    }
+}
+
+// What creating a config does once the configured maximum is reached
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigLimitPolicy {
+    // Refuse the new config
+    #[default]
+    Reject,
+    // Delete the oldest configs that are not active/enabled to make room
+    EvictOldest,
+}
+
+impl std::str::FromStr for ConfigLimitPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "reject" => Ok(ConfigLimitPolicy::Reject),
+            "evict-oldest" => Ok(ConfigLimitPolicy::EvictOldest),
+            _ => Err(format!("Unknown config limit policy: {}", value)),
+        }
+    }
+}
+
+// Maximum number of stored configs per kind; 0 means unlimited
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfigLimits {
+    pub max_wifi_configs: usize,
+    pub max_static_ip_configs: usize,
+    pub policy: ConfigLimitPolicy,
+}
+
+// Addresses between the network and broadcast address, or `None` for a non-contiguous mask and
+// for /31 and /32, which have no network/broadcast pair to exclude
+pub fn usable_host_range(
+    ip_address: std::net::Ipv4Addr,
+    subnet_mask: std::net::Ipv4Addr,
+) -> Option<(std::net::Ipv4Addr, std::net::Ipv4Addr)> {
+    let prefix_len = mask_to_prefix_len(&subnet_mask.to_string())?;
+    if prefix_len > 30 {
+        return None;
+    }
+    let mask = u32::from(subnet_mask);
+    let network = u32::from(ip_address) & mask;
+    let broadcast = network | !mask;
+    Some((
+        std::net::Ipv4Addr::from(network + 1),
+        std::net::Ipv4Addr::from(broadcast - 1),
+    ))
+}
+
+// Dotted subnet mask for a prefix length, or `None` if the prefix is longer than 32
+pub fn prefix_len_to_mask(prefix_len: u8) -> Option<String> {
+    if prefix_len > 32 {
+        return None;
+    }
+    Some(prefix_len_to_ipv4_mask(prefix_len).to_string())
+}
+
+// Netmask for a prefix length; anything past 32 is treated as /32
+fn prefix_len_to_ipv4_mask(prefix_len: u8) -> std::net::Ipv4Addr {
+    let bits = u32::MAX.checked_shl(32 - prefix_len.min(32) as u32).unwrap_or(0);
+    std::net::Ipv4Addr::from(bits)
+}
+
+// Scanner backends disagree on units: Linux reports dBm ("-52" or "-52 dBm"), others a
+// percentage ("80%", or a bare 0-100) or a quality fraction ("56/70"). Percentages are mapped
+// linearly onto -100..-50 dBm, the same approximation NetworkManager uses.
+pub fn normalize_signal_level(raw: &str) -> Option<i32> {
+    let value = raw.trim();
+    let value = value
+        .strip_suffix("dBm")
+        .or_else(|| value.strip_suffix("dbm"))
+        .unwrap_or(value)
+        .trim();
+
+    let percentage = |percent: f64| (percent.clamp(0.0, 100.0) / 2.0 - 100.0).round() as i32;
+    if let Some(percent) = value.strip_suffix('%') {
+        return percent.trim().parse::<f64>().ok().map(percentage);
+    }
+    if let Some((quality, max)) = value.split_once('/') {
+        let quality: f64 = quality.trim().parse().ok()?;
+        let max: f64 = max.trim().parse().ok()?;
+        return (max > 0.0).then(|| percentage(quality / max * 100.0));
+    }
+
+    let number: f64 = value.parse().ok()?;
+    if !number.is_finite() {
+        return None;
+    }
+    match number {
+        // Real-world dBm readings; anything lower is garbage
+        n if (-120.0..0.0).contains(&n) => Some(n.round() as i32),
+        n if (0.0..=100.0).contains(&n) => Some(percentage(n)),
+        _ => None,
+    }
+}
+
+// Inverse of the percentage mapping above: -100 dBm and weaker is 0%, -50 dBm and stronger 100%
+pub fn signal_quality_percent(dbm: i32) -> u8 {
+    ((dbm.clamp(-100, -50) + 100) * 2) as u8
+}
+
+// Scanner backends report either a channel number ("6", or "36,+1" on macOS) or a frequency
+// in MHz ("5180"). Bare channel numbers 1-14 are taken as 2.4GHz and higher ones as 5GHz, since
+// 6GHz networks can only be told apart by their frequency.
+pub fn parse_wifi_channel(raw: &str) -> (Option<u16>, Option<WifiBand>) {
+    let value = raw.trim();
+    let value = value
+        .strip_suffix("MHz")
+        .or_else(|| value.strip_suffix("mhz"))
+        .unwrap_or(value)
+        .trim();
+    let digits: String = value.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let Ok(number) = digits.parse::<u16>() else {
+        return (None, None);
+    };
+
+    match number {
+        1..=14 => (Some(number), Some(WifiBand::TwoPointFour)),
+        32..=177 => (Some(number), Some(WifiBand::Five)),
+        2484 => (Some(14), Some(WifiBand::TwoPointFour)),
+        2412..=2472 => (Some((number - 2407) / 5), Some(WifiBand::TwoPointFour)),
+        5160..=5885 => (Some((number - 5000) / 5), Some(WifiBand::Five)),
+        5955..=7115 => (Some((number - 5950) / 5), Some(WifiBand::Six)),
+        _ => (None, None),
+    }
+}
+
+impl InterfaceStats {
+    // Byte rates between an earlier sample and this one
+    pub fn rate_since(&self, interface_name: String, previous: &InterfaceStats, elapsed: std::time::Duration) -> InterfaceRate {
+        let seconds = elapsed.as_secs_f64();
+        let per_second = |delta: u64| if seconds > 0.0 { delta as f64 / seconds } else { 0.0 };
+
+        InterfaceRate {
+            interface_name,
+            rx_bytes_per_sec: per_second(counter_delta(previous.rx_bytes, self.rx_bytes)),
+            tx_bytes_per_sec: per_second(counter_delta(previous.tx_bytes, self.tx_bytes)),
+            interval_ms: elapsed.as_millis() as u64,
+        }
+    }
+}
+
+// Difference between two counter samples, accounting for 32-bit counters wrapping around.
+// A drop on a counter that was already past the 32-bit range means it was reset, so count nothing.
+fn counter_delta(previous: u64, current: u64) -> u64 {
+    if current >= previous {
+        current - previous
+    } else if previous <= u64::from(u32::MAX) {
+        (u64::from(u32::MAX) - previous) + current + 1
+    } else {
+        0
+    }
+}
+
+impl InterfaceAdminState {
+    pub fn new(interface_name: String, desired_up: bool) -> Self {
+        Self {
+            interface_name,
+            desired_up,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::static_ip;
+
+    fn with_mask(subnet_mask: Option<&str>, prefix_len: Option<u8>) -> NewStaticIpConfig {
+        NewStaticIpConfig {
+            subnet_mask: subnet_mask.map(str::to_string),
+            prefix_len,
+            ..static_ip("eth0", "192.168.1.60")
+        }
+    }
+
+    #[test]
+    fn matching_mask_and_prefix_are_accepted() {
+        let config = with_mask(Some("255.255.255.0"), Some(24));
+        assert_eq!(config.resolve_subnet_mask().unwrap(), "255.255.255.0");
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn mismatching_mask_and_prefix_are_rejected() {
+        let config = with_mask(Some("255.255.0.0"), Some(24));
+        let error = config.resolve_subnet_mask().unwrap_err();
+        assert_eq!(error.to_string(), "subnet_mask 255.255.0.0 does not match prefix /24");
+        assert!(!config.validate().is_empty());
+    }
+
+    #[test]
+    fn either_mask_form_alone_is_enough() {
+        assert_eq!(with_mask(Some("255.255.255.252"), None).resolve_subnet_mask().unwrap(), "255.255.255.252");
+        assert_eq!(with_mask(None, Some(16)).resolve_subnet_mask().unwrap(), "255.255.0.0");
+        assert!(with_mask(None, None).resolve_subnet_mask().is_err());
+        assert!(with_mask(None, Some(33)).resolve_subnet_mask().is_err());
+        assert!(with_mask(Some("255.0.255.0"), None).resolve_subnet_mask().is_err());
+    }
+
+    fn with_search_domains(search_domains: &[&str]) -> NewStaticIpConfig {
+        NewStaticIpConfig {
+            search_domains: search_domains.iter().map(|domain| domain.to_string()).collect(),
+            ..static_ip("eth0", "192.168.1.60")
+        }
+    }
+
+    #[test]
+    fn search_domains_are_validated() {
+        assert!(with_search_domains(&["home.lan", "lab.example.com."]).validate().is_empty());
+        // No search domains at all is fine
+        assert!(with_search_domains(&[]).validate().is_empty());
+
+        let overlong = format!("{}.lan", "a".repeat(64));
+        let errors = with_search_domains(&["home.lan", &overlong]).validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with(&format!("search domain {}: ", overlong)), "{}", errors[0]);
+        assert!(errors[0].contains("longer than 63 characters"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn static_ip_interface_names_are_validated() {
+        for interface_name in ["eth0 up", "eth0;reboot", "$(reboot)"] {
+            let errors = static_ip(interface_name, "192.168.1.60").validate();
+            assert_eq!(errors, vec![format!("interface_name is not a valid interface name: {}", interface_name)]);
+        }
+        assert!(static_ip("eth0.100", "192.168.1.60").validate().is_empty());
+    }
+
+    #[test]
+    fn mac_addresses_are_normalized_from_each_format() {
+        for input in ["aa:bb:cc:0d:0e:ff", "AA-BB-CC-0D-0E-FF", "aabb.cc0d.0eff", " AA:bb:CC:0d:0E:ff "] {
+            let mac: MacAddress = input.parse().unwrap();
+            assert_eq!(mac.to_string(), "aa:bb:cc:0d:0e:ff", "{}", input);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn malformed_mac_addresses_are_rejected() {
+        for input in ["", "aabbccddeeff", "aa:bb:cc:dd:ee", "aa:bb:cc:dd:ee:ff:00", "aa:bb:cc:dd:ee:fg", "a:bb:cc:dd:ee:fff", "aa:bb-cc:dd:ee:ff", "aabb.ccdd.eef"] {
+            assert!(input.parse::<MacAddress>().is_err(), "{}", input);
+        }
+        assert!(serde_json::from_str::<MacAddress>("\"not a mac\"").is_err());
+    }
+
+    #[test]
+    fn counter_delta_handles_growth_wraps_and_resets() {
+        assert_eq!(counter_delta(100, 250), 150);
+        assert_eq!(counter_delta(u64::from(u32::MAX), 4), 5);
+        assert_eq!(counter_delta(u64::from(u32::MAX) - 9, 10), 20);
+        // A 64-bit counter going backwards was reset, not wrapped
+        assert_eq!(counter_delta(u64::from(u32::MAX) + 10, 5), 0);
+    }
+
+    #[test]
+    fn signal_levels_normalize_to_dbm() {
+        assert_eq!(normalize_signal_level("-67 dBm"), Some(-67));
+        assert_eq!(normalize_signal_level("-67.4"), Some(-67));
+        assert_eq!(normalize_signal_level("70%"), Some(-65));
+        assert_eq!(normalize_signal_level("35/70"), Some(-75));
+        assert_eq!(normalize_signal_level("100"), Some(-50));
+        assert_eq!(normalize_signal_level("garbage"), None);
+        assert_eq!(normalize_signal_level("-500 dBm"), None);
+        assert_eq!(normalize_signal_level("3/0"), None);
+        assert_eq!(normalize_signal_level(""), None);
+    }
+
+    #[test]
+    fn primary_interface_prefers_the_pin_then_falls_back() {
+        let interface = |name: &str, interface_type, ipv4: Option<&str>| NetworkInterface {
+            name: name.to_string(),
+            interface_type,
+            mac_address: None,
+            is_up: true,
+            has_carrier: None,
+            ipv4_addresses: ipv4.map(str::to_string).into_iter().collect(),
+            ipv6_addresses: Vec::new(),
+            ipv4_cidrs: Vec::new(),
+            ipv6_cidrs: Vec::new(),
+            current_ip: ipv4.map(str::to_string),
+        };
+        let interfaces = vec![
+            interface("lo", InterfaceType::Loopback, Some("127.0.0.1")),
+            interface("wlan0", InterfaceType::Wireless, Some("192.168.1.20")),
+            interface("eth0", InterfaceType::Ethernet, Some("192.168.1.10")),
+        ];
+        let no_route = DefaultRoute::default();
+        let route = DefaultRoute { gateway: Some("192.168.1.1".to_string()), interface: Some("wlan0".to_string()) };
+        let select = |preferred, route| {
+            select_primary_interface(&interfaces, preferred, route).map(|(interface, source)| (interface.name.as_str(), source))
+        };
+
+        assert_eq!(select(Some("eth0"), &route), Some(("eth0", PrimaryInterfaceSource::Preferred)));
+        // A pinned interface that is not present falls back to the default route
+        assert_eq!(select(Some("usb0"), &route), Some(("wlan0", PrimaryInterfaceSource::DefaultRoute)));
+        assert_eq!(select(None, &no_route), Some(("eth0", PrimaryInterfaceSource::FirstUp)));
+        assert_eq!(select_primary_interface(&interfaces[..1], None, &no_route).map(|(_, source)| source), None);
+    }
+
+    #[test]
+    fn wifi_channels_parse_from_numbers_or_frequencies() {
+        assert_eq!(parse_wifi_channel("6"), (Some(6), Some(WifiBand::TwoPointFour)));
+        assert_eq!(parse_wifi_channel("5180"), (Some(36), Some(WifiBand::Five)));
+        assert_eq!(parse_wifi_channel("2437 MHz"), (Some(6), Some(WifiBand::TwoPointFour)));
+        assert_eq!(parse_wifi_channel("2484"), (Some(14), Some(WifiBand::TwoPointFour)));
+        assert_eq!(parse_wifi_channel("5955"), (Some(1), Some(WifiBand::Six)));
+        assert_eq!(parse_wifi_channel(""), (None, None));
+        assert_eq!(parse_wifi_channel("900"), (None, None));
+    }
+
+    #[test]
+    fn usable_host_range_excludes_network_and_broadcast() {
+        let range = |prefix_len| {
+            let (first, last) = with_mask(None, Some(prefix_len)).usable_host_range()?;
+            Some((first.to_string(), last.to_string()))
+        };
+        assert_eq!(range(24), Some(("192.168.1.1".to_string(), "192.168.1.254".to_string())));
+        assert_eq!(range(30), Some(("192.168.1.61".to_string(), "192.168.1.62".to_string())));
+        assert_eq!(range(31), None);
+        assert_eq!(range(32), None);
+    }
+
+    #[test]
+    fn static_ip_configs_reject_invalid_addresses() {
+        let config = |ip_address, subnet_mask, gateway, dns_primary, dns_secondary| {
+            StaticIpConfig::new("eth0".to_string(), ip_address, subnet_mask, gateway, dns_primary, dns_secondary, Vec::new())
+        };
+        let config_error = |result: Result<StaticIpConfig, NetworkError>| match result {
+            Err(NetworkError::Validation(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        };
+
+        let valid = config("192.168.1.60", "255.255.255.0", "192.168.1.1", "2606:4700::1111", Some("1.1.1.1")).unwrap();
+        assert_eq!(valid.prefix_len, 24);
+        assert!(config_error(config("192.168.1.300", "255.255.255.0", "192.168.1.1", "1.1.1.1", None)).starts_with("ip_address"));
+        assert!(config_error(config("192.168.1.60", "255.0.255.0", "192.168.1.1", "1.1.1.1", None)).contains("255.0.255.0"));
+        assert!(config_error(config("192.168.1.60", "255.255.255.0", "fe80::1", "1.1.1.1", None)).starts_with("gateway"));
+        assert!(config_error(config("192.168.1.60", "255.255.255.0", "192.168.1.1", "dns", None)).starts_with("dns_primary"));
+        assert!(config_error(config("192.168.1.60", "255.255.255.0", "192.168.1.1", "1.1.1.1", Some(""))).starts_with("dns_secondary"));
+    }
+
+    #[test]
+    fn address_conflicts_cover_live_duplicates_and_static_configs() {
+        let interface = |name: &str, is_up: bool, ipv4: &[&str], ipv6: &[&str]| NetworkInterface {
+            name: name.to_string(),
+            interface_type: InterfaceType::Ethernet,
+            mac_address: None,
+            is_up,
+            has_carrier: None,
+            ipv4_addresses: ipv4.iter().map(|a| a.to_string()).collect(),
+            ipv6_addresses: ipv6.iter().map(|a| a.to_string()).collect(),
+            ipv4_cidrs: Vec::new(),
+            ipv6_cidrs: Vec::new(),
+            current_ip: None,
+        };
+        let interfaces = vec![
+            interface("lo", true, &["127.0.0.1"], &["::1"]),
+            interface("eth0", true, &["192.168.1.50/24", "169.254.0.1"], &["fe80::1"]),
+            interface("wlan0", true, &["192.168.1.50", "169.254.0.1"], &["fe80::1"]),
+            interface("eth1", true, &["127.0.0.1", "10.0.0.5"], &[]),
+            // Down interfaces hold nothing
+            interface("eth2", false, &["10.0.0.5"], &[]),
+        ];
+        let config = |interface_name: &str, ip: &str| {
+            StaticIpConfig::new(interface_name.to_string(), ip, "255.255.255.0", "10.0.0.1", "1.1.1.1", None, Vec::new()).unwrap()
+        };
+        let on_eth1 = config("eth1", "10.0.0.5");
+        let elsewhere = config("eth2", "10.0.0.5");
+        let free = config("eth2", "10.0.0.6");
+
+        let conflicts = AddressConflict::detect(&interfaces, &[on_eth1, elsewhere.clone(), free]);
+        assert_eq!(conflicts.len(), 2, "{:?}", conflicts);
+        assert_eq!(conflicts[0].kind, AddressConflictKind::DuplicateLive);
+        assert_eq!(conflicts[0].address.to_string(), "192.168.1.50");
+        assert_eq!(conflicts[0].interfaces, vec!["eth0", "wlan0"]);
+        assert_eq!(conflicts[1].kind, AddressConflictKind::StaticConfig);
+        assert_eq!(conflicts[1].address.to_string(), "10.0.0.5");
+        assert_eq!(conflicts[1].interfaces, vec!["eth1"]);
+        assert_eq!(conflicts[1].static_ip_config_id, Some(elsewhere.id));
+        assert_eq!(conflicts[1].config_interface.as_deref(), Some("eth2"));
+    }
+}
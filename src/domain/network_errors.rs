@@ -0,0 +1,45 @@
+// Network domain errors - let the web layer tell bad input apart from server failures
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkError {
+    // The request itself is invalid; retrying it unchanged will fail again
+    Validation(String),
+    NotFound(String),
+    Conflict(String),
+    // The caller is not allowed to do this
+    Forbidden(String),
+    // A system call (e.g. a WiFi scan) did not finish in time
+    Timeout(String),
+    // Repository, system or applier failure
+    Internal(String),
+}
+
+impl NetworkError {
+    pub fn message(&self) -> &str {
+        match self {
+            NetworkError::Validation(message)
+            | NetworkError::NotFound(message)
+            | NetworkError::Conflict(message)
+            | NetworkError::Forbidden(message)
+            | NetworkError::Timeout(message)
+            | NetworkError::Internal(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+// Repositories and appliers report plain strings; those are always internal failures
+impl From<String> for NetworkError {
+    fn from(message: String) -> Self {
+        NetworkError::Internal(message)
+    }
+}
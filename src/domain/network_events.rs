@@ -0,0 +1,43 @@
+// Domain events - notable state changes pushed to an `EventSink` for outside integrations
+// (webhooks, notification bridges). Unlike the event history they are fire-and-forget.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum NetworkEventKind {
+    // A WiFi config became the active one
+    WifiActivated { id: String, ssid: String },
+    // The active WiFi config was disconnected (currently only by a forced delete)
+    WifiDeactivated { id: String, ssid: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NetworkEvent {
+    #[serde(flatten)]
+    pub kind: NetworkEventKind,
+    pub at: DateTime<Utc>,
+}
+
+impl NetworkEvent {
+    pub fn new(kind: NetworkEventKind) -> Self {
+        Self { kind, at: Utc::now() }
+    }
+}
+
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    async fn publish(&self, event: &NetworkEvent) -> Result<(), String>;
+}
+
+// Default sink: events go nowhere
+pub struct NoopEventSink;
+
+#[async_trait]
+impl EventSink for NoopEventSink {
+    async fn publish(&self, _event: &NetworkEvent) -> Result<(), String> {
+        Ok(())
+    }
+}
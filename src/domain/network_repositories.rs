@@ -6,7 +6,10 @@ use crate::domain::network_entities::*;
 #[async_trait]
 pub trait WifiConfigRepository: Send + Sync {
     async fn save(&self, config: &WifiConfig) -> Result<(), String>;
+    // Atomically save a config whose id isn't taken yet; false (and nothing saved) when it is
+    async fn insert_if_absent(&self, config: &WifiConfig) -> Result<bool, String>;
     async fn find_all(&self) -> Result<Vec<WifiConfig>, String>;
+    async fn find_by_id(&self, id: &str) -> Result<Option<WifiConfig>, String>;
     async fn find_active(&self) -> Result<Option<WifiConfig>, String>;
     async fn set_active(&self, id: &str) -> Result<(), String>;
     async fn delete(&self, id: &str) -> Result<(), String>;
@@ -15,13 +18,78 @@ pub trait WifiConfigRepository: Send + Sync {
 #[async_trait]
 pub trait StaticIpConfigRepository: Send + Sync {
     async fn save(&self, config: &StaticIpConfig) -> Result<(), String>;
+    // Atomically save a config whose id isn't taken yet; false (and nothing saved) when it is
+    async fn insert_if_absent(&self, config: &StaticIpConfig) -> Result<bool, String>;
     async fn find_all(&self) -> Result<Vec<StaticIpConfig>, String>;
-    async fn enable(&self, id: &str) -> Result<(), String>;
-    async fn disable(&self, id: &str) -> Result<(), String>;
+    async fn find_by_id(&self, id: &str) -> Result<Option<StaticIpConfig>, String>;
+    // Set `is_enabled` and stamp `enabled_at` / `disabled_at` with `at`
+    async fn enable(&self, id: &str, at: chrono::DateTime<chrono::Utc>) -> Result<(), String>;
+    async fn disable(&self, id: &str, at: chrono::DateTime<chrono::Utc>) -> Result<(), String>;
     async fn delete(&self, id: &str) -> Result<(), String>;
 }
 
 #[async_trait]
 pub trait NetworkInterfaceRepository: Send + Sync {
     async fn get_interfaces(&self) -> Result<Vec<NetworkInterface>, String>;
-}
\ No newline at end of file
+    async fn get_interface_by_name(&self, name: &str) -> Result<Option<NetworkInterface>, String>;
+    // Like the appliers, only touches the system with `apply-system` (or in simulation)
+    async fn set_link_state(&self, name: &str, up: bool) -> Result<ApplyOutcome, String>;
+    async fn get_wifi_link_status(&self, name: &str) -> Result<WifiLinkStatus, String>;
+    async fn get_default_route(&self) -> Result<DefaultRoute, String>;
+}
+
+#[async_trait]
+pub trait NetworkProfileRepository: Send + Sync {
+    async fn save(&self, profile: &NetworkProfile) -> Result<(), String>;
+    async fn find_all(&self) -> Result<Vec<NetworkProfile>, String>;
+    async fn find_by_id(&self, id: &str) -> Result<Option<NetworkProfile>, String>;
+}
+
+// Source of nearby WiFi networks; may be slow, callers apply their own timeout
+#[async_trait]
+pub trait WifiScanner: Send + Sync {
+    async fn scan(&self) -> Result<Vec<ScannedWifiNetwork>, String>;
+}
+
+// Checks whether another host on the link already answers for an address
+#[async_trait]
+pub trait IpProber: Send + Sync {
+    // Short name of the probe technique, e.g. "arp" or "icmp"
+    fn method(&self) -> &'static str;
+    async fn probe(&self, interface_name: &str, ip_address: std::net::Ipv4Addr) -> Result<bool, String>;
+}
+
+// Times DNS queries against individual resolvers
+#[async_trait]
+pub trait DnsProber: Send + Sync {
+    // Resolvers the system uses, for when no static IP config sets any
+    async fn system_resolvers(&self) -> Result<Vec<std::net::IpAddr>, String>;
+    // Round trip of one A query for `hostname`; any answer counts, including NXDOMAIN
+    async fn query(&self, server: std::net::IpAddr, hostname: &str, timeout: std::time::Duration) -> Result<std::time::Duration, String>;
+}
+
+#[async_trait]
+pub trait InterfaceAdminStateRepository: Send + Sync {
+    async fn save(&self, state: &InterfaceAdminState) -> Result<(), String>;
+    async fn find_all(&self) -> Result<Vec<InterfaceAdminState>, String>;
+}
+
+// Small persisted key-value store for user preferences
+#[async_trait]
+pub trait SettingsRepository: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<String>, String>;
+    // `None` removes the key
+    async fn set(&self, key: &str, value: Option<String>) -> Result<(), String>;
+}
+
+// Append-only log of interface events and config changes
+#[async_trait]
+pub trait EventHistoryRepository: Send + Sync {
+    async fn append(&self, event: &HistoryEvent) -> Result<(), String>;
+    async fn query(&self, filter: &EventHistoryFilter) -> Result<Vec<HistoryEvent>, String>;
+}
+
+#[async_trait]
+pub trait InterfaceStatsRepository: Send + Sync {
+    async fn get_stats(&self, interface_name: &str) -> Result<InterfaceStats, String>;
+}
@@ -2,60 +2,244 @@
 
 use async_trait::async_trait;
 use std::sync::Arc;
+use crate::domain::clock::{Clock, SystemClock};
 use crate::domain::network_entities::*;
 use crate::domain::network_repositories::*;
+use crate::domain::network_appliers::*;
+use crate::domain::network_errors::NetworkError;
+use crate::domain::network_events::{EventSink, NetworkEvent, NetworkEventKind, NoopEventSink};
+use crate::domain::notifications::{ConfigChange, NotificationService};
+use crate::domain::validation::{is_valid_interface_name, validate_interface_name};
 
 #[async_trait]
 pub trait NetworkConfigService: Send + Sync {
-    async fn create_wifi_config(&self, ssid: String, password: String, security_type: WifiSecurityType) -> Result<WifiConfig, String>;
+    fn validate_wifi_config(&self, new_config: &NewWifiConfig) -> Vec<String>;
+    async fn create_wifi_config(&self, new_config: NewWifiConfig) -> Result<WifiConfig, NetworkError>;
     async fn get_wifi_configs(&self) -> Result<Vec<WifiConfig>, String>;
     async fn get_active_wifi_config(&self) -> Result<Option<WifiConfig>, String>;
-    async fn activate_wifi_config(&self, id: &str) -> Result<(), String>;
-    async fn delete_wifi_config(&self, id: &str) -> Result<(), String>;
+    async fn activate_wifi_config(&self, id: &str) -> Result<ApplyOutcome, NetworkError>;
+    async fn delete_wifi_config(&self, id: &str, force: bool) -> Result<(), NetworkError>;
+    async fn set_wifi_auto_connect(&self, id: &str, auto_connect: bool) -> Result<WifiConfig, NetworkError>;
+    async fn apply_wifi_config_unmarked(&self, id: &str) -> Result<ApplyOutcome, NetworkError>;
+    async fn mark_wifi_config_active(&self, id: &str) -> Result<WifiConfig, NetworkError>;
+    async fn deactivate_wifi_config(&self, id: &str) -> Result<ApplyOutcome, NetworkError>;
     
-    async fn create_static_ip_config(
-        &self,
-        interface_name: String,
-        ip_address: String,
-        subnet_mask: String,
-        gateway: String,
-        dns_primary: String,
-        dns_secondary: Option<String>,
-    ) -> Result<StaticIpConfig, String>;
+    fn validate_static_ip_config(&self, new_config: &NewStaticIpConfig) -> Vec<String>;
+    async fn create_static_ip_config(&self, new_config: NewStaticIpConfig) -> Result<StaticIpConfig, NetworkError>;
     async fn get_static_ip_configs(&self) -> Result<Vec<StaticIpConfig>, String>;
-    async fn enable_static_ip(&self, id: &str) -> Result<(), String>;
-    async fn disable_static_ip(&self, id: &str) -> Result<(), String>;
+    async fn reorder_static_ip_dns(&self, id: &str, dns_servers: Vec<String>) -> Result<StaticIpConfig, NetworkError>;
+    async fn replace_static_ip_dns(&self, id: &str, dns_servers: Vec<String>) -> Result<StaticIpConfig, NetworkError>;
+    async fn enable_static_ip(&self, id: &str, takeover: bool, force: bool) -> Result<ApplyOutcome, NetworkError>;
+    async fn disable_static_ip(&self, id: &str) -> Result<ApplyOutcome, String>;
     async fn delete_static_ip_config(&self, id: &str) -> Result<(), String>;
+    async fn import_configs(&self, wifi_configs: Vec<WifiConfig>, static_ip_configs: Vec<StaticIpConfig>) -> Result<ImportReport, NetworkError>;
     
     async fn get_network_interfaces(&self) -> Result<Vec<NetworkInterface>, String>;
-    async fn scan_wifi_networks(&self) -> Result<Vec<ScannedWifiNetwork>, String>;
+    async fn get_network_interface(&self, interface_name: &str) -> Result<NetworkInterface, NetworkError>;
+    async fn set_interface_admin_state(&self, interface_name: String, up: bool) -> Result<(InterfaceAdminState, ApplyOutcome), NetworkError>;
+    async fn reconcile_interface_admin_states(&self) -> Result<Vec<String>, String>;
+    async fn reconcile_configs(&self) -> Result<ReconcileReport, String>;
+    async fn get_interface_rate(&self, interface_name: &str, interval: std::time::Duration) -> Result<InterfaceRate, NetworkError>;
+    async fn get_all_interface_stats(&self) -> Result<std::collections::BTreeMap<String, InterfaceStats>, String>;
+    async fn get_wifi_link_status(&self, interface_name: &str) -> Result<WifiLinkStatus, NetworkError>;
+    async fn get_default_route(&self) -> Result<DefaultRoute, String>;
+    async fn scan_wifi_networks(&self) -> Result<Vec<ScannedWifiNetwork>, NetworkError>;
 }
 
 pub struct NetworkConfigServiceImpl {
     wifi_repository: Arc<dyn WifiConfigRepository>,
     static_ip_repository: Arc<dyn StaticIpConfigRepository>,
     interface_repository: Arc<dyn NetworkInterfaceRepository>,
+    admin_state_repository: Arc<dyn InterfaceAdminStateRepository>,
+    stats_repository: Arc<dyn InterfaceStatsRepository>,
+    static_ip_applier: Arc<dyn StaticIpApplier>,
+    wifi_applier: Arc<dyn WifiApplier>,
+    wifi_scanner: Arc<dyn WifiScanner>,
+    scan_timeout: std::time::Duration,
+    config_limits: ConfigLimits,
+    event_history: Option<Arc<dyn EventHistoryRepository>>,
+    event_sink: Arc<dyn EventSink>,
+    notifications: Option<Arc<dyn NotificationService>>,
+    clock: Arc<dyn Clock>,
+    // One lock per interface name, so changes to the same interface run one at a time
+    interface_locks: std::sync::Mutex<std::collections::HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    // Only one WiFi config can be active, so every change to which one is runs under this lock
+    wifi_lock: tokio::sync::Mutex<()>,
 }
 
 impl NetworkConfigServiceImpl {
+    pub const DEFAULT_SCAN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         wifi_repository: Arc<dyn WifiConfigRepository>,
         static_ip_repository: Arc<dyn StaticIpConfigRepository>,
         interface_repository: Arc<dyn NetworkInterfaceRepository>,
+        admin_state_repository: Arc<dyn InterfaceAdminStateRepository>,
+        stats_repository: Arc<dyn InterfaceStatsRepository>,
+        static_ip_applier: Arc<dyn StaticIpApplier>,
+        wifi_applier: Arc<dyn WifiApplier>,
+        wifi_scanner: Arc<dyn WifiScanner>,
     ) -> Self {
         Self {
             wifi_repository,
             static_ip_repository,
             interface_repository,
+            admin_state_repository,
+            stats_repository,
+            static_ip_applier,
+            wifi_applier,
+            wifi_scanner,
+            scan_timeout: Self::DEFAULT_SCAN_TIMEOUT,
+            config_limits: ConfigLimits::default(),
+            event_history: None,
+            event_sink: Arc::new(NoopEventSink),
+            notifications: None,
+            clock: Arc::new(SystemClock),
+            interface_locks: std::sync::Mutex::new(std::collections::HashMap::new()),
+            wifi_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn with_scan_timeout(mut self, scan_timeout: std::time::Duration) -> Self {
+        self.scan_timeout = scan_timeout;
+        self
+    }
+
+    pub fn with_config_limits(mut self, config_limits: ConfigLimits) -> Self {
+        self.config_limits = config_limits;
+        self
+    }
+
+    pub fn with_event_history(mut self, event_history: Arc<dyn EventHistoryRepository>) -> Self {
+        self.event_history = Some(event_history);
+        self
+    }
+
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = event_sink;
+        self
+    }
+
+    // Operator notifications for creates, deletes, enables and activations
+    pub fn with_notifications(mut self, notifications: Arc<dyn NotificationService>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    // Source of created_at and other timestamps this service stamps
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    // Held across the read-check-write-apply of a change to `interface_name`. Other interfaces,
+    // and operations that touch no single interface, are not blocked.
+    async fn lock_interface(&self, interface_name: &str) -> tokio::sync::OwnedMutexGuard<()> {
+        let lock = self
+            .interface_locks
+            .lock()
+            .unwrap()
+            .entry(interface_name.to_string())
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+
+    // History is best effort: a failed write is logged and never fails the change itself
+    async fn record_event(&self, event_type: HistoryEventType, subject: &str, detail: Option<String>) {
+        if let Some(event_history) = &self.event_history
+            && let Err(e) = event_history
+                .append(&HistoryEvent {
+                    timestamp: self.clock.now(),
+                    ..HistoryEvent::new(event_type, subject, detail)
+                })
+                .await
+        {
+            tracing::warn!("Failed to record {} event for {}: {}", event_type.as_str(), subject, e);
+        }
+    }
+
+    fn notify(&self, change: ConfigChange) {
+        if let Some(notifications) = &self.notifications {
+            notifications.notify(change);
+        }
+    }
+
+    // Published in the background so a slow sink never holds up the change; failures are only logged
+    fn emit(&self, kind: NetworkEventKind) {
+        let event_sink = self.event_sink.clone();
+        let event = NetworkEvent {
+            at: self.clock.now(),
+            ..NetworkEvent::new(kind)
+        };
+        tokio::spawn(async move {
+            if let Err(e) = event_sink.publish(&event).await {
+                tracing::warn!("Failed to publish {:?}: {}", event.kind, e);
+            }
+        });
+    }
+
+    // Keep applier failures on the config so they outlive the HTTP response
+    async fn record_wifi_apply(&self, mut config: WifiConfig, result: &Result<ApplyOutcome, String>) -> Result<(), String> {
+        if config.record_apply_error(result.as_ref().err(), self.clock.now()) {
+            self.wifi_repository.save(&config).await?;
+        }
+        Ok(())
+    }
+
+    async fn record_static_ip_apply(&self, mut config: StaticIpConfig, result: &Result<ApplyOutcome, String>) -> Result<(), String> {
+        if config.record_apply_error(result.as_ref().err(), self.clock.now()) {
+            self.static_ip_repository.save(&config).await?;
         }
+        Ok(())
     }
 }
 
 #[async_trait]
 impl NetworkConfigService for NetworkConfigServiceImpl {
-    async fn create_wifi_config(&self, ssid: String, password: String, security_type: WifiSecurityType) -> Result<WifiConfig, String> {
-        let config = WifiConfig::new(ssid, password, security_type);
-        self.wifi_repository.save(&config).await?;
+    fn validate_wifi_config(&self, new_config: &NewWifiConfig) -> Vec<String> {
+        new_config.validate()
+    }
+
+    async fn create_wifi_config(&self, new_config: NewWifiConfig) -> Result<WifiConfig, NetworkError> {
+        let errors = self.validate_wifi_config(&new_config);
+        if !errors.is_empty() {
+            return Err(NetworkError::Validation(errors.join("; ")));
+        }
+
+        let mut config = WifiConfig::new(
+            new_config.effective_ssid().to_string(),
+            new_config.password,
+            new_config.security_type,
+            new_config.hidden,
+        )
+        .with_auto_connect(new_config.auto_connect)
+        .with_preferred_band(new_config.preferred_band)
+        .with_created_at(self.clock.now());
+        // Cheap check before evicting anything; the insert below settles races
+        if let Some(id) = new_config.id {
+            if self.wifi_repository.find_by_id(&id).await?.is_some() {
+                return Err(NetworkError::Conflict(format!("WiFi config {} already exists", id)));
+            }
+            config = config.with_id(id);
+        }
+
+        let existing = self.wifi_repository.find_all().await?;
+        let evicted = configs_to_evict(
+            "WiFi",
+            self.config_limits.max_wifi_configs,
+            self.config_limits.policy,
+            existing.iter().map(|config| (config.id.as_str(), config.created_at, config.is_active)),
+        )?;
+        for id in evicted {
+            self.wifi_repository.delete(&id).await?;
+        }
+        if !self.wifi_repository.insert_if_absent(&config).await? {
+            return Err(NetworkError::Conflict(format!("WiFi config {} already exists", config.id)));
+        }
+        self.record_event(HistoryEventType::WifiCreated, &config.id, Some(config.ssid.clone())).await;
+        self.notify(ConfigChange::WifiCreated { ssid: config.ssid.clone() });
         Ok(config)
     }
 
@@ -67,32 +251,163 @@ impl NetworkConfigService for NetworkConfigServiceImpl {
         self.wifi_repository.find_active().await
     }
 
-    async fn activate_wifi_config(&self, id: &str) -> Result<(), String> {
-        self.wifi_repository.set_active(id).await
+    async fn activate_wifi_config(&self, id: &str) -> Result<ApplyOutcome, NetworkError> {
+        let _guard = self.wifi_lock.lock().await;
+        let mut config = self.wifi_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("WiFi config {} not found", id)))?;
+        // Re-activating the active config must not bounce the connection. One whose last apply
+        // failed is applied again, since that is the only way to recover from the failure.
+        if config.is_active && config.last_error.is_none() {
+            return Ok(ApplyOutcome {
+                applied: false,
+                already_active: true,
+                detail: Some("already active".to_string()),
+            });
+        }
+
+        // Only a config that made it onto the system becomes the active one
+        let result = self.wifi_applier.apply(&config).await;
+        if result.is_ok() {
+            self.wifi_repository.set_active(id).await?;
+            // Keep the local copy in step, since recording the apply saves it back
+            config.is_active = true;
+        }
+        let activated = result.is_ok();
+        self.record_wifi_apply(config.clone(), &result).await?;
+        let outcome = result?;
+        if activated {
+            self.record_event(HistoryEventType::WifiActivated, id, Some(config.ssid.clone())).await;
+            self.emit(NetworkEventKind::WifiActivated { id: config.id.clone(), ssid: config.ssid.clone() });
+            self.notify(ConfigChange::WifiActivated { ssid: config.ssid.clone() });
+        }
+        Ok(outcome)
     }
 
-    async fn delete_wifi_config(&self, id: &str) -> Result<(), String> {
-        self.wifi_repository.delete(id).await
+    // Deleting the active config could strand the box, so it takes `force`, which disconnects first
+    async fn delete_wifi_config(&self, id: &str, force: bool) -> Result<(), NetworkError> {
+        let _guard = self.wifi_lock.lock().await;
+        let config = self.wifi_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("WiFi config {} not found", id)))?;
+        if config.is_active {
+            if !force {
+                return Err(NetworkError::Conflict(format!(
+                    "WiFi config {} is active; pass force=true to disconnect and delete it",
+                    id
+                )));
+            }
+            self.wifi_applier.disconnect(&config).await?;
+            self.emit(NetworkEventKind::WifiDeactivated { id: config.id.clone(), ssid: config.ssid.clone() });
+        }
+        self.wifi_repository.delete(id).await?;
+        self.record_event(HistoryEventType::WifiDeleted, id, Some(config.ssid.clone())).await;
+        self.notify(ConfigChange::WifiDeleted { ssid: config.ssid });
+        Ok(())
     }
 
-    async fn create_static_ip_config(
-        &self,
-        interface_name: String,
-        ip_address: String,
-        subnet_mask: String,
-        gateway: String,
-        dns_primary: String,
-        dns_secondary: Option<String>,
-    ) -> Result<StaticIpConfig, String> {
-        let config = StaticIpConfig::new(
-            interface_name,
-            ip_address,
-            subnet_mask,
-            gateway,
-            dns_primary,
-            dns_secondary,
-        );
-        self.static_ip_repository.save(&config).await?;
+    // Stored only: the generated wpa_supplicant block picks it up the next time the config is applied
+    async fn set_wifi_auto_connect(&self, id: &str, auto_connect: bool) -> Result<WifiConfig, NetworkError> {
+        let _guard = self.wifi_lock.lock().await;
+        let mut config = self.wifi_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("WiFi config {} not found", id)))?;
+        config.auto_connect = auto_connect;
+        self.wifi_repository.save(&config).await?;
+        Ok(config)
+    }
+
+    // Push a config onto the system without making it the active one, so a switch can be
+    // verified before the previous config gives up that flag
+    async fn apply_wifi_config_unmarked(&self, id: &str) -> Result<ApplyOutcome, NetworkError> {
+        let _guard = self.wifi_lock.lock().await;
+        let config = self.wifi_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("WiFi config {} not found", id)))?;
+        let result = self.wifi_applier.apply(&config).await;
+        self.record_wifi_apply(config, &result).await?;
+        Ok(result?)
+    }
+
+    // Make a config active without applying it again, once a switch to it has been verified
+    async fn mark_wifi_config_active(&self, id: &str) -> Result<WifiConfig, NetworkError> {
+        let _guard = self.wifi_lock.lock().await;
+        let mut config = self.wifi_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("WiFi config {} not found", id)))?;
+        self.wifi_repository.set_active(id).await?;
+        self.record_event(HistoryEventType::WifiActivated, id, Some(config.ssid.clone())).await;
+        if !config.is_active {
+            self.emit(NetworkEventKind::WifiActivated { id: config.id.clone(), ssid: config.ssid.clone() });
+            self.notify(ConfigChange::WifiActivated { ssid: config.ssid.clone() });
+        }
+        config.is_active = true;
+        Ok(config)
+    }
+
+    // Disconnect from a config's network and leave no config active. Disconnects even when the
+    // config isn't marked active, since a failed apply may still have half-joined the network.
+    async fn deactivate_wifi_config(&self, id: &str) -> Result<ApplyOutcome, NetworkError> {
+        let _guard = self.wifi_lock.lock().await;
+        let mut config = self.wifi_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("WiFi config {} not found", id)))?;
+        let outcome = self.wifi_applier.disconnect(&config).await?;
+        if config.is_active {
+            config.is_active = false;
+            self.wifi_repository.save(&config).await?;
+            self.emit(NetworkEventKind::WifiDeactivated { id: config.id.clone(), ssid: config.ssid.clone() });
+        }
+        Ok(outcome)
+    }
+
+    fn validate_static_ip_config(&self, new_config: &NewStaticIpConfig) -> Vec<String> {
+        new_config.validate()
+    }
+
+    async fn create_static_ip_config(&self, new_config: NewStaticIpConfig) -> Result<StaticIpConfig, NetworkError> {
+        let errors = self.validate_static_ip_config(&new_config);
+        if !errors.is_empty() {
+            return Err(NetworkError::Validation(errors.join("; ")));
+        }
+
+        let subnet_mask = new_config.resolve_subnet_mask()?;
+        let mut config = StaticIpConfig::new(
+            new_config.interface_name,
+            &new_config.ip_address,
+            &subnet_mask,
+            &new_config.gateway,
+            &new_config.dns_primary,
+            new_config.dns_secondary.as_deref(),
+            new_config.search_domains,
+        )?
+        .with_created_at(self.clock.now());
+        let _guard = self.lock_interface(&config.interface_name).await;
+        // Cheap check before evicting anything; the insert below settles races
+        if let Some(id) = new_config.id {
+            if self.static_ip_repository.find_by_id(&id).await?.is_some() {
+                return Err(NetworkError::Conflict(format!("Static IP config {} already exists", id)));
+            }
+            config = config.with_id(id);
+        }
+
+        let existing = self.static_ip_repository.find_all().await?;
+        let evicted = configs_to_evict(
+            "static IP",
+            self.config_limits.max_static_ip_configs,
+            self.config_limits.policy,
+            existing.iter().map(|config| (config.id.as_str(), config.created_at, config.is_enabled)),
+        )?;
+        for id in evicted {
+            self.static_ip_repository.delete(&id).await?;
+        }
+        if !self.static_ip_repository.insert_if_absent(&config).await? {
+            return Err(NetworkError::Conflict(format!("Static IP config {} already exists", config.id)));
+        }
+        self.record_event(
+            HistoryEventType::StaticIpCreated,
+            &config.id,
+            Some(format!("{} {}", config.interface_name, config.ip_address)),
+        )
+        .await;
+        self.notify(ConfigChange::StaticIpCreated {
+            interface_name: config.interface_name.clone(),
+            address: config.ip_address,
+        });
         Ok(config)
     }
 
@@ -101,49 +416,1204 @@ impl NetworkConfigService for NetworkConfigServiceImpl {
     }
 
 
+    async fn reorder_static_ip_dns(&self, id: &str, dns_servers: Vec<String>) -> Result<StaticIpConfig, NetworkError> {
+        let interface_name = self.static_ip_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("Static IP config {} not found", id)))?
+            .interface_name;
+        let _guard = self.lock_interface(&interface_name).await;
+        // Read again under the lock so a concurrent enable or disable isn't overwritten
+        let mut config = self.static_ip_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("Static IP config {} not found", id)))?;
+        config.reorder_dns_servers(dns_servers)?;
+        self.static_ip_repository.save(&config).await?;
 
-    async fn enable_static_ip(&self, id: &str) -> Result<(), String> {
-        self.static_ip_repository.enable(id).await
+        // Resolver order only takes effect once the file is rewritten
+        if config.is_enabled {
+            let result = self.static_ip_applier.apply(&config).await;
+            if config.record_apply_error(result.as_ref().err(), self.clock.now()) {
+                self.static_ip_repository.save(&config).await?;
+            }
+            result?;
+        }
+        Ok(config)
     }
 
-    async fn disable_static_ip(&self, id: &str) -> Result<(), String> {
-        self.static_ip_repository.disable(id).await
+    // The applier writes the whole config, but with the addressing unchanged only the
+    // resolvers differ from what is live
+    async fn replace_static_ip_dns(&self, id: &str, dns_servers: Vec<String>) -> Result<StaticIpConfig, NetworkError> {
+        let interface_name = self.static_ip_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("Static IP config {} not found", id)))?
+            .interface_name;
+        let _guard = self.lock_interface(&interface_name).await;
+        // Read again under the lock so a concurrent enable or disable isn't overwritten
+        let mut config = self.static_ip_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("Static IP config {} not found", id)))?;
+        config.replace_dns_servers(dns_servers)?;
+        config.updated_at = Some(self.clock.now());
+        self.static_ip_repository.save(&config).await?;
+
+        if config.is_enabled {
+            let result = self.static_ip_applier.apply(&config).await;
+            if config.record_apply_error(result.as_ref().err(), self.clock.now()) {
+                self.static_ip_repository.save(&config).await?;
+            }
+            result?;
+        }
+        Ok(config)
+    }
+
+    // Only one config may be enabled per interface; `takeover` disables an enabled sibling instead of refusing
+    // `force` enables the config even when its subnet overlaps an enabled config on another interface
+    async fn enable_static_ip(&self, id: &str, takeover: bool, force: bool) -> Result<ApplyOutcome, NetworkError> {
+        let interface_name = self.static_ip_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("Static IP config {} not found", id)))?
+            .interface_name;
+        let _guard = self.lock_interface(&interface_name).await;
+        // Read again under the lock: a concurrent enable may have changed the siblings meanwhile
+        let configs = self.static_ip_repository.find_all().await?;
+        let target = configs.iter().find(|config| config.id == id)
+            .ok_or_else(|| NetworkError::NotFound(format!("Static IP config {} not found", id)))?;
+        let siblings: Vec<&StaticIpConfig> = configs
+            .iter()
+            .filter(|config| config.is_enabled && config.id != id && config.interface_name == target.interface_name)
+            .collect();
+        if !siblings.is_empty() && !takeover {
+            let sibling_ids: Vec<&str> = siblings.iter().map(|config| config.id.as_str()).collect();
+            return Err(NetworkError::Conflict(format!(
+                "Static IP config {} is already enabled on {}; pass takeover=true to replace it with {}",
+                sibling_ids.join(", "),
+                target.interface_name,
+                id
+            )));
+        }
+        let overlapping = target.overlapping_enabled(&configs);
+        if !overlapping.is_empty() && !force {
+            let overlaps: Vec<String> = overlapping.iter().map(|config| config.describe_overlap()).collect();
+            return Err(NetworkError::Conflict(format!(
+                "Subnet {} of static IP config {} overlaps {}; pass force=true to enable it anyway",
+                target.subnet(),
+                id,
+                overlaps.join(", ")
+            )));
+        }
+        // No applier removal needed: applying the new config rewrites the interface's file
+        for sibling in siblings {
+            self.static_ip_repository.disable(&sibling.id, self.clock.now()).await?;
+        }
+
+        self.static_ip_repository.enable(id, self.clock.now()).await?;
+        let config = self.static_ip_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("Static IP config {} not found", id)))?;
+        self.record_event(
+            HistoryEventType::StaticIpEnabled,
+            id,
+            Some(format!("{} {}", config.interface_name, config.ip_address)),
+        )
+        .await;
+        self.notify(ConfigChange::StaticIpEnabled {
+            interface_name: config.interface_name.clone(),
+            address: config.ip_address,
+        });
+        let result = self.static_ip_applier.apply(&config).await;
+        self.record_static_ip_apply(config, &result).await?;
+        Ok(result?)
+    }
+
+    async fn disable_static_ip(&self, id: &str) -> Result<ApplyOutcome, String> {
+        let interface_name = self.static_ip_repository.find_by_id(id).await?
+            .ok_or_else(|| "Static IP config not found".to_string())?
+            .interface_name;
+        let _guard = self.lock_interface(&interface_name).await;
+        self.static_ip_repository.disable(id, self.clock.now()).await?;
+        let config = self.static_ip_repository.find_by_id(id).await?
+            .ok_or_else(|| "Static IP config not found".to_string())?;
+        self.record_event(
+            HistoryEventType::StaticIpDisabled,
+            id,
+            Some(format!("{} {}", config.interface_name, config.ip_address)),
+        )
+        .await;
+        self.notify(ConfigChange::StaticIpDisabled {
+            interface_name: config.interface_name.clone(),
+            address: config.ip_address,
+        });
+        let result = self.static_ip_applier.remove(&config).await;
+        self.record_static_ip_apply(config, &result).await?;
+        result
     }
 
     async fn delete_static_ip_config(&self, id: &str) -> Result<(), String> {
-        self.static_ip_repository.delete(id).await
+        let existing = self.static_ip_repository.find_by_id(id).await?;
+        let _guard = match &existing {
+            Some(config) => Some(self.lock_interface(&config.interface_name).await),
+            None => None,
+        };
+        self.static_ip_repository.delete(id).await?;
+        if let Some(config) = existing {
+            self.record_event(
+                HistoryEventType::StaticIpDeleted,
+                id,
+                Some(format!("{} {}", config.interface_name, config.ip_address)),
+            )
+            .await;
+            self.notify(ConfigChange::StaticIpDeleted {
+                interface_name: config.interface_name,
+                address: config.ip_address,
+            });
+        }
+        Ok(())
+    }
+
+    // Upsert already validated configs. Nothing is written unless the whole batch fits under the
+    // config limits. Imports never change what is live: a replaced config keeps its active/enabled
+    // flag and a new one starts out inactive/disabled.
+    async fn import_configs(&self, wifi_configs: Vec<WifiConfig>, static_ip_configs: Vec<StaticIpConfig>) -> Result<ImportReport, NetworkError> {
+        let existing_wifi = self.wifi_repository.find_all().await?;
+        let existing_static_ip = self.static_ip_repository.find_all().await?;
+        check_import_limit(
+            "WiFi",
+            self.config_limits.max_wifi_configs,
+            existing_wifi.iter().map(|config| config.id.as_str()),
+            wifi_configs.iter().map(|config| config.id.as_str()),
+        )?;
+        check_import_limit(
+            "static IP",
+            self.config_limits.max_static_ip_configs,
+            existing_static_ip.iter().map(|config| config.id.as_str()),
+            static_ip_configs.iter().map(|config| config.id.as_str()),
+        )?;
+
+        let mut report = ImportReport::default();
+        for mut config in wifi_configs {
+            match existing_wifi.iter().find(|existing| existing.id == config.id) {
+                Some(existing) => {
+                    config.is_active = existing.is_active;
+                    report.updated += 1;
+                }
+                None => {
+                    config.is_active = false;
+                    report.created += 1;
+                }
+            }
+            self.wifi_repository.save(&config).await?;
+            self.record_event(HistoryEventType::WifiCreated, &config.id, Some(format!("imported {}", config.ssid))).await;
+        }
+        for mut config in static_ip_configs {
+            match existing_static_ip.iter().find(|existing| existing.id == config.id) {
+                Some(existing) => {
+                    config.is_enabled = existing.is_enabled;
+                    config.enabled_at = existing.enabled_at;
+                    config.disabled_at = existing.disabled_at;
+                    report.updated += 1;
+                }
+                None => {
+                    config.is_enabled = false;
+                    report.created += 1;
+                }
+            }
+            self.static_ip_repository.save(&config).await?;
+            self.record_event(
+                HistoryEventType::StaticIpCreated,
+                &config.id,
+                Some(format!("imported {} on {}", config.ip_address, config.interface_name)),
+            )
+            .await;
+        }
+        Ok(report)
     }
 
     async fn get_network_interfaces(&self) -> Result<Vec<NetworkInterface>, String> {
         self.interface_repository.get_interfaces().await
     }
 
-    async fn scan_wifi_networks(&self) -> Result<Vec<ScannedWifiNetwork>, String> {
-        // Simplified approach without panic handling for now
-        match wifiscanner::scan() {
-            Ok(networks) => {
-                let scanned_networks: Vec<ScannedWifiNetwork> = networks
-                    .into_iter()
-                    .filter_map(|network| {
-                        // Filter out networks with invalid data that might cause issues
-                        if network.ssid.is_empty() {
-                            None
-                        } else {
-                            Some(ScannedWifiNetwork {
-                                ssid: network.ssid,
-                                mac: if network.mac.is_empty() { "Unknown".to_string() } else { network.mac },
-                                signal_level: network.signal_level,
-                                channel: if network.channel.is_empty() { "Unknown".to_string() } else { network.channel },
-                                security: network.security,
-                            })
-                        }
-                    })
-                    .collect();
-                Ok(scanned_networks)
+    async fn get_network_interface(&self, interface_name: &str) -> Result<NetworkInterface, NetworkError> {
+        validate_interface_name(interface_name)?;
+        self.interface_repository
+            .get_interface_by_name(interface_name)
+            .await?
+            .ok_or_else(|| NetworkError::NotFound(format!("Interface {} not found", interface_name)))
+    }
+
+    async fn set_interface_admin_state(&self, interface_name: String, up: bool) -> Result<(InterfaceAdminState, ApplyOutcome), NetworkError> {
+        validate_interface_name(&interface_name)?;
+        let _guard = self.lock_interface(&interface_name).await;
+        // Persist the desired state first so it is re-applied on boot even if applying it now fails
+        let state = InterfaceAdminState {
+            updated_at: self.clock.now(),
+            ..InterfaceAdminState::new(interface_name, up)
+        };
+        self.admin_state_repository.save(&state).await?;
+        let outcome = self.interface_repository.set_link_state(&state.interface_name, up).await?;
+        Ok((state, outcome))
+    }
+
+    async fn reconcile_interface_admin_states(&self) -> Result<Vec<String>, String> {
+        let mut failures = Vec::new();
+        for state in self.admin_state_repository.find_all().await? {
+            // The state file may have been edited by hand
+            if !is_valid_interface_name(&state.interface_name) {
+                failures.push(format!("Invalid interface name: {:?}", state.interface_name));
+                continue;
+            }
+            if let Err(e) = self.interface_repository.set_link_state(&state.interface_name, state.desired_up).await {
+                failures.push(e);
+            }
+        }
+        Ok(failures)
+    }
+
+    // Push the active WiFi config and every enabled static IP config onto the system again,
+    // e.g. after a reboot. A failure is recorded on its config and does not stop the rest.
+    async fn reconcile_configs(&self) -> Result<ReconcileReport, String> {
+        let mut report = ReconcileReport::default();
+
+        // A manual-only network is not rejoined behind the user's back
+        let wifi_guard = self.wifi_lock.lock().await;
+        if let Some(config) = self.wifi_repository.find_active().await?
+            && config.auto_connect
+        {
+            let action = format!("WiFi config {} ({})", config.id, config.ssid);
+            let result = self.wifi_applier.apply(&config).await;
+            self.record_wifi_apply(config, &result).await?;
+            report.record(action, result);
+        }
+        drop(wifi_guard);
+        for config in self.static_ip_repository.find_all().await? {
+            if !config.is_enabled {
+                continue;
+            }
+            let _guard = self.lock_interface(&config.interface_name).await;
+            // Read again under the lock: it may have been disabled or deleted meanwhile
+            let Some(config) = self.static_ip_repository.find_by_id(&config.id).await? else {
+                continue;
+            };
+            if !config.is_enabled {
+                continue;
             }
-            Err(e) => Err(format!("WiFi scan failed: {:?}", e))
+            let action = format!("static IP config {} ({} on {})", config.id, config.ip_address, config.interface_name);
+            let result = self.static_ip_applier.apply(&config).await;
+            self.record_static_ip_apply(config, &result).await?;
+            report.record(action, result);
+        }
+        Ok(report)
+    }
+
+    // Counters for every interface in one pass; interfaces without readable counters are left out
+    async fn get_all_interface_stats(&self) -> Result<std::collections::BTreeMap<String, InterfaceStats>, String> {
+        let mut stats = std::collections::BTreeMap::new();
+        for interface in self.interface_repository.get_interfaces().await? {
+            match self.stats_repository.get_stats(&interface.name).await {
+                Ok(interface_stats) => {
+                    stats.insert(interface.name, interface_stats);
+                }
+                Err(e) => tracing::debug!("Skipping stats for {}: {}", interface.name, e),
+            }
+        }
+        Ok(stats)
+    }
+
+    async fn get_interface_rate(&self, interface_name: &str, interval: std::time::Duration) -> Result<InterfaceRate, NetworkError> {
+        validate_interface_name(interface_name)?;
+        let first = self.stats_repository.get_stats(interface_name).await?;
+        let started = tokio::time::Instant::now();
+        tokio::time::sleep(interval).await;
+        let second = self.stats_repository.get_stats(interface_name).await?;
+        Ok(second.rate_since(interface_name.to_string(), &first, started.elapsed()))
+    }
+
+    async fn get_wifi_link_status(&self, interface_name: &str) -> Result<WifiLinkStatus, NetworkError> {
+        validate_interface_name(interface_name)?;
+        Ok(self.interface_repository.get_wifi_link_status(interface_name).await?)
+    }
+
+    async fn get_default_route(&self) -> Result<DefaultRoute, String> {
+        self.interface_repository.get_default_route().await
+    }
+
+    async fn scan_wifi_networks(&self) -> Result<Vec<ScannedWifiNetwork>, NetworkError> {
+        let networks = tokio::time::timeout(self.scan_timeout, self.wifi_scanner.scan())
+            .await
+            .map_err(|_| NetworkError::Timeout(format!(
+                "WiFi scan did not finish within {}s",
+                self.scan_timeout.as_secs_f64()
+            )))??;
+
+        // Filter out networks with invalid data that might cause issues
+        Ok(networks
+            .into_iter()
+            .filter(|network| !network.ssid.is_empty())
+            .map(|mut network| {
+                network.signal_level = normalize_signal_level(&network.signal_raw);
+                (network.channel_number, network.band) = parse_wifi_channel(&network.channel);
+                network
+            })
+            .collect())
+    }
+}
+
+// How many imported configs were new and how many replaced a stored config with the same id
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub created: usize,
+    pub updated: usize,
+}
+
+// What a startup reconcile did: one line per config re-applied, and per config that failed
+#[derive(Debug, Default)]
+pub struct ReconcileReport {
+    pub applied: Vec<String>,
+    pub failures: Vec<String>,
+}
+
+impl ReconcileReport {
+    fn record(&mut self, action: String, result: Result<ApplyOutcome, String>) {
+        match result {
+            Ok(outcome) => self.applied.push(match outcome.detail {
+                Some(detail) => format!("{}: {}", action, detail),
+                None => action,
+            }),
+            Err(e) => self.failures.push(format!("{}: {}", action, e)),
         }
     }
+}
+
+// Ids of the configs to delete so one more fits under `max` (0 is unlimited), oldest first.
+// `configs` yields (id, created_at, in use); configs in use are never evicted.
+fn configs_to_evict<'a>(
+    kind: &str,
+    max: usize,
+    policy: ConfigLimitPolicy,
+    configs: impl Iterator<Item = (&'a str, chrono::DateTime<chrono::Utc>, bool)>,
+) -> Result<Vec<String>, NetworkError> {
+    let mut configs: Vec<_> = configs.collect();
+    if max == 0 || configs.len() < max {
+        return Ok(Vec::new());
+    }
+    if policy == ConfigLimitPolicy::Reject {
+        return Err(NetworkError::Conflict(format!(
+            "{} config limit of {} reached; delete a config first",
+            kind, max
+        )));
+    }
 
+    let excess = configs.len() + 1 - max;
+    configs.retain(|(_, _, in_use)| !in_use);
+    if configs.len() < excess {
+        return Err(NetworkError::Conflict(format!(
+            "{} config limit of {} reached and not enough configs are unused to evict",
+            kind, max
+        )));
+    }
+    configs.sort_by_key(|(_, created_at, _)| *created_at);
+    Ok(configs.into_iter().take(excess).map(|(id, _, _)| id.to_string()).collect())
+}
 
-}
\ No newline at end of file
+// Imports are all-or-nothing, so unlike a create they never evict to make room
+fn check_import_limit<'a>(
+    kind: &str,
+    max: usize,
+    existing: impl Iterator<Item = &'a str>,
+    imported: impl Iterator<Item = &'a str>,
+) -> Result<(), NetworkError> {
+    let ids: std::collections::HashSet<&str> = existing.chain(imported).collect();
+    if max != 0 && ids.len() > max {
+        return Err(NetworkError::Conflict(format!(
+            "Importing would leave {} {} configs, over the limit of {}",
+            ids.len(),
+            kind,
+            max
+        )));
+    }
+    Ok(())
+}
+
+// Result of switching to a profile; `static_ip` is `None` for DHCP profiles
+#[derive(Debug, Clone)]
+pub struct ProfileActivation {
+    pub profile: NetworkProfile,
+    pub wifi: ApplyOutcome,
+    pub static_ip: Option<ApplyOutcome>,
+}
+
+#[async_trait]
+pub trait NetworkProfileService: Send + Sync {
+    async fn create_profile(&self, name: String, wifi_config_id: String, static_ip_config_id: Option<String>) -> Result<NetworkProfile, NetworkError>;
+    async fn get_profiles(&self) -> Result<Vec<NetworkProfile>, String>;
+    async fn activate_profile(&self, id: &str) -> Result<ProfileActivation, NetworkError>;
+}
+
+pub struct NetworkProfileServiceImpl {
+    profile_repository: Arc<dyn NetworkProfileRepository>,
+    network_service: Arc<dyn NetworkConfigService>,
+    clock: Arc<dyn Clock>,
+}
+
+impl NetworkProfileServiceImpl {
+    pub fn new(profile_repository: Arc<dyn NetworkProfileRepository>, network_service: Arc<dyn NetworkConfigService>) -> Self {
+        Self {
+            profile_repository,
+            network_service,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    // Put back the WiFi and static IP state from before a failed activation; returns what could not be restored
+    async fn roll_back(
+        &self,
+        profile_wifi_id: &str,
+        previous_wifi_id: Option<&str>,
+        static_ip_config_id: Option<&str>,
+        previous_static_ids: &[String],
+    ) -> Vec<String> {
+        let mut failures = Vec::new();
+        if let Some(id) = static_ip_config_id {
+            if let Err(e) = self.network_service.disable_static_ip(id).await {
+                failures.push(e);
+            }
+            for previous_id in previous_static_ids {
+                if let Err(e) = self.network_service.enable_static_ip(previous_id, true, true).await {
+                    failures.push(e.to_string());
+                }
+            }
+        }
+        match previous_wifi_id {
+            Some(id) => {
+                if let Err(e) = self.network_service.activate_wifi_config(id).await {
+                    failures.push(e.to_string());
+                }
+            }
+            // Nothing to go back to, so leave the profile's network rather than stay on it
+            None => {
+                if let Err(e) = self.network_service.deactivate_wifi_config(profile_wifi_id).await {
+                    failures.push(e.to_string());
+                }
+            }
+        }
+        failures
+    }
+}
+
+#[async_trait]
+impl NetworkProfileService for NetworkProfileServiceImpl {
+    async fn create_profile(&self, name: String, wifi_config_id: String, static_ip_config_id: Option<String>) -> Result<NetworkProfile, NetworkError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(NetworkError::Validation("name is required".to_string()));
+        }
+        let wifi_configs = self.network_service.get_wifi_configs().await?;
+        if !wifi_configs.iter().any(|config| config.id == wifi_config_id) {
+            return Err(NetworkError::Validation(format!("WiFi config {} does not exist", wifi_config_id)));
+        }
+        if let Some(static_id) = &static_ip_config_id {
+            let static_configs = self.network_service.get_static_ip_configs().await?;
+            if !static_configs.iter().any(|config| &config.id == static_id) {
+                return Err(NetworkError::Validation(format!("Static IP config {} does not exist", static_id)));
+            }
+        }
+
+        let profile = NetworkProfile::new(name, wifi_config_id, static_ip_config_id).with_created_at(self.clock.now());
+        self.profile_repository.save(&profile).await?;
+        Ok(profile)
+    }
+
+    async fn get_profiles(&self) -> Result<Vec<NetworkProfile>, String> {
+        self.profile_repository.find_all().await
+    }
+
+    // All or nothing: if either step fails, the previous WiFi and static IP state is restored
+    async fn activate_profile(&self, id: &str) -> Result<ProfileActivation, NetworkError> {
+        let profile = self.profile_repository.find_by_id(id).await?
+            .ok_or_else(|| NetworkError::NotFound(format!("Network profile {} not found", id)))?;
+
+        let previous_wifi_id = self.network_service.get_active_wifi_config().await?.map(|config| config.id);
+        let static_configs = self.network_service.get_static_ip_configs().await?;
+        let target_static = match &profile.static_ip_config_id {
+            Some(static_id) => Some(
+                static_configs.iter().find(|config| &config.id == static_id).cloned()
+                    .ok_or_else(|| NetworkError::Validation(format!("Static IP config {} no longer exists", static_id)))?,
+            ),
+            None => None,
+        };
+        // Only a config this activation turns on needs undoing
+        let newly_enabled = target_static.as_ref().filter(|config| !config.is_enabled);
+        let previous_static_ids: Vec<String> = newly_enabled
+            .map(|target| {
+                static_configs.iter()
+                    .filter(|config| config.is_enabled && config.interface_name == target.interface_name)
+                    .map(|config| config.id.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let wifi = match self.network_service.activate_wifi_config(&profile.wifi_config_id).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                let e = e.to_string();
+                let failures = self.roll_back(&profile.wifi_config_id, previous_wifi_id.as_deref(), None, &[]).await;
+                return Err(activation_error(&profile, &e, &failures));
+            }
+        };
+
+        let static_ip = match &target_static {
+            // Switching sites replaces whatever static config the interface had
+            Some(config) => match self.network_service.enable_static_ip(&config.id, true, true).await {
+                Ok(outcome) => Some(outcome),
+                Err(e) => {
+                    let e = e.to_string();
+                    let failures = self
+                        .roll_back(
+                            &profile.wifi_config_id,
+                            previous_wifi_id.as_deref(),
+                            newly_enabled.map(|config| config.id.as_str()),
+                            &previous_static_ids,
+                        )
+                        .await;
+                    return Err(activation_error(&profile, &e, &failures));
+                }
+            },
+            None => None,
+        };
+
+        Ok(ProfileActivation { profile, wifi, static_ip })
+    }
+}
+
+fn activation_error(profile: &NetworkProfile, error: &str, rollback_failures: &[String]) -> NetworkError {
+    let mut message = format!("Activating profile {} failed: {}", profile.name, error);
+    if !rollback_failures.is_empty() {
+        message.push_str(&format!("; rollback incomplete: {}", rollback_failures.join("; ")));
+    }
+    NetworkError::Internal(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[tokio::test]
+    async fn activating_the_active_config_changes_nothing() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let config = service.create_wifi_config(wifi("Home")).await.unwrap();
+        service.activate_wifi_config(&config.id).await.unwrap();
+        let calls = network.applier.calls();
+        let stored = network.wifi_repository.find_by_id(&config.id).await.unwrap().unwrap();
+
+        let outcome = service.activate_wifi_config(&config.id).await.unwrap();
+        assert!(outcome.already_active);
+        assert!(!outcome.applied);
+        assert_eq!(network.applier.calls(), calls);
+        let after = network.wifi_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert_eq!(serde_json::to_value(&after).unwrap(), serde_json::to_value(&stored).unwrap());
+    }
+
+    #[tokio::test]
+    async fn failed_activation_is_not_active_and_can_be_retried() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let config = service.create_wifi_config(wifi("Home")).await.unwrap();
+
+        network.applier.set_failure(Some("wpa_cli reconfigure failed"));
+        assert!(service.activate_wifi_config(&config.id).await.is_err());
+        let stored = network.wifi_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert!(!stored.is_active);
+        assert_eq!(stored.last_error.as_deref(), Some("wpa_cli reconfigure failed"));
+
+        network.applier.set_failure(None);
+        let outcome = service.activate_wifi_config(&config.id).await.unwrap();
+        assert!(!outcome.already_active);
+        assert_eq!(network.applier.calls().len(), 2);
+        let stored = network.wifi_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert!(stored.is_active);
+        assert!(stored.last_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn active_config_with_an_apply_error_is_applied_again() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let config = service.create_wifi_config(wifi("Home")).await.unwrap();
+        service.activate_wifi_config(&config.id).await.unwrap();
+
+        // e.g. a failed re-apply at boot
+        let mut stored = network.wifi_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        stored.last_error = Some("interface missing".to_string());
+        network.wifi_repository.save(&stored).await.unwrap();
+
+        let outcome = service.activate_wifi_config(&config.id).await.unwrap();
+        assert!(!outcome.already_active);
+        assert_eq!(network.applier.calls().len(), 2);
+        let stored = network.wifi_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert!(stored.is_active);
+        assert!(stored.last_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn interface_admin_state_is_stored_and_reapplied_on_reconcile() {
+        let network = TestNetwork::new();
+        let service = network.service();
+
+        let (state, outcome) = service.set_interface_admin_state("eth0".to_string(), false).await.unwrap();
+        assert!(!state.desired_up);
+        assert!(outcome.applied);
+        let stored = network.admin_states.find_all().await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].interface_name, "eth0");
+        assert!(!stored[0].desired_up);
+
+        // Something else brings the link back up; reconcile puts the stored state back
+        network.interfaces.set_link_state("eth0", true).await.unwrap();
+        let failures = service.reconcile_interface_admin_states().await.unwrap();
+        assert!(failures.is_empty());
+        let eth0 = service.get_network_interface("eth0").await.unwrap();
+        assert!(!eth0.is_up);
+    }
+
+    #[tokio::test]
+    async fn interface_admin_state_rejects_bad_names_without_storing() {
+        let network = TestNetwork::new();
+        let service = network.service();
+
+        let result = service.set_interface_admin_state("eth0; reboot".to_string(), false).await;
+        assert!(matches!(result, Err(NetworkError::Validation(_))));
+        assert!(network.admin_states.find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn interface_rate_is_computed_from_two_samples() {
+        let mut network = TestNetwork::new();
+        network.stats = Arc::new(SampledStats::new(vec![(1_000, 500), (5_000, 1_500)]));
+
+        let rate = network
+            .service()
+            .get_interface_rate("eth0", std::time::Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(rate.interface_name, "eth0");
+        assert_eq!(rate.rx_bytes_per_sec, 2_000.0);
+        assert_eq!(rate.tx_bytes_per_sec, 500.0);
+        assert_eq!(rate.interval_ms, 2_000);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn interface_rate_survives_a_32_bit_counter_wrap() {
+        let mut network = TestNetwork::new();
+        let before_wrap = u64::from(u32::MAX) - 99;
+        network.stats = Arc::new(SampledStats::new(vec![(before_wrap, 0), (100, 0)]));
+
+        let rate = network
+            .service()
+            .get_interface_rate("eth0", std::time::Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(rate.rx_bytes_per_sec, 200.0);
+    }
+
+    #[tokio::test]
+    async fn reconcile_reports_unknown_interfaces() {
+        let network = TestNetwork::new();
+        network
+            .admin_states
+            .save(&InterfaceAdminState::new("eth9".to_string(), true))
+            .await
+            .unwrap();
+
+        let failures = network.service().reconcile_interface_admin_states().await.unwrap();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("eth9"));
+    }
+
+    #[tokio::test]
+    async fn supplied_ids_are_used_and_duplicates_conflict() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let new_wifi = NewWifiConfig { id: Some("home-wifi".to_string()), ..wifi("Home") };
+        assert_eq!(service.create_wifi_config(new_wifi.clone()).await.unwrap().id, "home-wifi");
+        assert!(matches!(service.create_wifi_config(new_wifi).await, Err(NetworkError::Conflict(_))));
+
+        let new_static = NewStaticIpConfig { id: Some("eth0-lan".to_string()), ..static_ip("eth0", "192.168.1.60") };
+        assert_eq!(service.create_static_ip_config(new_static.clone()).await.unwrap().id, "eth0-lan");
+        assert!(matches!(service.create_static_ip_config(new_static).await, Err(NetworkError::Conflict(_))));
+
+        assert_eq!(network.wifi_repository.find_all().await.unwrap().len(), 1);
+        assert_eq!(network.static_ip_repository.find_all().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_creates_with_one_id_store_one_config() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let creates: Vec<_> = (0..8)
+            .map(|n| {
+                let service = service.clone();
+                let new_config = NewWifiConfig { id: Some("home-wifi".to_string()), ..wifi(&format!("Home{}", n)) };
+                tokio::spawn(async move { service.create_wifi_config(new_config).await })
+            })
+            .collect();
+
+        let mut created = 0;
+        for create in creates {
+            match create.await.unwrap() {
+                Ok(_) => created += 1,
+                Err(e) => assert!(matches!(e, NetworkError::Conflict(_)), "{:?}", e),
+            }
+        }
+        assert_eq!(created, 1);
+        assert_eq!(network.wifi_repository.find_all().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn dns_reorder_must_be_a_permutation() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let new_config = NewStaticIpConfig { dns_secondary: Some("9.9.9.9".to_string()), ..static_ip("eth0", "192.168.1.60") };
+        let config = service.create_static_ip_config(new_config).await.unwrap();
+
+        for order in [vec!["1.1.1.1", "8.8.8.8"], vec!["1.1.1.1"], vec!["1.1.1.1", "1.1.1.1"]] {
+            let order = order.into_iter().map(str::to_string).collect();
+            let error = service.reorder_static_ip_dns(&config.id, order).await.unwrap_err();
+            assert!(matches!(error, NetworkError::Validation(_)), "{:?}", error);
+        }
+        let stored = network.static_ip_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert_eq!(stored.dns_servers(), config.dns_servers());
+
+        let reordered = service
+            .reorder_static_ip_dns(&config.id, vec!["9.9.9.9".to_string(), "1.1.1.1".to_string()])
+            .await
+            .unwrap();
+        let stored = network.static_ip_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert_eq!(stored.dns_servers(), reordered.dns_servers());
+        assert_eq!(stored.dns_servers().iter().map(|ip| ip.to_string()).collect::<Vec<_>>(), vec!["9.9.9.9", "1.1.1.1"]);
+    }
+
+    #[tokio::test]
+    async fn deleting_the_active_wifi_config_needs_force() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let config = service.create_wifi_config(wifi("Home")).await.unwrap();
+        service.activate_wifi_config(&config.id).await.unwrap();
+
+        let error = service.delete_wifi_config(&config.id, false).await.unwrap_err();
+        assert!(matches!(error, NetworkError::Conflict(_)), "{:?}", error);
+        assert!(network.wifi_repository.find_by_id(&config.id).await.unwrap().is_some());
+        assert_eq!(network.applier.calls(), vec![format!("apply wifi {}", config.id)]);
+
+        service.delete_wifi_config(&config.id, true).await.unwrap();
+        assert!(network.wifi_repository.find_by_id(&config.id).await.unwrap().is_none());
+        assert_eq!(network.applier.calls().last(), Some(&format!("disconnect wifi {}", config.id)));
+    }
+
+    #[tokio::test]
+    async fn inactive_wifi_configs_are_deleted_without_disconnecting() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let config = service.create_wifi_config(wifi("Home")).await.unwrap();
+        service.delete_wifi_config(&config.id, false).await.unwrap();
+        assert!(network.wifi_repository.find_all().await.unwrap().is_empty());
+        assert!(network.applier.calls().is_empty());
+    }
+
+    fn profile_service(network: &TestNetwork) -> (Arc<NetworkConfigServiceImpl>, NetworkProfileServiceImpl) {
+        let service = Arc::new(network.service());
+        let profiles = NetworkProfileServiceImpl::new(
+            Arc::new(crate::infrastructure::network_repositories::InMemoryNetworkProfileRepository::new()),
+            service.clone(),
+        );
+        (service, profiles)
+    }
+
+    #[tokio::test]
+    async fn profiles_must_name_existing_configs() {
+        let network = TestNetwork::new();
+        let (service, profiles) = profile_service(&network);
+        let home = service.create_wifi_config(wifi("Home")).await.unwrap();
+        let lan = service.create_static_ip_config(static_ip("wlan0", "192.168.1.60")).await.unwrap();
+
+        for (name, wifi_id, static_id) in [
+            ("  ", home.id.clone(), None),
+            ("Home", "missing".to_string(), None),
+            ("Home", home.id.clone(), Some("missing".to_string())),
+        ] {
+            let error = profiles.create_profile(name.to_string(), wifi_id, static_id).await.unwrap_err();
+            assert!(matches!(error, NetworkError::Validation(_)), "{:?}", error);
+        }
+        let profile = profiles.create_profile(" Home ".to_string(), home.id, Some(lan.id)).await.unwrap();
+        assert_eq!(profile.name, "Home");
+        assert_eq!(profiles.get_profiles().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn profile_activation_sets_wifi_and_static_ip() {
+        let network = TestNetwork::new();
+        let (service, profiles) = profile_service(&network);
+        let home = service.create_wifi_config(wifi("Home")).await.unwrap();
+        let lan = service.create_static_ip_config(static_ip("wlan0", "192.168.1.60")).await.unwrap();
+        let profile = profiles.create_profile("Home".to_string(), home.id.clone(), Some(lan.id.clone())).await.unwrap();
+
+        let activation = profiles.activate_profile(&profile.id).await.unwrap();
+        assert!(activation.wifi.applied);
+        assert!(activation.static_ip.is_some());
+        assert_eq!(service.get_active_wifi_config().await.unwrap().map(|config| config.id), Some(home.id));
+        assert!(network.static_ip_repository.find_by_id(&lan.id).await.unwrap().unwrap().is_enabled);
+    }
+
+    #[tokio::test]
+    async fn failed_profile_activation_leaves_the_profile_network_when_none_was_active() {
+        let network = TestNetwork::new();
+        let (service, profiles) = profile_service(&network);
+        let home = service.create_wifi_config(wifi("Home")).await.unwrap();
+        let lan = service.create_static_ip_config(static_ip("wlan0", "192.168.1.60")).await.unwrap();
+        let profile = profiles.create_profile("Home".to_string(), home.id.clone(), Some(lan.id.clone())).await.unwrap();
+        network.applier.set_failure_for("apply static", Some("networkctl failed"));
+
+        let error = profiles.activate_profile(&profile.id).await.unwrap_err();
+        assert!(error.to_string().contains("networkctl failed"), "{}", error);
+        assert!(!error.to_string().contains("rollback incomplete"), "{}", error);
+        assert!(service.get_active_wifi_config().await.unwrap().is_none());
+        assert!(!network.static_ip_repository.find_by_id(&lan.id).await.unwrap().unwrap().is_enabled);
+        assert_eq!(network.applier.calls().last(), Some(&format!("disconnect wifi {}", home.id)));
+    }
+
+    #[tokio::test]
+    async fn failed_profile_activation_restores_the_previous_network() {
+        let network = TestNetwork::new();
+        let (service, profiles) = profile_service(&network);
+        let previous = service.create_wifi_config(wifi("Previous")).await.unwrap();
+        service.activate_wifi_config(&previous.id).await.unwrap();
+        let home = service.create_wifi_config(wifi("Home")).await.unwrap();
+        let lan = service.create_static_ip_config(static_ip("wlan0", "192.168.1.60")).await.unwrap();
+        let profile = profiles.create_profile("Home".to_string(), home.id, Some(lan.id)).await.unwrap();
+        network.applier.set_failure_for("apply static", Some("networkctl failed"));
+
+        assert!(profiles.activate_profile(&profile.id).await.is_err());
+        assert_eq!(service.get_active_wifi_config().await.unwrap().map(|config| config.id), Some(previous.id));
+    }
+
+    #[tokio::test]
+    async fn failed_apply_is_recorded_on_the_config_until_an_apply_succeeds() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let config = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+
+        network.applier.set_failure(Some("networkctl failed"));
+        assert!(service.enable_static_ip(&config.id, false, false).await.is_err());
+        let failed = network.static_ip_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert_eq!(failed.last_error.as_deref(), Some("networkctl failed"));
+        assert!(failed.last_error_at.is_some());
+
+        network.applier.set_failure(None);
+        service.enable_static_ip(&config.id, false, false).await.unwrap();
+        let recovered = network.static_ip_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert!(recovered.last_error.is_none());
+        assert!(recovered.last_error_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn enabling_next_to_an_enabled_sibling_needs_takeover() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let first = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        let second = service.create_static_ip_config(static_ip("eth0", "192.168.1.61")).await.unwrap();
+        service.enable_static_ip(&first.id, false, false).await.unwrap();
+
+        match service.enable_static_ip(&second.id, false, false).await {
+            Err(NetworkError::Conflict(message)) => {
+                assert!(message.contains(&first.id) && message.contains(&second.id), "{}", message);
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+        assert!(network.static_ip_repository.find_by_id(&first.id).await.unwrap().unwrap().is_enabled);
+        assert!(!network.static_ip_repository.find_by_id(&second.id).await.unwrap().unwrap().is_enabled);
+
+        service.enable_static_ip(&second.id, true, false).await.unwrap();
+        assert!(!network.static_ip_repository.find_by_id(&first.id).await.unwrap().unwrap().is_enabled);
+        assert!(network.static_ip_repository.find_by_id(&second.id).await.unwrap().unwrap().is_enabled);
+    }
+
+    #[test]
+    fn config_limit_rejects_or_evicts_the_oldest_unused() {
+        let at = |minute: i64| chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000 + minute * 60, 0).unwrap();
+        let configs = [("active", at(0), true), ("old", at(1), false), ("newer", at(2), false)];
+
+        assert!(configs_to_evict("WiFi", 4, ConfigLimitPolicy::Reject, configs.iter().copied()).unwrap().is_empty());
+        assert!(configs_to_evict("WiFi", 0, ConfigLimitPolicy::Reject, configs.iter().copied()).unwrap().is_empty());
+        match configs_to_evict("WiFi", 3, ConfigLimitPolicy::Reject, configs.iter().copied()) {
+            Err(NetworkError::Conflict(message)) => assert!(message.contains("limit of 3"), "{}", message),
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+
+        let evicted = configs_to_evict("WiFi", 3, ConfigLimitPolicy::EvictOldest, configs.iter().copied()).unwrap();
+        assert_eq!(evicted, vec!["old".to_string()]);
+        let evicted = configs_to_evict("WiFi", 2, ConfigLimitPolicy::EvictOldest, configs.iter().copied()).unwrap();
+        assert_eq!(evicted, vec!["old".to_string(), "newer".to_string()]);
+        assert!(matches!(
+            configs_to_evict("WiFi", 1, ConfigLimitPolicy::EvictOldest, configs.iter().copied()),
+            Err(NetworkError::Conflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reconcile_applies_each_enabled_config_once() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let eth0 = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        let eth1 = service.create_static_ip_config(static_ip("eth1", "192.168.2.60")).await.unwrap();
+        service.create_static_ip_config(static_ip("eth2", "192.168.3.60")).await.unwrap();
+        service.enable_static_ip(&eth0.id, false, false).await.unwrap();
+        service.enable_static_ip(&eth1.id, false, false).await.unwrap();
+        let before = network.applier.calls().len();
+
+        network.applier.set_failure_for("apply static", Some("ip addr add failed"));
+        let report = service.reconcile_configs().await.unwrap();
+        let mut calls = network.applier.calls()[before..].to_vec();
+        let mut expected = vec![format!("apply static {}", eth0.id), format!("apply static {}", eth1.id)];
+        calls.sort();
+        expected.sort();
+        assert_eq!(calls, expected);
+        assert!(report.applied.is_empty());
+        assert_eq!(report.failures.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn ssids_are_trimmed_unless_exact() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        assert_eq!(service.create_wifi_config(wifi(" MyNet ")).await.unwrap().ssid, "MyNet");
+        let exact = NewWifiConfig { exact_ssid: true, ..wifi(" MyNet ") };
+        assert_eq!(service.create_wifi_config(exact).await.unwrap().ssid, " MyNet ");
+
+        match service.create_wifi_config(wifi("   ")).await {
+            Err(NetworkError::Validation(message)) => assert!(message.contains("only whitespace"), "{}", message),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_enables_on_one_interface_leave_one_enabled() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let first = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        let second = service.create_static_ip_config(static_ip("eth0", "192.168.1.61")).await.unwrap();
+        network.applier.set_delay(std::time::Duration::from_millis(50));
+
+        let enables: Vec<_> = [first.id.clone(), second.id.clone()]
+            .into_iter()
+            .map(|id| {
+                let service = service.clone();
+                tokio::spawn(async move { service.enable_static_ip(&id, false, false).await })
+            })
+            .collect();
+        let mut succeeded = 0;
+        for enable in enables {
+            match enable.await.unwrap() {
+                Ok(_) => succeeded += 1,
+                Err(e) => assert!(matches!(e, NetworkError::Conflict(_)), "{:?}", e),
+            }
+        }
+        assert_eq!(succeeded, 1);
+        let enabled = network.static_ip_repository.find_all().await.unwrap().into_iter().filter(|config| config.is_enabled);
+        assert_eq!(enabled.count(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_activations_leave_one_active_config() {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        let home = service.create_wifi_config(wifi("Home")).await.unwrap();
+        let office = service.create_wifi_config(wifi("Office")).await.unwrap();
+        network.applier.set_delay(std::time::Duration::from_millis(50));
+
+        let activations: Vec<_> = [home.id.clone(), office.id.clone()]
+            .into_iter()
+            .map(|id| {
+                let service = service.clone();
+                tokio::spawn(async move { service.activate_wifi_config(&id).await })
+            })
+            .collect();
+        for activation in activations {
+            activation.await.unwrap().unwrap();
+        }
+        // The one applied last is the one on the air, and it must be the one marked active
+        let last_applied = network.applier.calls().last().unwrap().trim_start_matches("apply wifi ").to_string();
+        let active: Vec<_> = network.wifi_repository.find_all().await.unwrap().into_iter().filter(|config| config.is_active).collect();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, last_applied);
+    }
+
+    #[derive(Default)]
+    struct RecordingEventSink {
+        events: std::sync::Mutex<Vec<NetworkEventKind>>,
+    }
+
+    #[async_trait]
+    impl EventSink for RecordingEventSink {
+        async fn publish(&self, event: &NetworkEvent) -> Result<(), String> {
+            self.events.lock().unwrap().push(event.kind.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingNotifications {
+        changes: std::sync::Mutex<Vec<ConfigChange>>,
+    }
+
+    impl NotificationService for RecordingNotifications {
+        fn notify(&self, change: ConfigChange) {
+            self.changes.lock().unwrap().push(change);
+        }
+    }
+
+    #[tokio::test]
+    async fn wifi_activation_is_only_announced_after_a_successful_apply() {
+        let network = TestNetwork::new();
+        let history = Arc::new(crate::infrastructure::network_repositories::JsonlEventHistoryRepository::new(
+            temp_dir().join("events.jsonl"),
+        ));
+        let events = Arc::new(RecordingEventSink::default());
+        let notifications = Arc::new(RecordingNotifications::default());
+        let service = network
+            .service()
+            .with_event_history(history.clone())
+            .with_event_sink(events.clone())
+            .with_notifications(notifications.clone());
+        let config = service.create_wifi_config(wifi("Home")).await.unwrap();
+        let activations = || async {
+            let filter = EventHistoryFilter {
+                since: None,
+                event_types: Some(vec![HistoryEventType::WifiActivated]),
+                limit: 10,
+            };
+            history.query(&filter).await.unwrap().len()
+        };
+
+        network.applier.set_failure(Some("wpa_supplicant rejected the config"));
+        assert!(service.activate_wifi_config(&config.id).await.is_err());
+        // Events are published from a spawned task; give it the chance to run
+        tokio::task::yield_now().await;
+        assert_eq!(activations().await, 0);
+        assert!(events.events.lock().unwrap().is_empty());
+        assert!(!notifications.changes.lock().unwrap().contains(&ConfigChange::WifiActivated { ssid: "Home".to_string() }));
+
+        network.applier.set_failure(None);
+        service.activate_wifi_config(&config.id).await.unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(activations().await, 1);
+        assert_eq!(
+            *events.events.lock().unwrap(),
+            vec![NetworkEventKind::WifiActivated { id: config.id.clone(), ssid: "Home".to_string() }]
+        );
+        assert!(notifications.changes.lock().unwrap().contains(&ConfigChange::WifiActivated { ssid: "Home".to_string() }));
+    }
+
+    #[tokio::test]
+    async fn enabling_an_overlapping_subnet_on_another_interface_needs_force() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let eth0 = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        let overlapping = service.create_static_ip_config(static_ip("wlan0", "192.168.1.70")).await.unwrap();
+        let separate = service.create_static_ip_config(static_ip("wlan0", "10.0.0.70")).await.unwrap();
+        service.enable_static_ip(&eth0.id, false, false).await.unwrap();
+
+        let refused = service.enable_static_ip(&overlapping.id, false, false).await;
+        assert!(matches!(refused, Err(NetworkError::Conflict(message)) if message.contains("eth0")));
+        assert!(!network.static_ip_repository.find_by_id(&overlapping.id).await.unwrap().unwrap().is_enabled);
+
+        service.enable_static_ip(&separate.id, false, false).await.unwrap();
+        service.enable_static_ip(&overlapping.id, true, true).await.unwrap();
+        assert!(network.static_ip_repository.find_by_id(&overlapping.id).await.unwrap().unwrap().is_enabled);
+    }
+
+    #[tokio::test]
+    async fn enabling_and_disabling_stamp_the_config() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let config = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        assert_eq!((config.enabled_at, config.disabled_at), (None, None));
+        let stored = || async { network.static_ip_repository.find_by_id(&config.id).await.unwrap().unwrap() };
+
+        let before = chrono::Utc::now();
+        service.enable_static_ip(&config.id, false, false).await.unwrap();
+        let enabled = stored().await;
+        let enabled_at = enabled.enabled_at.unwrap();
+        assert!(enabled_at >= before && enabled_at <= chrono::Utc::now());
+        assert_eq!(enabled.disabled_at, None);
+
+        service.disable_static_ip(&config.id).await.unwrap();
+        let disabled = stored().await;
+        assert_eq!(disabled.enabled_at, Some(enabled_at));
+        let disabled_at = disabled.disabled_at.unwrap();
+        assert!(disabled_at >= enabled_at);
+
+        // Each stamp keeps its value when the other one moves
+        service.enable_static_ip(&config.id, false, false).await.unwrap();
+        let reenabled = stored().await;
+        assert!(reenabled.enabled_at.unwrap() >= disabled_at);
+        assert_eq!(reenabled.disabled_at, Some(disabled_at));
+    }
+
+    #[tokio::test]
+    async fn timestamps_come_from_the_injected_clock() {
+        let network = TestNetwork::new();
+        let start = chrono::DateTime::parse_from_rfc3339("2026-01-01T12:00:00Z").unwrap().to_utc();
+        let clock = Arc::new(FixedClock::new(start));
+        let service = network.service().with_clock(clock.clone());
+        let minute = chrono::Duration::minutes(1);
+
+        let first = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        clock.advance(minute);
+        let second = service.create_static_ip_config(static_ip("wlan0", "10.0.0.60")).await.unwrap();
+        assert_eq!((first.created_at, second.created_at), (start, start + minute));
+
+        clock.advance(minute);
+        service.enable_static_ip(&first.id, false, false).await.unwrap();
+        clock.advance(minute);
+        service.disable_static_ip(&first.id).await.unwrap();
+        let first = network.static_ip_repository.find_by_id(&first.id).await.unwrap().unwrap();
+        assert_eq!(first.enabled_at, Some(start + minute * 2));
+        assert_eq!(first.disabled_at, Some(start + minute * 3));
+
+        clock.advance(minute);
+        network.applier.set_failure(Some("networkd rejected the file"));
+        assert!(service.enable_static_ip(&second.id, false, false).await.is_err());
+        let second = network.static_ip_repository.find_by_id(&second.id).await.unwrap().unwrap();
+        assert_eq!(second.enabled_at, Some(start + minute * 4));
+        assert_eq!(second.last_error_at, Some(start + minute * 4));
+    }
+
+    #[tokio::test]
+    async fn replacing_dns_servers_leaves_the_addressing_alone() {
+        let network = TestNetwork::new();
+        let service = network.service();
+        let config = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        service.enable_static_ip(&config.id, false, false).await.unwrap();
+
+        let replaced = service
+            .replace_static_ip_dns(&config.id, vec!["9.9.9.9".to_string(), " 2620:fe::fe ".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(replaced.dns_servers(), vec!["9.9.9.9".parse::<std::net::IpAddr>().unwrap(), "2620:fe::fe".parse().unwrap()]);
+        assert!(replaced.updated_at.is_some());
+        let stored = network.static_ip_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert_eq!(stored.dns_servers(), replaced.dns_servers());
+        assert_eq!(
+            (stored.ip_address, stored.prefix_len, stored.gateway, stored.is_enabled),
+            (config.ip_address, config.prefix_len, config.gateway, true)
+        );
+        // Enabled, so the new resolvers are applied right away
+        assert_eq!(network.applier.calls().iter().filter(|call| **call == format!("apply static {}", config.id)).count(), 2);
+
+        for servers in [vec![], vec!["9.9.9.9".to_string(), "9.9.9.9".to_string()], vec!["not-an-ip".to_string()]] {
+            let refused = service.replace_static_ip_dns(&config.id, servers).await;
+            assert!(matches!(refused, Err(NetworkError::Validation(_))), "{:?}", refused);
+        }
+        let unchanged = network.static_ip_repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert_eq!(unchanged.dns_servers(), replaced.dns_servers());
+    }
+}
@@ -0,0 +1,107 @@
+// Operator notifications - one-line, human-readable messages about configuration changes for chat
+// webhooks (Slack, Discord, ...). Best effort: a failed delivery is logged and never fails the change.
+
+use async_trait::async_trait;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    WifiCreated { ssid: String },
+    WifiActivated { ssid: String },
+    WifiDeleted { ssid: String },
+    StaticIpCreated { interface_name: String, address: Ipv4Addr },
+    StaticIpEnabled { interface_name: String, address: Ipv4Addr },
+    StaticIpDisabled { interface_name: String, address: Ipv4Addr },
+    StaticIpDeleted { interface_name: String, address: Ipv4Addr },
+}
+
+impl ConfigChange {
+    pub fn message(&self) -> String {
+        match self {
+            ConfigChange::WifiCreated { ssid } => format!("WiFi network \"{}\" added", ssid),
+            ConfigChange::WifiActivated { ssid } => format!("WiFi network \"{}\" activated", ssid),
+            ConfigChange::WifiDeleted { ssid } => format!("WiFi network \"{}\" deleted", ssid),
+            ConfigChange::StaticIpCreated { interface_name, address } => {
+                format!("Static IP {} added for {}", address, interface_name)
+            }
+            ConfigChange::StaticIpEnabled { interface_name, address } => {
+                format!("Static IP {} enabled on {}", address, interface_name)
+            }
+            ConfigChange::StaticIpDisabled { interface_name, address } => {
+                format!("Static IP {} disabled on {}", address, interface_name)
+            }
+            ConfigChange::StaticIpDeleted { interface_name, address } => {
+                format!("Static IP {} deleted from {}", address, interface_name)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    async fn send(&self, text: &str) -> Result<(), String>;
+}
+
+pub trait NotificationService: Send + Sync {
+    fn notify(&self, change: ConfigChange);
+}
+
+pub struct NotificationServiceImpl {
+    sink: Arc<dyn NotificationSink>,
+}
+
+impl NotificationServiceImpl {
+    pub fn new(sink: Arc<dyn NotificationSink>) -> Self {
+        Self { sink }
+    }
+}
+
+impl NotificationService for NotificationServiceImpl {
+    // Sent in the background so a slow webhook never holds up the change
+    fn notify(&self, change: ConfigChange) {
+        let sink = self.sink.clone();
+        let text = change.message();
+        tokio::spawn(async move {
+            if let Err(e) = sink.send(&text).await {
+                tracing::warn!("Failed to send notification \"{}\": {}", text, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::network_services::NetworkConfigService;
+    use crate::test_support::{static_ip, TestNetwork};
+
+    #[derive(Default)]
+    struct RecordingSink {
+        sent: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl NotificationSink for RecordingSink {
+        async fn send(&self, text: &str) -> Result<(), String> {
+            self.sent.lock().unwrap().push(text.to_string());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn enabling_a_static_ip_sends_a_formatted_message() {
+        let sink = Arc::new(RecordingSink::default());
+        let network = TestNetwork::new();
+        let service = network.service().with_notifications(Arc::new(NotificationServiceImpl::new(sink.clone())));
+        let config = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        service.enable_static_ip(&config.id, false, false).await.unwrap();
+        // Sent from spawned tasks; give them the chance to run
+        tokio::task::yield_now().await;
+
+        assert_eq!(
+            *sink.sent.lock().unwrap(),
+            vec!["Static IP 192.168.1.60 added for eth0", "Static IP 192.168.1.60 enabled on eth0"]
+        );
+    }
+}
@@ -8,4 +8,8 @@ use crate::domain::entities::Greeting;
 pub trait GreetingRepository: Send + Sync {
     async fn save(&self, greeting: &Greeting) -> Result<(), String>;
     async fn find_all(&self) -> Result<Vec<Greeting>, String>;
+    async fn find_by_id(&self, id: &str) -> Result<Option<Greeting>, String>;
+    async fn find_by_message_language(&self, message: &str, language: &str) -> Result<Option<Greeting>, String>;
+    // Atomically return the stored greeting with the same message and language, or save this one
+    async fn save_if_absent(&self, greeting: &Greeting) -> Result<Greeting, String>;
 }
\ No newline at end of file
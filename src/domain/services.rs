@@ -1,42 +1,179 @@
 // Domain services - contain business logic and use cases
 
 use async_trait::async_trait;
-use crate::domain::entities::Greeting;
+use crate::domain::clock::{Clock, SystemClock};
+use crate::domain::entities::{is_valid_language_tag, Greeting};
+use crate::domain::errors::GreetingError;
 use crate::domain::repositories::GreetingRepository;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[async_trait]
 pub trait GreetingService: Send + Sync {
-    async fn create_greeting(&self, message: String, language: String) -> Result<Greeting, String>;
+    async fn create_greeting(&self, message: String, language: String, dedup: bool) -> Result<Greeting, GreetingError>;
     async fn get_default_greeting(&self) -> Result<Greeting, String>;
-    async fn list_greetings(&self) -> Result<Vec<Greeting>, String>;
+    async fn render(&self, id: &str, vars: &HashMap<String, String>) -> Result<String, GreetingError>;
+    async fn list_greetings(
+        &self,
+        created_after: Option<chrono::DateTime<chrono::Utc>>,
+        created_before: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Greeting>, String>;
 }
 
 pub struct GreetingServiceImpl {
     repository: Arc<dyn GreetingRepository>,
+    clock: Arc<dyn Clock>,
 }
 
 impl GreetingServiceImpl {
     pub fn new(repository: Arc<dyn GreetingRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    // Source of created_at for new greetings
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
     }
 }
 
 #[async_trait]
 impl GreetingService for GreetingServiceImpl {
-    async fn create_greeting(&self, message: String, language: String) -> Result<Greeting, String> {
-        let greeting = Greeting::new(message, language);
-        self.repository.save(&greeting).await?;
-        Ok(greeting)
-    }
+    // With `dedup`, an existing greeting with the same message and language is returned instead
+    async fn create_greeting(&self, message: String, language: String, dedup: bool) -> Result<Greeting, GreetingError> {
+        if !is_valid_language_tag(&language) {
+            return Err(GreetingError::Validation(format!(
+                "language must be a language code like \"en\" or \"en-US\": {:?}",
+                language
+            )));
+        }
 
+        if !dedup {
+            let greeting = Greeting::new(message, language).with_created_at(self.clock.now());
+            self.repository.save(&greeting).await?;
+            return Ok(greeting);
+        }
 
+        // Cheap read-locked check first; the write-locked insert below settles races
+        if let Some(existing) = self.repository.find_by_message_language(&message, &language).await? {
+            return Ok(existing);
+        }
+        let greeting = Greeting::new(message, language).with_created_at(self.clock.now());
+        Ok(self.repository.save_if_absent(&greeting).await?)
+    }
 
     async fn get_default_greeting(&self) -> Result<Greeting, String> {
         Ok(Greeting::default_hello_world())
     }
 
-    async fn list_greetings(&self) -> Result<Vec<Greeting>, String> {
-        self.repository.find_all().await
+    // Use a stored greeting as a template, e.g. "Hello, {name}!" with {"name": "Ada"}
+    async fn render(&self, id: &str, vars: &HashMap<String, String>) -> Result<String, GreetingError> {
+        let greeting = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| GreetingError::NotFound(format!("Greeting {} not found", id)))?;
+        greeting.render(vars).map_err(GreetingError::Validation)
+    }
+
+    // Both bounds are exclusive; a missing bound leaves that side of the window open
+    async fn list_greetings(
+        &self,
+        created_after: Option<chrono::DateTime<chrono::Utc>>,
+        created_before: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Greeting>, String> {
+        let greetings = self.repository.find_all().await?;
+        Ok(greetings
+            .into_iter()
+            .filter(|greeting| created_after.is_none_or(|after| greeting.created_at > after))
+            .filter(|greeting| created_before.is_none_or(|before| greeting.created_at < before))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::InMemoryGreetingRepository;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_identical_creates_with_dedup_store_one_greeting() {
+        let repository = Arc::new(InMemoryGreetingRepository::new());
+        let service = Arc::new(GreetingServiceImpl::new(repository.clone()));
+        let creates: Vec<_> = (0..16)
+            .map(|_| {
+                let service = service.clone();
+                tokio::spawn(async move { service.create_greeting("Hello".to_string(), "en".to_string(), true).await })
+            })
+            .collect();
+
+        let mut ids = Vec::new();
+        for create in creates {
+            ids.push(create.await.unwrap().unwrap().id);
+        }
+        ids.dedup();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(repository.find_all().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn creates_without_dedup_store_copies() {
+        let repository = Arc::new(InMemoryGreetingRepository::new());
+        let service = GreetingServiceImpl::new(repository.clone());
+        service.create_greeting("Hello".to_string(), "en".to_string(), false).await.unwrap();
+        service.create_greeting("Hello".to_string(), "en".to_string(), false).await.unwrap();
+        assert_eq!(repository.find_all().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn language_must_be_a_language_code() {
+        let service = GreetingServiceImpl::new(Arc::new(InMemoryGreetingRepository::new()));
+        for language in ["en", "en-US"] {
+            let greeting = service.create_greeting("Hello".to_string(), language.to_string(), false).await.unwrap();
+            assert_eq!(greeting.language, language);
+        }
+        for language in ["", "english"] {
+            match service.create_greeting("Hello".to_string(), language.to_string(), false).await {
+                Err(GreetingError::Validation(message)) => assert!(message.contains("language code"), "{}", message),
+                other => panic!("expected a validation error for {:?}, got {:?}", language, other),
+            }
+        }
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn greetings_render_their_placeholders() {
+        let service = GreetingServiceImpl::new(Arc::new(InMemoryGreetingRepository::new()));
+        let greeting = service
+            .create_greeting("Hello, {name}! {name} has {count} {new} messages".to_string(), "en".to_string(), false)
+            .await
+            .unwrap();
+        let vars: HashMap<String, String> =
+            [("name", "Ada"), ("count", "3"), ("new", "unread")].map(|(k, v)| (k.to_string(), v.to_string())).into();
+        assert_eq!(service.render(&greeting.id, &vars).await.unwrap(), "Hello, Ada! Ada has 3 unread messages");
+
+        // Braces that are not placeholders are left alone
+        let json = service.create_greeting("{\"a\": {x y}}".to_string(), "en".to_string(), false).await.unwrap();
+        assert_eq!(service.render(&json.id, &HashMap::new()).await.unwrap(), "{\"a\": {x y}}");
+
+        match service.render(&greeting.id, &HashMap::from([("name".to_string(), "Ada".to_string())])).await {
+            Err(GreetingError::Validation(message)) => assert_eq!(message, "No value for placeholder(s): count, new"),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+        assert!(matches!(service.render("missing", &vars).await, Err(GreetingError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn greetings_are_stamped_by_the_injected_clock() {
+        let created_at = chrono::DateTime::parse_from_rfc3339("2026-01-01T12:00:00Z").unwrap().to_utc();
+        let clock = Arc::new(crate::test_support::FixedClock::new(created_at));
+        let service = GreetingServiceImpl::new(Arc::new(InMemoryGreetingRepository::new())).with_clock(clock.clone());
+        let plain = service.create_greeting("Hello".to_string(), "en".to_string(), false).await.unwrap();
+        clock.advance(chrono::Duration::minutes(1));
+        let deduped = service.create_greeting("Hola".to_string(), "es".to_string(), true).await.unwrap();
+        assert_eq!(plain.created_at, created_at);
+        assert_eq!(deduped.created_at, created_at + chrono::Duration::minutes(1));
+    }
+}
@@ -0,0 +1,24 @@
+// System entities - host-wide settings
+
+use serde::{Deserialize, Serialize};
+
+// Whether the kernel routes packets between interfaces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct IpForwarding {
+    pub ipv4: bool,
+    pub ipv6: bool,
+}
+
+// What the running process is allowed to do to the network stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessPrivileges {
+    pub running_as_root: bool,
+    // CAP_NET_ADMIN in the effective set, which is enough without being root
+    pub has_net_admin: bool,
+}
+
+impl ProcessPrivileges {
+    pub fn can_manage_network(&self) -> bool {
+        self.running_as_root || self.has_net_admin
+    }
+}
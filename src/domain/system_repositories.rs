@@ -0,0 +1,21 @@
+// System repository traits - host-wide settings outside of the network configuration
+
+use async_trait::async_trait;
+use crate::domain::system_entities::{IpForwarding, ProcessPrivileges};
+
+#[async_trait]
+pub trait HostnameRepository: Send + Sync {
+    async fn get(&self) -> Result<String, String>;
+    async fn set(&self, hostname: &str) -> Result<(), String>;
+}
+
+#[async_trait]
+pub trait IpForwardingRepository: Send + Sync {
+    async fn get(&self) -> Result<IpForwarding, String>;
+    async fn set(&self, forwarding: &IpForwarding) -> Result<(), String>;
+}
+
+#[async_trait]
+pub trait PrivilegeProbe: Send + Sync {
+    async fn probe(&self) -> Result<ProcessPrivileges, String>;
+}
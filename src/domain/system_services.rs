@@ -0,0 +1,107 @@
+// System services - host-wide settings that sit next to the network configuration
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use crate::domain::network_errors::NetworkError;
+use crate::domain::system_entities::IpForwarding;
+use crate::domain::system_repositories::{HostnameRepository, IpForwardingRepository};
+use crate::domain::validation::validate_hostname;
+
+#[async_trait]
+pub trait HostnameService: Send + Sync {
+    async fn get(&self) -> Result<String, NetworkError>;
+    async fn set(&self, hostname: &str) -> Result<String, NetworkError>;
+}
+
+pub struct HostnameServiceImpl {
+    repository: Arc<dyn HostnameRepository>,
+    // Off when changes are not pushed to the system; the hostname can still be read
+    can_set: bool,
+}
+
+impl HostnameServiceImpl {
+    pub fn new(repository: Arc<dyn HostnameRepository>, can_set: bool) -> Self {
+        Self { repository, can_set }
+    }
+}
+
+#[async_trait]
+impl HostnameService for HostnameServiceImpl {
+    async fn get(&self) -> Result<String, NetworkError> {
+        Ok(self.repository.get().await?)
+    }
+
+    async fn set(&self, hostname: &str) -> Result<String, NetworkError> {
+        validate_hostname(hostname)?;
+        if !self.can_set {
+            return Err(NetworkError::Forbidden(
+                "Changing the hostname requires the apply-system feature".to_string(),
+            ));
+        }
+        self.repository.set(hostname).await?;
+        tracing::info!("Hostname set to {}", hostname);
+        Ok(hostname.to_string())
+    }
+}
+
+#[async_trait]
+pub trait IpForwardingService: Send + Sync {
+    async fn get(&self) -> Result<IpForwarding, NetworkError>;
+    async fn set(&self, forwarding: IpForwarding) -> Result<IpForwarding, NetworkError>;
+}
+
+pub struct IpForwardingServiceImpl {
+    repository: Arc<dyn IpForwardingRepository>,
+    // Off when changes are not pushed to the system; the current values can still be read
+    can_set: bool,
+}
+
+impl IpForwardingServiceImpl {
+    pub fn new(repository: Arc<dyn IpForwardingRepository>, can_set: bool) -> Self {
+        Self { repository, can_set }
+    }
+}
+
+#[async_trait]
+impl IpForwardingService for IpForwardingServiceImpl {
+    async fn get(&self) -> Result<IpForwarding, NetworkError> {
+        Ok(self.repository.get().await?)
+    }
+
+    async fn set(&self, forwarding: IpForwarding) -> Result<IpForwarding, NetworkError> {
+        if !self.can_set {
+            return Err(NetworkError::Forbidden(
+                "Changing IP forwarding requires the apply-system feature".to_string(),
+            ));
+        }
+        self.repository.set(&forwarding).await?;
+        tracing::info!("IP forwarding set to ipv4={} ipv6={}", forwarding.ipv4, forwarding.ipv6);
+        Ok(forwarding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::simulation::SimulatedHostnameRepository;
+
+    #[tokio::test]
+    async fn hostnames_are_validated_before_they_are_set() {
+        let service = HostnameServiceImpl::new(Arc::new(SimulatedHostnameRepository::new()), true);
+        assert_eq!(service.set("homelab-01").await.unwrap(), "homelab-01");
+        assert_eq!(service.get().await.unwrap(), "homelab-01");
+
+        let overlong = "a".repeat(64);
+        for hostname in ["home_lab", overlong.as_str(), "", "-homelab"] {
+            assert!(matches!(service.set(hostname).await, Err(NetworkError::Validation(_))), "{:?}", hostname);
+        }
+        assert_eq!(service.get().await.unwrap(), "homelab-01");
+    }
+
+    #[tokio::test]
+    async fn hostname_is_read_only_without_system_apply() {
+        let service = HostnameServiceImpl::new(Arc::new(SimulatedHostnameRepository::new()), false);
+        assert!(matches!(service.set("homelab-01").await, Err(NetworkError::Forbidden(_))));
+        assert_eq!(service.get().await.unwrap(), "homelab-sim");
+    }
+}
@@ -0,0 +1,364 @@
+// Validation primitives - pure checks shared by the config entities, services and the config loader.
+// Messages are written for API clients, so they name the field and echo the rejected value.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
+use crate::domain::network_entities::WifiSecurityType;
+use crate::domain::network_errors::NetworkError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError(String);
+
+impl ValidationError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<ValidationError> for NetworkError {
+    fn from(error: ValidationError) -> Self {
+        NetworkError::Validation(error.0)
+    }
+}
+
+// Kernel interface names are at most IFNAMSIZ - 1 bytes
+pub const MAX_INTERFACE_NAME_LEN: usize = 15;
+
+// SSIDs are at most 32 bytes (802.11)
+pub const MAX_SSID_LEN: usize = 32;
+
+pub fn parse_ipv4(field: &str, value: &str) -> Result<Ipv4Addr, ValidationError> {
+    value
+        .parse()
+        .map_err(|_| ValidationError::new(format!("{} is not a valid IPv4 address: {}", field, value)))
+}
+
+pub fn parse_ip(field: &str, value: &str) -> Result<IpAddr, ValidationError> {
+    value
+        .parse()
+        .map_err(|_| ValidationError::new(format!("{} is not a valid IP address: {}", field, value)))
+}
+
+// Ones followed only by zeros, e.g. 255.255.255.0 but not 255.0.255.0
+pub fn is_contiguous_mask(mask: Ipv4Addr) -> bool {
+    let bits = u32::from(mask);
+    bits.leading_ones() + bits.trailing_zeros() == 32
+}
+
+// Prefix length of a dotted subnet mask
+pub fn parse_subnet_mask(subnet_mask: &str) -> Result<u8, ValidationError> {
+    subnet_mask
+        .parse()
+        .ok()
+        .filter(|mask| is_contiguous_mask(*mask))
+        .map(|mask| u32::from(mask).leading_ones() as u8)
+        .ok_or_else(|| ValidationError::new(format!("Invalid subnet mask: {}", subnet_mask)))
+}
+
+// Prefix length of a dotted subnet mask, or `None` if it is not a valid contiguous mask
+pub fn mask_to_prefix_len(subnet_mask: &str) -> Option<u8> {
+    parse_subnet_mask(subnet_mask).ok()
+}
+
+fn prefix_mask(prefix_len: u8) -> u32 {
+    u32::MAX.checked_shl(32 - prefix_len.min(32) as u32).unwrap_or(0)
+}
+
+// Network address of `ip_address`/`prefix_len`, e.g. 192.168.1.0 for 192.168.1.10/24
+pub fn network_address(ip_address: Ipv4Addr, prefix_len: u8) -> Ipv4Addr {
+    Ipv4Addr::from(u32::from(ip_address) & prefix_mask(prefix_len))
+}
+
+// Two subnets overlap when the shorter prefix contains the other's network address
+pub fn subnets_overlap(a: Ipv4Addr, a_prefix_len: u8, b: Ipv4Addr, b_prefix_len: u8) -> bool {
+    let prefix_len = a_prefix_len.min(b_prefix_len);
+    network_address(a, prefix_len) == network_address(b, prefix_len)
+}
+
+// The gateway must be reachable on-link from `ip_address`. A /32 has no subnet to be in, so its
+// gateway is taken as an explicit on-link route and accepted.
+pub fn gateway_in_subnet(ip_address: Ipv4Addr, prefix_len: u8, gateway: Ipv4Addr) -> Result<(), ValidationError> {
+    if prefix_len >= 32 {
+        return Ok(());
+    }
+    if network_address(ip_address, prefix_len) == network_address(gateway, prefix_len) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(format!(
+            "gateway {} is not in the subnet of {}/{}",
+            gateway, ip_address, prefix_len
+        )))
+    }
+}
+
+// An SSID as it will be stored, i.e. after any trimming
+pub fn validate_ssid(ssid: &str) -> Result<(), ValidationError> {
+    if ssid.is_empty() {
+        Err(ValidationError::new("ssid is required"))
+    } else if ssid.trim().is_empty() {
+        Err(ValidationError::new("ssid must not be only whitespace"))
+    } else if ssid.len() > MAX_SSID_LEN {
+        Err(ValidationError::new(format!("ssid must be at most {} bytes", MAX_SSID_LEN)))
+    } else {
+        Ok(())
+    }
+}
+
+// Printable ASCII (32-126), the character set of WPA passphrases
+fn is_printable_ascii(value: &str) -> bool {
+    value.bytes().all(|b| (32..=126).contains(&b))
+}
+
+// The key or passphrase each security type accepts; open networks take anything. Keys end up in
+// wpa_supplicant.conf, so none may contain control characters such as a newline.
+pub fn validate_psk(security_type: &WifiSecurityType, password: &str) -> Result<(), ValidationError> {
+    let is_hex = |value: &str| value.chars().all(|c| c.is_ascii_hexdigit());
+    match security_type {
+        WifiSecurityType::Open => Ok(()),
+        WifiSecurityType::WEP => {
+            let valid = match password.len() {
+                5 | 13 => is_printable_ascii(password),
+                10 | 26 => is_hex(password),
+                _ => false,
+            };
+            if valid {
+                Ok(())
+            } else {
+                Err(ValidationError::new("WEP key must be 5 or 13 characters, or 10 or 26 hex digits"))
+            }
+        }
+        WifiSecurityType::WPA | WifiSecurityType::WPA2 => {
+            let valid = (password.len() == 64 && is_hex(password))
+                || ((8..=63).contains(&password.len()) && is_printable_ascii(password));
+            if valid {
+                Ok(())
+            } else {
+                Err(ValidationError::new("WPA passphrase must be 8-63 printable ASCII characters or 64 hex digits"))
+            }
+        }
+        WifiSecurityType::WPA3 => {
+            if password.len() < 8 {
+                Err(ValidationError::new("WPA3 password must be at least 8 characters"))
+            } else if password.chars().any(char::is_control) {
+                Err(ValidationError::new("WPA3 password must not contain control characters"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+// Interface names end up in file paths and command arguments, so only accept letters, digits and
+// '.', '-', '_', ':', '@' (VLANs like eth0.100, aliases like eth0:1). A leading '-' could be read as a
+// command-line option, and "." / ".." would escape /sys/class/net.
+pub fn is_valid_interface_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_INTERFACE_NAME_LEN
+        && name != "."
+        && name != ".."
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':' | '@'))
+}
+
+pub fn validate_interface_name(name: &str) -> Result<(), ValidationError> {
+    if is_valid_interface_name(name) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(format!("Invalid interface name: {:?}", name)))
+    }
+}
+
+// Caller-supplied config ids: 1-64 letters, digits, '-', '_' or '.', so they are safe in URLs and file names
+pub fn validate_config_id(id: &str) -> Result<(), ValidationError> {
+    let well_formed = !id.is_empty()
+        && id.len() <= 64
+        && !id.starts_with('.')
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if well_formed {
+        Ok(())
+    } else {
+        Err(ValidationError::new(format!(
+            "id must be 1-64 letters, digits, '-', '_' or '.' and not start with '.': {}",
+            id
+        )))
+    }
+}
+
+// Hostname-style domain name: dot-separated labels of letters, digits and inner hyphens,
+// each at most 63 characters and at most 253 in total. A trailing dot is allowed.
+pub fn validate_domain_name(name: &str) -> Result<(), ValidationError> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    if name.is_empty() {
+        return Err(ValidationError::new("domain name is empty"));
+    }
+    if name.len() > 253 {
+        return Err(ValidationError::new("domain name is longer than 253 characters"));
+    }
+    for label in name.split('.') {
+        if label.is_empty() {
+            return Err(ValidationError::new("domain name has an empty label"));
+        }
+        if label.len() > 63 {
+            return Err(ValidationError::new(format!("label '{}' is longer than 63 characters", label)));
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(ValidationError::new(format!("label '{}' contains invalid characters", label)));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(ValidationError::new(format!("label '{}' starts or ends with a hyphen", label)));
+        }
+    }
+    Ok(())
+}
+
+// RFC 1123 host name: the domain name rules without the trailing dot
+pub fn validate_hostname(name: &str) -> Result<(), ValidationError> {
+    if name.ends_with('.') {
+        return Err(ValidationError::new("hostname must not end with a dot"));
+    }
+    validate_domain_name(name).map_err(|e| ValidationError::new(format!("Invalid hostname {:?}: {}", name, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn psk_must_be_printable_ascii() {
+        assert!(validate_psk(&WifiSecurityType::WPA2, "correct horse").is_ok());
+        assert!(validate_psk(&WifiSecurityType::WPA2, "correct\"horse").is_ok());
+        assert!(validate_psk(&WifiSecurityType::WPA2, "correct\nhorse").is_err());
+        assert!(validate_psk(&WifiSecurityType::WPA2, "correct\u{7f}horse").is_err());
+        assert!(validate_psk(&WifiSecurityType::WEP, "ab\ncd").is_err());
+        assert!(validate_psk(&WifiSecurityType::WPA3, "correct horse\n").is_err());
+        assert!(validate_psk(&WifiSecurityType::WPA3, "correct horse").is_ok());
+    }
+
+    #[test]
+    fn interface_names_with_shell_metacharacters_are_rejected() {
+        for name in ["eth0", "wlan0", "eth0.100", "br-lan", "veth1@if2", "enp3s0:1", "a".repeat(15).as_str()] {
+            assert!(is_valid_interface_name(name), "{}", name);
+        }
+        for name in ["", "eth 0", "eth0;reboot", "$(reboot)", "`id`", "eth0|x", "eth0/..", "..", "-eth0", "a".repeat(16).as_str()] {
+            assert!(!is_valid_interface_name(name), "{}", name);
+            assert!(validate_interface_name(name).is_err(), "{}", name);
+        }
+    }
+
+    fn ip4(address: &str) -> Ipv4Addr {
+        address.parse().unwrap()
+    }
+
+    #[test]
+    fn addresses_name_the_field_they_came_from() {
+        assert_eq!(parse_ipv4("gateway", "192.168.1.1"), Ok(ip4("192.168.1.1")));
+        assert_eq!(
+            parse_ipv4("gateway", "192.168.1").unwrap_err().to_string(),
+            "gateway is not a valid IPv4 address: 192.168.1"
+        );
+        assert!(parse_ipv4("gateway", "fe80::1").is_err());
+        assert!(parse_ip("dns_primary", "2606:4700::1111").is_ok());
+        assert_eq!(
+            parse_ip("dns_primary", "dns.example").unwrap_err().to_string(),
+            "dns_primary is not a valid IP address: dns.example"
+        );
+    }
+
+    #[test]
+    fn subnet_masks_must_be_contiguous() {
+        assert!(is_contiguous_mask(ip4("255.255.255.0")));
+        assert!(is_contiguous_mask(ip4("0.0.0.0")));
+        assert!(is_contiguous_mask(ip4("255.255.255.255")));
+        assert!(!is_contiguous_mask(ip4("255.0.255.0")));
+        assert!(!is_contiguous_mask(ip4("0.255.255.255")));
+
+        assert_eq!(parse_subnet_mask("255.255.255.0"), Ok(24));
+        assert_eq!(parse_subnet_mask("255.255.255.252"), Ok(30));
+        assert_eq!(parse_subnet_mask("255.0.255.0").unwrap_err().to_string(), "Invalid subnet mask: 255.0.255.0");
+        assert!(parse_subnet_mask("24").is_err());
+    }
+
+    #[test]
+    fn gateway_must_be_on_link() {
+        assert!(gateway_in_subnet(ip4("192.168.1.60"), 24, ip4("192.168.1.1")).is_ok());
+        assert_eq!(
+            gateway_in_subnet(ip4("192.168.1.60"), 24, ip4("192.168.2.1")).unwrap_err().to_string(),
+            "gateway 192.168.2.1 is not in the subnet of 192.168.1.60/24"
+        );
+        assert!(gateway_in_subnet(ip4("10.1.2.3"), 8, ip4("10.200.0.1")).is_ok());
+        // A /32 gateway is an explicit on-link route
+        assert!(gateway_in_subnet(ip4("203.0.113.7"), 32, ip4("198.51.100.1")).is_ok());
+    }
+
+    #[test]
+    fn ssids_are_1_to_32_bytes_and_not_blank() {
+        assert!(validate_ssid("Home").is_ok());
+        assert!(validate_ssid(&"x".repeat(32)).is_ok());
+        assert_eq!(validate_ssid("").unwrap_err().to_string(), "ssid is required");
+        assert_eq!(validate_ssid("  ").unwrap_err().to_string(), "ssid must not be only whitespace");
+        assert_eq!(validate_ssid(&"x".repeat(33)).unwrap_err().to_string(), "ssid must be at most 32 bytes");
+        // Bytes, not characters: 11 three-byte characters are 33 bytes
+        assert!(validate_ssid(&"\u{20ac}".repeat(11)).is_err());
+    }
+
+    #[test]
+    fn psk_length_depends_on_the_security_type() {
+        assert!(validate_psk(&WifiSecurityType::Open, "").is_ok());
+        assert!(validate_psk(&WifiSecurityType::WEP, "abcde").is_ok());
+        assert!(validate_psk(&WifiSecurityType::WEP, "0123456789").is_ok());
+        assert!(validate_psk(&WifiSecurityType::WEP, "012345678g").is_err());
+        assert!(validate_psk(&WifiSecurityType::WEP, "abcdef").is_err());
+        assert!(validate_psk(&WifiSecurityType::WPA2, "short").is_err());
+        assert!(validate_psk(&WifiSecurityType::WPA2, &"a".repeat(63)).is_ok());
+        assert!(validate_psk(&WifiSecurityType::WPA2, &"a".repeat(64)).is_ok());
+        assert!(validate_psk(&WifiSecurityType::WPA2, &"g".repeat(64)).is_err());
+        assert!(validate_psk(&WifiSecurityType::WPA3, &"\u{e9}".repeat(80)).is_ok());
+        assert!(validate_psk(&WifiSecurityType::WPA3, "short").is_err());
+    }
+
+    #[test]
+    fn domain_names_follow_the_label_rules() {
+        for name in ["home.lan", "lab.example.com.", "a-b.c", "x"] {
+            assert!(validate_domain_name(name).is_ok(), "{}", name);
+        }
+        for (name, message) in [
+            ("", "domain name is empty"),
+            ("home..lan", "domain name has an empty label"),
+            ("-home.lan", "label '-home' starts or ends with a hyphen"),
+            ("home_lab.lan", "label 'home_lab' contains invalid characters"),
+        ] {
+            assert_eq!(validate_domain_name(name).unwrap_err().to_string(), message);
+        }
+        assert!(validate_domain_name(&"a".repeat(64)).is_err());
+        assert!(validate_domain_name(&vec!["a".repeat(63); 4].join(".")).is_err());
+    }
+
+    #[test]
+    fn hostnames_are_domain_names_without_a_trailing_dot() {
+        assert!(validate_hostname("homelab-01").is_ok());
+        assert!(validate_hostname("homelab-01.lan").is_ok());
+        assert_eq!(validate_hostname("homelab.").unwrap_err().to_string(), "hostname must not end with a dot");
+        assert_eq!(
+            validate_hostname("home_lab").unwrap_err().to_string(),
+            "Invalid hostname \"home_lab\": label 'home_lab' contains invalid characters"
+        );
+    }
+
+    #[test]
+    fn subnets_overlap_when_the_wider_one_contains_the_other() {
+        let ip = |s: &str| s.parse::<Ipv4Addr>().unwrap();
+        assert!(subnets_overlap(ip("192.168.1.10"), 24, ip("192.168.1.200"), 24));
+        assert!(subnets_overlap(ip("192.168.0.1"), 16, ip("192.168.42.1"), 24));
+        assert!(subnets_overlap(ip("192.168.42.1"), 24, ip("192.168.0.1"), 16));
+        assert!(!subnets_overlap(ip("192.168.1.10"), 24, ip("192.168.2.10"), 24));
+        assert!(!subnets_overlap(ip("192.168.1.10"), 25, ip("192.168.1.200"), 25));
+    }
+}
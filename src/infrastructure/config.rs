@@ -0,0 +1,548 @@
+// Application configuration - TOML file, environment variables and CLI flags
+//
+// Precedence (highest first): CLI flags, environment variables, `--config` file, built-in defaults
+
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use crate::domain::network_entities::{ConfigLimitPolicy, ConfigLimits, WifiSecurityType};
+use crate::domain::validation::is_valid_interface_name;
+
+// Short enough to type, long enough not to be guessed
+const MIN_ADMIN_TOKEN_LEN: usize = 16;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    // Listen on this Unix domain socket instead of host:port
+    pub unix_socket: Option<PathBuf>,
+    // Serve HTTPS with this PEM certificate chain and private key; both or neither
+    pub tls_cert: Option<PathBuf>,
+    pub tls_key: Option<PathBuf>,
+    pub data_dir: PathBuf,
+    pub wifi_interface: String,
+    pub monitor_interval_secs: u64,
+    // Give up on a WiFi scan after this many seconds (504 to the client)
+    pub scan_timeout_secs: u64,
+    // Rescan WiFi in the background this often (at least 30s) for the settings page; 0 disables
+    pub background_scan_secs: u64,
+    // Forget a finished WiFi scan after this many seconds, so the settings page and timed-out scans
+    // never show networks from long ago; 0 keeps it until the next scan
+    pub scan_cache_ttl_secs: u64,
+    // Requests per minute allowed from one client address (429 beyond that); 0 disables
+    pub rate_limit_per_minute: u32,
+    // Browser origins (e.g. "https://dashboard.lan") allowed to call the API cross-origin; empty sends no CORS headers
+    pub cors_origins: Vec<String>,
+    // Client subnets allowed to reach the server; empty allows everyone
+    pub admin_cidrs: Vec<String>,
+    // Bearer token for the admin-only endpoints (logs, revealed secrets), which are closed without it.
+    // Set in the config file or environment only, so it never shows up in the process list.
+    pub admin_token: Option<String>,
+    // Revert an enabled static IP unless it is confirmed within this many seconds; 0 disables
+    pub safe_mode_revert_secs: u64,
+    // Replay the response of a repeated Idempotency-Key POST within this many seconds; 0 disables
+    pub idempotency_ttl_secs: u64,
+    // Answer 504 when a request takes longer than this many seconds (streams are exempt); 0 disables
+    pub request_timeout_secs: u64,
+    // Maximum stored WiFi / static IP configs; 0 means unlimited
+    pub max_wifi_configs: usize,
+    pub max_static_ip_configs: usize,
+    // What creating a config past the maximum does: "reject" (409) or "evict-oldest"
+    pub config_limit_policy: ConfigLimitPolicy,
+    // Keep a copy of every generated networkd/wpa_supplicant file under `<data_dir>/generated/`
+    pub keep_generated_files: bool,
+    // Serve fake interfaces and WiFi networks and never touch the system
+    pub simulate: bool,
+    // JSON fixture for simulation mode; built-in demo data when unset
+    pub simulation_fixture: Option<PathBuf>,
+    // Initial values of the settings page forms
+    pub default_security_type: WifiSecurityType,
+    pub default_dns_primary: Option<IpAddr>,
+    pub default_dns_secondary: Option<IpAddr>,
+    // POST WiFi activation/deactivation events as JSON to this http:// URL
+    pub event_webhook_url: Option<String>,
+    // POST a `{"text": ...}` chat message about each config change to this http:// URL
+    pub notification_webhook_url: Option<String>,
+    // Serve the settings page and its assets at /; off leaves only the API
+    pub web_ui: bool,
+    // Serve Prometheus metrics at /metrics
+    pub metrics: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 80,
+            unix_socket: None,
+            tls_cert: None,
+            tls_key: None,
+            data_dir: PathBuf::from("data"),
+            wifi_interface: "wlan0".to_string(),
+            monitor_interval_secs: 5,
+            scan_timeout_secs: 10,
+            background_scan_secs: 0,
+            scan_cache_ttl_secs: 0,
+            rate_limit_per_minute: 0,
+            cors_origins: Vec::new(),
+            admin_cidrs: Vec::new(),
+            admin_token: None,
+            safe_mode_revert_secs: 0,
+            idempotency_ttl_secs: 600,
+            request_timeout_secs: 30,
+            max_wifi_configs: 0,
+            max_static_ip_configs: 0,
+            config_limit_policy: ConfigLimitPolicy::Reject,
+            keep_generated_files: false,
+            simulate: false,
+            simulation_fixture: None,
+            default_security_type: WifiSecurityType::WPA2,
+            default_dns_primary: None,
+            default_dns_secondary: None,
+            event_webhook_url: None,
+            notification_webhook_url: None,
+            web_ui: true,
+            metrics: true,
+        }
+    }
+}
+
+impl Config {
+    // Build the configuration from the process arguments and environment
+    pub fn load() -> Result<Self, String> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        Self::load_from(&args, |name| std::env::var(name).ok())
+    }
+
+    // `load` with the arguments and environment variables passed in
+    fn load_from(args: &[String], env: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let cli = CliArgs::parse(args)?;
+
+        let config_path = cli
+            .config
+            .clone()
+            .or_else(|| env("HOMELABME_CONFIG").map(PathBuf::from));
+
+        let mut config = match config_path {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        config.apply_env(&env)?;
+        config.apply_cli(cli);
+        if config.tls_cert.is_some() != config.tls_key.is_some() {
+            return Err("TLS needs both a certificate (--tls-cert) and a private key (--tls-key)".to_string());
+        }
+        if config.admin_token.as_ref().is_some_and(|token| token.len() < MIN_ADMIN_TOKEN_LEN) {
+            return Err(format!("admin_token must be at least {} characters", MIN_ADMIN_TOKEN_LEN));
+        }
+        // Passed to wpa_cli and used in file names
+        if !is_valid_interface_name(&config.wifi_interface) {
+            return Err(format!("Invalid wifi_interface: {:?}", config.wifi_interface));
+        }
+        if let Some(origin) = config.cors_origins.iter().find(|origin| !is_valid_origin(origin)) {
+            return Err(format!("Invalid CORS origin {:?}; expected scheme://host[:port]", origin));
+        }
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config file {}: {}", path.display(), e))?;
+        Self::from_toml(&contents)
+            .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.to_string())
+    }
+
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    pub fn config_limits(&self) -> ConfigLimits {
+        ConfigLimits {
+            max_wifi_configs: self.max_wifi_configs,
+            max_static_ip_configs: self.max_static_ip_configs,
+            policy: self.config_limit_policy,
+        }
+    }
+
+    fn apply_env(&mut self, env: &impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        if let Some(host) = env("HOMELABME_HOST") {
+            self.host = host;
+        }
+        if let Some(port) = env("PORT") {
+            self.port = port
+                .parse()
+                .map_err(|_| format!("Invalid PORT value: {}", port))?;
+        }
+        if let Some(unix_socket) = env("HOMELABME_UNIX_SOCKET") {
+            self.unix_socket = Some(PathBuf::from(unix_socket));
+        }
+        if let Some(tls_cert) = env("HOMELABME_TLS_CERT") {
+            self.tls_cert = Some(PathBuf::from(tls_cert));
+        }
+        if let Some(tls_key) = env("HOMELABME_TLS_KEY") {
+            self.tls_key = Some(PathBuf::from(tls_key));
+        }
+        if let Some(data_dir) = env("HOMELABME_DATA_DIR") {
+            self.data_dir = PathBuf::from(data_dir);
+        }
+        if let Some(wifi_interface) = env("HOMELABME_WIFI_INTERFACE") {
+            self.wifi_interface = wifi_interface;
+        }
+        if let Some(secs) = env("HOMELABME_SCAN_TIMEOUT_SECS") {
+            self.scan_timeout_secs = secs
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_SCAN_TIMEOUT_SECS value: {}", secs))?;
+        }
+        if let Some(secs) = env("HOMELABME_BACKGROUND_SCAN_SECS") {
+            self.background_scan_secs = secs
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_BACKGROUND_SCAN_SECS value: {}", secs))?;
+        }
+        if let Some(secs) = env("HOMELABME_SCAN_CACHE_TTL_SECS") {
+            self.scan_cache_ttl_secs = secs
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_SCAN_CACHE_TTL_SECS value: {}", secs))?;
+        }
+        if let Some(limit) = env("HOMELABME_RATE_LIMIT_PER_MINUTE") {
+            self.rate_limit_per_minute = limit
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_RATE_LIMIT_PER_MINUTE value: {}", limit))?;
+        }
+        if let Some(origins) = env("HOMELABME_CORS_ORIGINS") {
+            self.cors_origins = split_list(&origins);
+        }
+        if let Some(web_ui) = env("HOMELABME_WEB_UI") {
+            self.web_ui = parse_flag("HOMELABME_WEB_UI", &web_ui)?;
+        }
+        if let Some(metrics) = env("HOMELABME_METRICS") {
+            self.metrics = parse_flag("HOMELABME_METRICS", &metrics)?;
+        }
+        if let Some(secs) = env("HOMELABME_SAFE_MODE_REVERT_SECS") {
+            self.safe_mode_revert_secs = secs
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_SAFE_MODE_REVERT_SECS value: {}", secs))?;
+        }
+        if let Some(secs) = env("HOMELABME_IDEMPOTENCY_TTL_SECS") {
+            self.idempotency_ttl_secs = secs
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_IDEMPOTENCY_TTL_SECS value: {}", secs))?;
+        }
+        if let Some(secs) = env("HOMELABME_REQUEST_TIMEOUT_SECS") {
+            self.request_timeout_secs = secs
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_REQUEST_TIMEOUT_SECS value: {}", secs))?;
+        }
+        if let Some(max) = env("HOMELABME_MAX_WIFI_CONFIGS") {
+            self.max_wifi_configs = max
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_MAX_WIFI_CONFIGS value: {}", max))?;
+        }
+        if let Some(max) = env("HOMELABME_MAX_STATIC_IP_CONFIGS") {
+            self.max_static_ip_configs = max
+                .parse()
+                .map_err(|_| format!("Invalid HOMELABME_MAX_STATIC_IP_CONFIGS value: {}", max))?;
+        }
+        if let Some(policy) = env("HOMELABME_CONFIG_LIMIT_POLICY") {
+            self.config_limit_policy = policy.parse()?;
+        }
+        if let Some(keep) = env("HOMELABME_KEEP_GENERATED_FILES") {
+            self.keep_generated_files = parse_flag("HOMELABME_KEEP_GENERATED_FILES", &keep)?;
+        }
+        if let Some(simulate) = env("HOMELABME_SIM") {
+            self.simulate = parse_flag("HOMELABME_SIM", &simulate)?;
+        }
+        if let Some(fixture) = env("HOMELABME_SIM_FIXTURE") {
+            self.simulation_fixture = Some(PathBuf::from(fixture));
+        }
+        if let Some(security_type) = env("HOMELABME_DEFAULT_SECURITY_TYPE") {
+            self.default_security_type = security_type
+                .parse()
+                .map_err(|e| format!("Invalid HOMELABME_DEFAULT_SECURITY_TYPE value: {}", e))?;
+        }
+        if let Some(dns) = env("HOMELABME_DEFAULT_DNS_PRIMARY") {
+            self.default_dns_primary = parse_optional_ip("HOMELABME_DEFAULT_DNS_PRIMARY", &dns)?;
+        }
+        if let Some(dns) = env("HOMELABME_DEFAULT_DNS_SECONDARY") {
+            self.default_dns_secondary = parse_optional_ip("HOMELABME_DEFAULT_DNS_SECONDARY", &dns)?;
+        }
+        if let Some(url) = env("HOMELABME_EVENT_WEBHOOK_URL") {
+            self.event_webhook_url = Some(url).filter(|url| !url.trim().is_empty());
+        }
+        if let Some(url) = env("HOMELABME_NOTIFICATION_WEBHOOK_URL") {
+            self.notification_webhook_url = Some(url).filter(|url| !url.trim().is_empty());
+        }
+        if let Some(token) = env("HOMELABME_ADMIN_TOKEN") {
+            self.admin_token = Some(token).filter(|token| !token.trim().is_empty());
+        }
+        if let Some(admin_cidrs) = env("HOMELABME_ADMIN_CIDRS") {
+            self.admin_cidrs = split_list(&admin_cidrs);
+        }
+        Ok(())
+    }
+
+    fn apply_cli(&mut self, cli: CliArgs) {
+        if let Some(host) = cli.host {
+            self.host = host;
+        }
+        if let Some(port) = cli.port {
+            self.port = port;
+        }
+        if let Some(unix_socket) = cli.unix_socket {
+            self.unix_socket = Some(unix_socket);
+        }
+        if let Some(tls_cert) = cli.tls_cert {
+            self.tls_cert = Some(tls_cert);
+        }
+        if let Some(tls_key) = cli.tls_key {
+            self.tls_key = Some(tls_key);
+        }
+        if let Some(data_dir) = cli.data_dir {
+            self.data_dir = data_dir;
+        }
+        if !cli.cors_origins.is_empty() {
+            self.cors_origins = cli.cors_origins;
+        }
+        if cli.no_web_ui {
+            self.web_ui = false;
+        }
+        if cli.no_metrics {
+            self.metrics = false;
+        }
+    }
+}
+
+// Comma-separated environment lists; blank entries are dropped
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Browsers send the Origin header as scheme://host[:port], with no path or trailing slash
+fn is_valid_origin(origin: &str) -> bool {
+    let Some((scheme, host)) = origin.split_once("://") else {
+        return false;
+    };
+    matches!(scheme, "http" | "https")
+        && !host.is_empty()
+        && !host.contains(['/', '?', '#'])
+        && axum::http::HeaderValue::from_str(origin).is_ok()
+}
+
+// An IP address environment value; empty unsets it
+fn parse_optional_ip(name: &str, value: &str) -> Result<Option<IpAddr>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value.parse().map(Some).map_err(|_| format!("Invalid {} value: {}", name, value))
+}
+
+// Boolean environment values: 1/true/yes/on or 0/false/no/off (empty is false)
+fn parse_flag(name: &str, value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" | "" => Ok(false),
+        _ => Err(format!("Invalid {} value: {}", name, value)),
+    }
+}
+
+// Command line flags that can override the config file
+#[derive(Debug, Default)]
+struct CliArgs {
+    config: Option<PathBuf>,
+    host: Option<String>,
+    port: Option<u16>,
+    unix_socket: Option<PathBuf>,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    // Repeatable; replaces the file and environment list
+    cors_origins: Vec<String>,
+    no_web_ui: bool,
+    no_metrics: bool,
+}
+
+impl CliArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut cli = Self::default();
+        let mut iter = args.iter();
+        while let Some(flag) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .cloned()
+                    .ok_or_else(|| format!("Missing value for {}", flag))
+            };
+            match flag.as_str() {
+                "--config" => cli.config = Some(PathBuf::from(value()?)),
+                "--host" => cli.host = Some(value()?),
+                "--port" => {
+                    let port = value()?;
+                    cli.port = Some(port.parse().map_err(|_| format!("Invalid --port value: {}", port))?);
+                }
+                "--unix" => cli.unix_socket = Some(PathBuf::from(value()?)),
+                "--tls-cert" => cli.tls_cert = Some(PathBuf::from(value()?)),
+                "--tls-key" => cli.tls_key = Some(PathBuf::from(value()?)),
+                "--data-dir" => cli.data_dir = Some(PathBuf::from(value()?)),
+                "--cors-origin" => cli.cors_origins.push(value()?),
+                "--no-web-ui" => cli.no_web_ui = true,
+                "--no-metrics" => cli.no_metrics = true,
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+        Ok(cli)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+    use std::collections::HashMap;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn env(values: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let values: HashMap<String, String> = values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| values.get(name).cloned()
+    }
+
+    #[test]
+    fn sample_toml_is_parsed() {
+        let config = Config::from_toml(
+            r#"
+            host = "127.0.0.1"
+            port = 8080
+            data_dir = "/var/lib/homelabme"
+            wifi_interface = "wlp2s0"
+            admin_cidrs = ["192.168.1.0/24"]
+            config_limit_policy = "evict-oldest"
+            default_security_type = "WPA3"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.bind_address(), "127.0.0.1:8080");
+        assert_eq!(config.data_dir, PathBuf::from("/var/lib/homelabme"));
+        assert_eq!(config.wifi_interface, "wlp2s0");
+        assert_eq!(config.admin_cidrs, vec!["192.168.1.0/24".to_string()]);
+        assert_eq!(config.config_limit_policy, ConfigLimitPolicy::EvictOldest);
+        assert_eq!(config.default_security_type, WifiSecurityType::WPA3);
+        // Everything not in the file keeps its default
+        assert_eq!(config.scan_timeout_secs, 10);
+    }
+
+    #[test]
+    fn unknown_toml_keys_are_rejected() {
+        assert!(Config::from_toml("prot = 8080").is_err());
+    }
+
+    #[test]
+    fn cli_beats_env_beats_file() {
+        let path = temp_dir().join("homelabme.toml");
+        std::fs::write(&path, "host = \"10.0.0.1\"\ndata_dir = \"/from/file\"\nport = 8000\n").unwrap();
+        let config_arg = path.to_string_lossy().to_string();
+
+        let config = Config::load_from(&args(&["--config", &config_arg]), env(&[])).unwrap();
+        assert_eq!(config.host, "10.0.0.1");
+        assert_eq!(config.data_dir, PathBuf::from("/from/file"));
+
+        let from_env = env(&[("HOMELABME_HOST", "10.0.0.2"), ("HOMELABME_DATA_DIR", "/from/env")]);
+        let config = Config::load_from(&args(&["--config", &config_arg]), from_env).unwrap();
+        assert_eq!(config.host, "10.0.0.2");
+        assert_eq!(config.data_dir, PathBuf::from("/from/env"));
+        assert_eq!(config.port, 8000);
+
+        let from_env = env(&[("HOMELABME_HOST", "10.0.0.2"), ("HOMELABME_DATA_DIR", "/from/env")]);
+        let cli = args(&["--config", &config_arg, "--host", "10.0.0.3", "--data-dir", "/from/cli"]);
+        let config = Config::load_from(&cli, from_env).unwrap();
+        assert_eq!(config.host, "10.0.0.3");
+        assert_eq!(config.data_dir, PathBuf::from("/from/cli"));
+    }
+
+    #[test]
+    fn config_file_can_come_from_the_environment() {
+        let path = temp_dir().join("homelabme.toml");
+        std::fs::write(&path, "port = 8123\n").unwrap();
+        let config = Config::load_from(&[], env(&[("HOMELABME_CONFIG", &path.to_string_lossy())])).unwrap();
+        assert_eq!(config.port, 8123);
+    }
+
+    #[test]
+    fn invalid_settings_are_reported() {
+        assert!(Config::load_from(&args(&["--port", "http"]), env(&[])).is_err());
+        assert!(Config::load_from(&args(&["--verbose"]), env(&[])).is_err());
+        assert!(Config::load_from(&[], env(&[("HOMELABME_SIM", "maybe")])).is_err());
+        assert!(Config::load_from(&args(&["--tls-cert", "cert.pem"]), env(&[])).is_err());
+        assert!(Config::load_from(&[], env(&[("HOMELABME_WIFI_INTERFACE", "wlan0; reboot")])).is_err());
+        assert!(Config::load_from(&[], env(&[("HOMELABME_ADMIN_TOKEN", "short")])).is_err());
+    }
+
+    #[test]
+    fn admin_token_comes_from_the_environment() {
+        let config = Config::load_from(&[], env(&[("HOMELABME_ADMIN_TOKEN", "0123456789abcdef")])).unwrap();
+        assert_eq!(config.admin_token.as_deref(), Some("0123456789abcdef"));
+        let config = Config::load_from(&[], env(&[("HOMELABME_ADMIN_TOKEN", " ")])).unwrap();
+        assert_eq!(config.admin_token, None);
+    }
+
+    #[test]
+    fn service_settings_load_from_every_source() {
+        let config = Config::from_toml(
+            r#"
+            scan_cache_ttl_secs = 120
+            rate_limit_per_minute = 60
+            cors_origins = ["https://dashboard.lan"]
+            web_ui = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.scan_cache_ttl_secs, 120);
+        assert_eq!(config.rate_limit_per_minute, 60);
+        assert_eq!(config.cors_origins, vec!["https://dashboard.lan".to_string()]);
+        assert!(!config.web_ui);
+        assert!(config.metrics);
+
+        let from_env = env(&[
+            ("HOMELABME_SCAN_CACHE_TTL_SECS", "30"),
+            ("HOMELABME_RATE_LIMIT_PER_MINUTE", "10"),
+            ("HOMELABME_CORS_ORIGINS", "http://a.lan, http://b.lan:8080,"),
+            ("HOMELABME_METRICS", "off"),
+        ]);
+        let config = Config::load_from(&[], from_env).unwrap();
+        assert_eq!(config.scan_cache_ttl_secs, 30);
+        assert_eq!(config.rate_limit_per_minute, 10);
+        assert_eq!(config.cors_origins, vec!["http://a.lan".to_string(), "http://b.lan:8080".to_string()]);
+        assert!(config.web_ui);
+        assert!(!config.metrics);
+
+        let cli = args(&["--cors-origin", "https://c.lan", "--cors-origin", "https://d.lan", "--no-web-ui"]);
+        let config = Config::load_from(&cli, env(&[("HOMELABME_CORS_ORIGINS", "http://a.lan")])).unwrap();
+        assert_eq!(config.cors_origins, vec!["https://c.lan".to_string(), "https://d.lan".to_string()]);
+        assert!(!config.web_ui);
+    }
+
+    #[test]
+    fn cors_origins_must_be_bare_origins() {
+        for origin in ["dashboard.lan", "https://dashboard.lan/", "ftp://dashboard.lan", "https://", "*"] {
+            let result = Config::load_from(&args(&["--cors-origin", origin]), env(&[]));
+            assert!(result.is_err(), "{}", origin);
+        }
+        assert!(Config::load_from(&args(&["--cors-origin", "http://[fd00::1]:8080"]), env(&[])).is_ok());
+        assert!(Config::load_from(&[], env(&[("HOMELABME_RATE_LIMIT_PER_MINUTE", "-1")])).is_err());
+    }
+}
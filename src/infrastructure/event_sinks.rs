@@ -0,0 +1,107 @@
+// Event sink implementations - deliver domain events to outside integrations
+
+use async_trait::async_trait;
+use axum::http::{header, Request, Uri};
+use hyper_util::rt::TokioIo;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use crate::domain::network_events::{EventSink, NetworkEvent};
+use crate::domain::notifications::NotificationSink;
+
+// JSON POSTs to a configured URL. Only plain http:// is supported; point it at a local relay
+// to reach an https endpoint.
+struct JsonWebhook {
+    url: Uri,
+    timeout: Duration,
+}
+
+impl JsonWebhook {
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn new(url: &str) -> Result<Self, String> {
+        let parsed: Uri = url.parse().map_err(|_| format!("Invalid webhook URL: {}", url))?;
+        if parsed.scheme_str() != Some("http") {
+            return Err(format!("Webhook URL must start with http:// (https is not supported): {}", url));
+        }
+        if parsed.host().is_none_or(str::is_empty) {
+            return Err(format!("Webhook URL has no host: {}", url));
+        }
+        Ok(Self { url: parsed, timeout: Self::DEFAULT_TIMEOUT })
+    }
+
+    async fn post_json(&self, body: String) -> Result<(), String> {
+        // Checked in `new`
+        let authority = self.url.authority().expect("webhook URL has a host");
+        // Bracketed IPv6 literals are not accepted by the resolver
+        let host = authority.host().trim_start_matches('[').trim_end_matches(']');
+        let port = authority.port_u16().unwrap_or(80);
+        let path = self.url.path_and_query().map_or("/", |path| path.as_str());
+
+        let stream = TcpStream::connect((host, port))
+            .await
+            .map_err(|e| format!("Failed to connect to {}: {}", authority, e))?;
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .map_err(|e| format!("HTTP handshake with {} failed: {}", authority, e))?;
+        tokio::spawn(connection);
+
+        let request = Request::post(path)
+            .header(header::HOST, authority.as_str())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .map_err(|e| e.to_string())?;
+        let response = sender
+            .send_request(request)
+            .await
+            .map_err(|e| format!("Webhook request to {} failed: {}", authority, e))?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Webhook {} answered {}", self.url, response.status()))
+        }
+    }
+
+    async fn post(&self, body: String) -> Result<(), String> {
+        tokio::time::timeout(self.timeout, self.post_json(body))
+            .await
+            .map_err(|_| format!("Webhook {} timed out after {}s", self.url, self.timeout.as_secs()))?
+    }
+}
+
+// POSTs each event as JSON
+pub struct WebhookEventSink {
+    webhook: JsonWebhook,
+}
+
+impl WebhookEventSink {
+    pub fn new(url: &str) -> Result<Self, String> {
+        Ok(Self { webhook: JsonWebhook::new(url)? })
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookEventSink {
+    async fn publish(&self, event: &NetworkEvent) -> Result<(), String> {
+        let body = serde_json::to_string(event).map_err(|e| e.to_string())?;
+        self.webhook.post(body).await
+    }
+}
+
+// POSTs `{"text": "..."}`, the message shape Slack and Mattermost incoming webhooks accept
+pub struct WebhookNotificationSink {
+    webhook: JsonWebhook,
+}
+
+impl WebhookNotificationSink {
+    pub fn new(url: &str) -> Result<Self, String> {
+        Ok(Self { webhook: JsonWebhook::new(url)? })
+    }
+}
+
+#[async_trait]
+impl NotificationSink for WebhookNotificationSink {
+    async fn send(&self, text: &str) -> Result<(), String> {
+        let body = serde_json::json!({ "text": text }).to_string();
+        self.webhook.post(body).await
+    }
+}
@@ -0,0 +1,130 @@
+// In-memory log buffer - keeps the most recent tracing events so they can be read over HTTP
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+pub const LOG_BUFFER_CAPACITY: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogRecord {
+    pub timestamp: String,
+    #[serde(serialize_with = "serialize_level")]
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+fn serialize_level<S: serde::Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(level.as_str())
+}
+
+// Bounded ring buffer; the lock is only held to push or copy records out
+pub struct LogBuffer {
+    capacity: usize,
+    records: Mutex<VecDeque<LogRecord>>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn push(&self, record: LogRecord) {
+        let mut records = self.records.lock().unwrap();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    // Up to `limit` most recent records at `level` or more severe, oldest first
+    pub fn recent(&self, level: Level, limit: usize) -> Vec<LogRecord> {
+        let records = self.records.lock().unwrap();
+        let mut matching: Vec<LogRecord> = records
+            .iter()
+            .rev()
+            .filter(|record| record.level <= level)
+            .take(limit)
+            .cloned()
+            .collect();
+        matching.reverse();
+        matching
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(LOG_BUFFER_CAPACITY)
+    }
+}
+
+// tracing layer that copies every event it sees into a shared LogBuffer
+pub struct LogBufferLayer {
+    buffer: Arc<LogBuffer>,
+}
+
+impl LogBufferLayer {
+    pub fn new(buffer: Arc<LogBuffer>) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.buffer.push(LogRecord {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+// Collects the `message` field followed by any other fields as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else if self.message.is_empty() {
+            self.fields.trim_start().to_string()
+        } else {
+            format!("{}{}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+}
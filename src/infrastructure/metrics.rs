@@ -0,0 +1,140 @@
+// Request metrics - per-route counters and latency histograms, rendered in the Prometheus text format
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// Route label for requests that matched no route, so unknown paths cannot grow the label set
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+// Upper bounds of the latency buckets, in seconds
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+#[derive(Default)]
+struct LatencyHistogram {
+    // Non-cumulative count per bucket; the extra last slot is +Inf
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    sum_secs: f64,
+    count: u64,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    // (route template, status class) -> requests
+    requests: BTreeMap<(String, &'static str), u64>,
+    latency: BTreeMap<String, LatencyHistogram>,
+}
+
+#[derive(Default)]
+pub struct RequestMetrics {
+    state: Mutex<MetricsState>,
+}
+
+impl RequestMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // `route` is the matched route template (e.g. /api/network/wifi/:id), never the raw path
+    pub fn record(&self, route: &str, status: u16, elapsed: Duration) {
+        let mut state = self.state.lock().unwrap();
+        *state
+            .requests
+            .entry((route.to_string(), status_class(status)))
+            .or_default() += 1;
+
+        let histogram = state.latency.entry(route.to_string()).or_default();
+        let secs = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| secs <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        histogram.buckets[bucket] += 1;
+        histogram.sum_secs += secs;
+        histogram.count += 1;
+    }
+
+    pub fn render(&self) -> String {
+        let state = self.state.lock().unwrap();
+        let mut out = String::new();
+
+        out.push_str("# HELP homelabme_requests_total HTTP requests by route and status class.\n");
+        out.push_str("# TYPE homelabme_requests_total counter\n");
+        for ((route, status_class), count) in &state.requests {
+            let _ = writeln!(
+                out,
+                "homelabme_requests_total{{route=\"{}\",status_class=\"{}\"}} {}",
+                escape_label(route),
+                status_class,
+                count
+            );
+        }
+
+        out.push_str("# HELP homelabme_request_duration_seconds HTTP request latency by route.\n");
+        out.push_str("# TYPE homelabme_request_duration_seconds histogram\n");
+        for (route, histogram) in &state.latency {
+            let route = escape_label(route);
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets.iter()) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "homelabme_request_duration_seconds_bucket{{route=\"{}\",le=\"{}\"}} {}",
+                    route, bound, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "homelabme_request_duration_seconds_bucket{{route=\"{}\",le=\"+Inf\"}} {}",
+                route, histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "homelabme_request_duration_seconds_sum{{route=\"{}\"}} {}",
+                route, histogram.sum_secs
+            );
+            let _ = writeln!(
+                out,
+                "homelabme_request_duration_seconds_count{{route=\"{}\"}} {}",
+                route, histogram.count
+            );
+        }
+        out
+    }
+}
+
+fn status_class(status: u16) -> &'static str {
+    match status {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[tokio::test]
+    async fn requests_are_counted_by_route_template() {
+        let app = TestApp::new();
+        app.get("/api/version").await;
+        app.get("/api/version").await;
+        app.get("/api/network/wifi/missing").await;
+        app.get("/no/such/page").await;
+
+        let metrics = body_text(app.get("/metrics").await).await;
+        assert!(metrics.contains("homelabme_requests_total{route=\"/api/version\",status_class=\"2xx\"} 2\n"), "{}", metrics);
+        assert!(metrics.contains("homelabme_requests_total{route=\"/api/network/wifi/:id\",status_class=\"4xx\"} 1\n"), "{}", metrics);
+        assert!(metrics.contains(&format!("homelabme_requests_total{{route=\"{}\",status_class=\"4xx\"}} 1\n", UNMATCHED_ROUTE)));
+        assert!(metrics.contains("homelabme_request_duration_seconds_count{route=\"/api/version\"} 2\n"));
+    }
+}
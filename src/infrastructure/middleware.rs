@@ -0,0 +1,794 @@
+// HTTP middleware - cross-cutting request handling applied in create_router
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+use crate::infrastructure::metrics::{RequestMetrics, UNMATCHED_ROUTE};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// Request ID stored in the request extensions for handlers that want to log it
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RequestId(pub String);
+
+// Reuse the caller's X-Request-Id (or generate one), log the request under it and echo it back
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= 128)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    request.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+
+    let started = std::time::Instant::now();
+    let mut response = next.run(request).instrument(span.clone()).await;
+
+    span.in_scope(|| {
+        tracing::info!(
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_millis() as u64,
+            "request completed"
+        );
+    });
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+// Cache-Control for a request path, or None to leave caching to the client's defaults.
+// Live state and anything that may carry a secret must never be stored by a browser or proxy.
+fn cache_control_for(path: &str) -> Option<&'static str> {
+    match path {
+        "/api/version" => Some("public, max-age=30"),
+        // Embedded in the binary, so they only change with an upgrade
+        "/favicon.ico" => Some("public, max-age=86400"),
+        path if path.starts_with("/assets/") => Some("public, max-age=86400"),
+        "/api/network/wifi/scan" | "/api/network/current" | "/api/network/settings" | "/api/logs" => Some("no-store"),
+        // Single WiFi configs, which can reveal the password
+        path if path.starts_with("/api/network/wifi/") => Some("no-store"),
+        _ => None,
+    }
+}
+
+// Count every request under its route template and status class, and time it
+pub async fn metrics_middleware(
+    State(metrics): State<Arc<RequestMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics.record(&route, response.status().as_u16(), started.elapsed());
+    response
+}
+
+// Add Cache-Control to responses that do not set their own
+pub async fn cache_control_middleware(request: Request, next: Next) -> Response {
+    let cache_control = cache_control_for(request.uri().path());
+    let mut response = next.run(request).await;
+    if let Some(cache_control) = cache_control {
+        response
+            .headers_mut()
+            .entry(header::CACHE_CONTROL)
+            .or_insert(HeaderValue::from_static(cache_control));
+    }
+    response
+}
+
+const PROBLEM_JSON: &str = "application/problem+json";
+
+// RFC 7807 problem details of an API error. `ApiError` attaches one to every error response it
+// builds; the body stays `{"error": ...}` unless the client asked for problem+json.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    problem_type: String,
+    title: String,
+    status: u16,
+    detail: String,
+}
+
+impl ProblemDetails {
+    pub fn new(status: StatusCode, kind: &str, detail: String) -> Self {
+        Self {
+            problem_type: format!("urn:homelabme:problem:{}", kind),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail,
+        }
+    }
+}
+
+// Swap the `{"error": ...}` body of an API error for problem details when the Accept header
+// lists application/problem+json. Errors without details attached (plain status codes) pass through.
+pub async fn problem_json_middleware(request: Request, next: Next) -> Response {
+    let wants_problem = request
+        .headers()
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media| media.split(';').next().is_some_and(|media| media.trim() == PROBLEM_JSON));
+    let mut response = next.run(request).await;
+    if !wants_problem {
+        return response;
+    }
+    let Some(problem) = response.extensions_mut().remove::<ProblemDetails>() else {
+        return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+    let body = match serde_json::to_vec(&problem) {
+        Ok(problem_body) => problem_body,
+        Err(_) => return Response::from_parts(parts, body),
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    Response::from_parts(parts, Body::from(body))
+}
+
+// Re-serialize JSON responses with indentation when the query has ?pretty=true (or just ?pretty),
+// for reading the API with curl. Anything that is not JSON, e.g. the SSE stream, passes through.
+pub async fn pretty_json_middleware(request: Request, next: Next) -> Response {
+    let pretty = request.uri().query().is_some_and(|query| {
+        query
+            .split('&')
+            .any(|pair| matches!(pair, "pretty" | "pretty=true" | "pretty=1"))
+    });
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json") || value.starts_with(PROBLEM_JSON));
+    if !pretty || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": format!("Failed to read response body: {}", e) })),
+            )
+                .into_response()
+        }
+    };
+    let pretty_body = serde_json::from_slice::<serde_json::Value>(&body)
+        .and_then(|value| serde_json::to_vec_pretty(&value));
+    match pretty_body {
+        Ok(pretty_body) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(pretty_body))
+        }
+        Err(_) => Response::from_parts(parts, Body::from(body)),
+    }
+}
+
+// An IPv4 or IPv6 network in CIDR notation, e.g. 192.168.1.0/24; a bare address is a single host
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl std::str::FromStr for Cidr {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_len) = match value.split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (value, None),
+        };
+        let network: IpAddr = address
+            .parse()
+            .map_err(|_| format!("Invalid CIDR {}: bad address", value))?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix_len| *prefix_len <= max_len)
+                .ok_or_else(|| format!("Invalid CIDR {}: bad prefix length", value))?,
+            None => max_len,
+        };
+        Ok(Self { network, prefix_len })
+    }
+}
+
+impl Cidr {
+    pub fn contains(&self, address: IpAddr) -> bool {
+        // Clients on a dual-stack socket show up as IPv4-mapped IPv6 addresses
+        match (self.network, address.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_len as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_len as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(address) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+// Source networks allowed to use the server; an empty list allows every client
+#[derive(Debug, Clone, Default)]
+pub struct AdminAllowlist {
+    cidrs: Vec<Cidr>,
+}
+
+impl AdminAllowlist {
+    pub fn parse(cidrs: &[String]) -> Result<Self, String> {
+        let cidrs = cidrs
+            .iter()
+            .map(|cidr| cidr.parse())
+            .collect::<Result<Vec<Cidr>, String>>()?;
+        Ok(Self { cidrs })
+    }
+
+    pub fn allows(&self, address: IpAddr) -> bool {
+        self.cidrs.is_empty() || self.cidrs.iter().any(|cidr| cidr.contains(address))
+    }
+}
+
+// Reject clients outside the admin allowlist. The address comes from the TCP connection,
+// never from X-Forwarded-For, so it cannot be spoofed by the client.
+// Unix socket connections carry no address; access to them is governed by the socket's file permissions.
+pub async fn admin_allowlist_middleware(
+    State(allowlist): State<Arc<AdminAllowlist>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match peer {
+        Some(ConnectInfo(peer)) if !allowlist.allows(peer.ip()) => {
+            tracing::warn!(client = %peer.ip(), "rejected client outside admin allowlist");
+            StatusCode::FORBIDDEN.into_response()
+        }
+        _ => next.run(request).await,
+    }
+}
+
+// Bearer token for the admin-only endpoints (server logs, revealed secrets). With no token
+// configured nobody is an admin, so those endpoints stay closed.
+pub struct AdminToken {
+    token: Option<String>,
+}
+
+impl AdminToken {
+    pub fn new(token: Option<String>) -> Self {
+        Self { token }
+    }
+
+    // Whether the request carries `Authorization: Bearer <token>`
+    pub fn verify(&self, headers: &HeaderMap) -> bool {
+        let Some(token) = &self.token else {
+            return false;
+        };
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|supplied| constant_time_eq(supplied.trim().as_bytes(), token.as_bytes()))
+    }
+}
+
+// Compares every byte regardless of where the first difference is, so response timing does not
+// reveal how much of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+// Only let requests with the admin token through; 401 otherwise
+pub async fn require_admin_token_middleware(
+    State(admin_token): State<Arc<AdminToken>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if admin_token.verify(request.headers()) {
+        return next.run(request).await;
+    }
+    let message = "An admin token is required (Authorization: Bearer <admin_token>)";
+    let mut response = (StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": message }))).into_response();
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+        .extensions_mut()
+        .insert(ProblemDetails::new(StatusCode::UNAUTHORIZED, "unauthorized", message.to_string()));
+    response
+}
+
+// Requests each client address may make per fixed one-minute window. In memory only; a zero
+// limit disables it.
+pub struct RateLimiter {
+    per_minute: u32,
+    windows: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+// Past this many tracked clients, finished windows are dropped before adding another
+const RATE_LIMIT_MAX_CLIENTS: usize = 4096;
+
+impl RateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Count a request from `client`; `Err` carries how long until its window resets
+    pub fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        if self.per_minute == 0 {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if windows.len() >= RATE_LIMIT_MAX_CLIENTS && !windows.contains_key(&client) {
+            windows.retain(|_, (started, _)| now.duration_since(*started) < RATE_LIMIT_WINDOW);
+        }
+        let (started, count) = windows.entry(client).or_insert((now, 0));
+        if now.duration_since(*started) >= RATE_LIMIT_WINDOW {
+            *started = now;
+            *count = 0;
+        }
+        if *count >= self.per_minute {
+            return Err(RATE_LIMIT_WINDOW.saturating_sub(now.duration_since(*started)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+// Answer 429 with Retry-After once a client is over the rate limit. Like the allowlist, this keys
+// on the TCP peer address; Unix socket connections are not limited.
+pub async fn rate_limit_middleware(
+    State(limiter): State<Arc<RateLimiter>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(ConnectInfo(peer)) = peer else {
+        return next.run(request).await;
+    };
+    let Err(retry_after) = limiter.check(peer.ip()) else {
+        return next.run(request).await;
+    };
+    tracing::warn!(client = %peer.ip(), "rate limit exceeded");
+    let message = "Too many requests; try again later";
+    let mut response =
+        (StatusCode::TOO_MANY_REQUESTS, Json(serde_json::json!({ "error": message }))).into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after.as_secs().max(1)));
+    response
+        .extensions_mut()
+        .insert(ProblemDetails::new(StatusCode::TOO_MANY_REQUESTS, "rate-limited", message.to_string()));
+    response
+}
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+// Request bodies are buffered to fingerprint them; config payloads are tiny
+const IDEMPOTENT_BODY_LIMIT: usize = 1024 * 1024;
+// Larger responses are passed through without being stored for replay
+const IDEMPOTENT_RESPONSE_LIMIT: usize = 1024 * 1024;
+
+enum IdempotencyEntry {
+    InFlight {
+        fingerprint: u64,
+        started: Instant,
+    },
+    Completed {
+        fingerprint: u64,
+        stored: Instant,
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Bytes,
+    },
+}
+
+impl IdempotencyEntry {
+    fn fingerprint(&self) -> u64 {
+        match self {
+            IdempotencyEntry::InFlight { fingerprint, .. } | IdempotencyEntry::Completed { fingerprint, .. } => {
+                *fingerprint
+            }
+        }
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        match self {
+            IdempotencyEntry::InFlight { started, .. } => started.elapsed() > ttl,
+            IdempotencyEntry::Completed { stored, .. } => stored.elapsed() > ttl,
+        }
+    }
+}
+
+// Successful POST responses remembered by Idempotency-Key, so a client retrying after a lost
+// response gets the original result instead of creating a duplicate. In memory only: a restart
+// forgets every key. A zero TTL disables it.
+pub struct IdempotencyCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, IdempotencyEntry>>,
+}
+
+impl IdempotencyCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+// Removes the InFlight entry unless disarmed, so a request whose handler panics or whose client
+// disconnects mid-request does not block its key until the TTL runs out
+struct InFlightGuard {
+    cache: Arc<IdempotencyCache>,
+    key: String,
+    armed: bool,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.armed {
+            let mut entries = self.cache.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if matches!(entries.get(&self.key), Some(IdempotencyEntry::InFlight { .. })) {
+                entries.remove(&self.key);
+            }
+        }
+    }
+}
+
+fn idempotency_error(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+// Replay the stored response for a repeated Idempotency-Key on a POST. Keys are scoped to the
+// request path; reusing one with a different body is a client bug and gets 422, and a repeat
+// that arrives while the first request is still running gets 409. Failed, streaming and
+// oversized responses are not stored, so the client can retry them with the same key.
+pub async fn idempotency_middleware(
+    State(cache): State<Arc<IdempotencyCache>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST || cache.ttl.is_zero() {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= 255 => key.to_string(),
+        _ => {
+            return idempotency_error(
+                StatusCode::BAD_REQUEST,
+                "Idempotency-Key must be 1-255 visible ASCII characters",
+            )
+        }
+    };
+    let cache_key = format!("{} {}", request.uri().path(), key);
+
+    let (parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, IDEMPOTENT_BODY_LIMIT).await {
+        Ok(body) => body,
+        Err(_) => return idempotency_error(StatusCode::PAYLOAD_TOO_LARGE, "Request body is too large"),
+    };
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    body.hash(&mut hasher);
+    let fingerprint = hasher.finish();
+
+    {
+        let mut entries = cache.entries.lock().unwrap();
+        entries.retain(|_, entry| !entry.is_expired(cache.ttl));
+        match entries.get(&cache_key) {
+            Some(entry) if entry.fingerprint() != fingerprint => {
+                return idempotency_error(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "Idempotency-Key was already used with a different request body",
+                );
+            }
+            Some(IdempotencyEntry::InFlight { .. }) => {
+                return idempotency_error(
+                    StatusCode::CONFLICT,
+                    "A request with this Idempotency-Key is still in progress",
+                );
+            }
+            Some(IdempotencyEntry::Completed { status, headers, body, .. }) => {
+                let mut response = Response::new(Body::from(body.clone()));
+                *response.status_mut() = *status;
+                *response.headers_mut() = headers.clone();
+                response
+                    .headers_mut()
+                    .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+                return response;
+            }
+            None => {
+                entries.insert(
+                    cache_key.clone(),
+                    IdempotencyEntry::InFlight { fingerprint, started: Instant::now() },
+                );
+            }
+        }
+    }
+    let mut guard = InFlightGuard { cache: cache.clone(), key: cache_key.clone(), armed: true };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    // Streaming or oversized responses can't be replayed; the guard frees the key for them
+    let fits = HttpBody::size_hint(response.body())
+        .upper()
+        .is_some_and(|upper| upper <= IDEMPOTENT_RESPONSE_LIMIT as u64);
+    if !fits {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, IDEMPOTENT_RESPONSE_LIMIT).await {
+        Ok(body) => body,
+        Err(e) => {
+            return idempotency_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("Failed to read response body: {}", e),
+            );
+        }
+    };
+
+    guard.armed = false;
+    let mut entries = cache.entries.lock().unwrap();
+    if parts.status.is_success() {
+        entries.insert(
+            cache_key,
+            IdempotencyEntry::Completed {
+                fingerprint,
+                stored: Instant::now(),
+                status: parts.status,
+                headers: parts.headers.clone(),
+                body: body.clone(),
+            },
+        );
+    } else {
+        entries.remove(&cache_key);
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::network_repositories::WifiConfigRepository;
+    use crate::domain::network_services::NetworkConfigService;
+    use crate::test_support::*;
+
+    fn ip(address: &str) -> IpAddr {
+        address.parse().unwrap()
+    }
+
+    fn from_peer(uri: &str, peer: &str) -> Request {
+        let mut request = Request::get(uri).body(Body::empty()).unwrap();
+        request.extensions_mut().insert(ConnectInfo(SocketAddr::new(ip(peer), 50000)));
+        request
+    }
+
+    #[test]
+    fn cidr_contains_addresses_in_its_network() {
+        let cidr: Cidr = "192.168.10.0/24".parse().unwrap();
+        assert!(cidr.contains(ip("192.168.10.7")));
+        assert!(!cidr.contains(ip("192.168.11.7")));
+        assert!(cidr.contains(ip("::ffff:192.168.10.7")));
+        assert!(!cidr.contains(ip("fd00::1")));
+
+        let cidr: Cidr = "fd00::/8".parse().unwrap();
+        assert!(cidr.contains(ip("fd12:3456::1")));
+        assert!(!cidr.contains(ip("fe80::1")));
+
+        let host: Cidr = "10.0.0.5".parse().unwrap();
+        assert!(host.contains(ip("10.0.0.5")));
+        assert!(!host.contains(ip("10.0.0.6")));
+        assert!("0.0.0.0/0".parse::<Cidr>().unwrap().contains(ip("8.8.8.8")));
+    }
+
+    #[test]
+    fn malformed_cidrs_are_rejected() {
+        for cidr in ["192.168.10.0/33", "fd00::/129", "192.168.10/24", "lan", "10.0.0.0/x"] {
+            assert!(cidr.parse::<Cidr>().is_err(), "{}", cidr);
+        }
+        assert!(AdminAllowlist::parse(&["10.0.0.0/8".to_string(), "bogus".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn allowlist_admits_listed_clients_only() {
+        let mut app = TestApp::new();
+        app.state.admin_allowlist = Arc::new(AdminAllowlist::parse(&["192.168.10.0/24".to_string()]).unwrap());
+
+        assert_eq!(app.send(from_peer("/api/version", "192.168.10.20")).await.status(), StatusCode::OK);
+        assert_eq!(app.send(from_peer("/api/version", "::ffff:192.168.10.20")).await.status(), StatusCode::OK);
+        assert_eq!(app.send(from_peer("/api/version", "192.168.20.20")).await.status(), StatusCode::FORBIDDEN);
+        assert_eq!(app.send(from_peer("/api/version", "::ffff:192.168.20.20")).await.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn empty_allowlist_admits_everyone() {
+        let app = TestApp::new();
+        assert_eq!(app.send(from_peer("/api/version", "203.0.113.9")).await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn responses_carry_a_generated_request_id() {
+        let app = TestApp::new();
+        let response = app.get("/api/version").await;
+        let request_id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(request_id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn a_supplied_request_id_is_echoed_unchanged() {
+        let app = TestApp::new();
+        let request = Request::get("/api/version")
+            .header(REQUEST_ID_HEADER, "trace-42/abc")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.send(request).await;
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "trace-42/abc");
+
+        // Too long to be worth keeping, so a fresh one replaces it
+        let request = Request::get("/api/version")
+            .header(REQUEST_ID_HEADER, "x".repeat(200))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.send(request).await;
+        assert_ne!(response.headers()[REQUEST_ID_HEADER], "x".repeat(200).as_str());
+    }
+
+    fn idempotent_post(uri: &str, key: &str, body: serde_json::Value) -> Request {
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(IDEMPOTENCY_KEY_HEADER, key)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn a_repeated_post_replays_the_first_response() {
+        let app = TestApp::new();
+        let body = serde_json::json!({ "ssid": "Home", "password": "correct horse", "security_type": "WPA2" });
+
+        let first = app.send(idempotent_post("/api/network/wifi", "create-home", body.clone())).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        let first = body_json(first).await;
+
+        let second = app.send(idempotent_post("/api/network/wifi", "create-home", body)).await;
+        assert_eq!(second.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(body_json(second).await, first);
+        assert_eq!(app.network.wifi_repository.find_all().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn an_abandoned_request_frees_its_key() {
+        let app = TestApp::new();
+        let config = app.service.create_wifi_config(wifi("Home")).await.unwrap();
+        let uri = format!("/api/network/wifi/{}/activate", config.id);
+        app.network.applier.set_delay(Duration::from_secs(5));
+
+        // The client gives up while the apply is still running
+        let abandoned = app.send(idempotent_post(&uri, "activate-home", serde_json::json!({})));
+        assert!(tokio::time::timeout(Duration::from_millis(50), abandoned).await.is_err());
+
+        app.network.applier.set_delay(Duration::ZERO);
+        let retry = app.send(idempotent_post(&uri, "activate-home", serde_json::json!({}))).await;
+        assert_eq!(retry.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn scans_are_never_cached_and_the_version_briefly() {
+        let app = TestApp::new();
+        let scan = app.get("/api/network/wifi/scan").await;
+        assert_eq!(scan.status(), StatusCode::OK);
+        assert_eq!(scan.headers()[header::CACHE_CONTROL], "no-store");
+        assert_eq!(app.get("/api/version").await.headers()[header::CACHE_CONTROL], "public, max-age=30");
+        assert!(app.get("/api/network/interfaces").await.headers().get(header::CACHE_CONTROL).is_none());
+    }
+
+    #[tokio::test]
+    async fn pretty_query_indents_json_responses() {
+        let app = TestApp::new();
+        let compact = body_text(app.get("/api/version").await).await;
+        assert!(!compact.contains('\n'), "{}", compact);
+
+        let pretty = body_text(app.get("/api/version?pretty").await).await;
+        assert!(pretty.starts_with("{\n  \""), "{}", pretty);
+        let parsed: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(parsed, serde_json::from_str::<serde_json::Value>(&compact).unwrap());
+    }
+
+    #[test]
+    fn rate_limiter_counts_each_client_separately() {
+        let limiter = RateLimiter::new(2);
+        assert!(limiter.check(ip("192.168.1.10")).is_ok());
+        assert!(limiter.check(ip("192.168.1.10")).is_ok());
+        let retry_after = limiter.check(ip("192.168.1.10")).unwrap_err();
+        assert!(retry_after > Duration::ZERO && retry_after <= Duration::from_secs(60));
+        assert!(limiter.check(ip("192.168.1.11")).is_ok());
+
+        let unlimited = RateLimiter::new(0);
+        assert!((0..100).all(|_| unlimited.check(ip("192.168.1.10")).is_ok()));
+    }
+
+    #[tokio::test]
+    async fn clients_over_the_rate_limit_get_429() {
+        let mut app = TestApp::new();
+        app.state.rate_limiter = Arc::new(RateLimiter::new(1));
+
+        assert_eq!(app.send(from_peer("/api/version", "192.168.1.10")).await.status(), StatusCode::OK);
+        let response = app.send(from_peer("/api/version", "192.168.1.10")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers()[header::RETRY_AFTER].to_str().unwrap().parse::<u64>().unwrap() >= 1);
+        assert_eq!(app.send(from_peer("/api/version", "192.168.1.11")).await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn cors_headers_are_sent_to_listed_origins_only() {
+        let mut app = TestApp::new();
+        app.state.cors_origins = vec![HeaderValue::from_static("https://dashboard.lan")];
+
+        let preflight = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/api/network/wifi")
+            .header(header::ORIGIN, "https://dashboard.lan")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type,idempotency-key")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.send(preflight).await;
+        assert!(response.status().is_success());
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://dashboard.lan");
+
+        let request = |origin: &str| {
+            Request::get("/api/version").header(header::ORIGIN, origin).body(Body::empty()).unwrap()
+        };
+        let response = app.send(request("https://dashboard.lan")).await;
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://dashboard.lan");
+        let response = app.send(request("https://evil.example")).await;
+        assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        // Without configured origins nothing is added
+        let app = TestApp::new();
+        let response = app.send(request("https://dashboard.lan")).await;
+        assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+}
@@ -1,6 +1,19 @@
 // Infrastructure layer - handles external concerns
 // Database, web framework, external services, etc.
 
+pub mod config;
 pub mod repositories;
+pub mod sysfs;
 pub mod network_repositories;
+pub mod network_appliers;
+pub mod snapshot;
+pub mod event_sinks;
+pub mod system_repositories;
+pub mod simulation;
+pub mod log_buffer;
+pub mod metrics;
+pub mod middleware;
+pub mod tls;
+#[cfg(unix)]
+pub mod unix_socket;
 pub mod web;
\ No newline at end of file
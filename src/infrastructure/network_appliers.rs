@@ -0,0 +1,586 @@
+// Network applier implementations
+// Without the `apply-system` feature every change is recorded but never pushed to the system
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use crate::domain::network_appliers::*;
+use crate::domain::network_entities::*;
+
+// No-op applier used when system apply is compiled out
+#[cfg_attr(feature = "apply-system", allow(dead_code))]
+pub struct DisabledApplier;
+
+#[cfg_attr(feature = "apply-system", allow(dead_code))]
+impl DisabledApplier {
+    fn outcome() -> ApplyOutcome {
+        ApplyOutcome {
+            applied: false,
+            already_active: false,
+            detail: Some("system apply disabled".to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl StaticIpApplier for DisabledApplier {
+    async fn apply(&self, _config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome())
+    }
+
+    async fn remove(&self, _config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome())
+    }
+}
+
+#[async_trait]
+impl WifiApplier for DisabledApplier {
+    async fn apply(&self, _config: &WifiConfig) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome())
+    }
+
+    async fn disconnect(&self, _config: &WifiConfig) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome())
+    }
+}
+
+#[async_trait]
+impl ServiceRestarter for DisabledApplier {
+    async fn restart(&self, _unit: &str) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome())
+    }
+}
+
+// Runs each call of the wrapped applier on its own task and waits for it. Dropping the caller's
+// future (a client hanging up mid-request, a timed-out handler) then only stops the waiting: the
+// apply itself still runs to the end, so a restart is never cut off halfway through writing
+// files or reloading services. Only the applier call is protected: the service's bookkeeping after
+// it (e.g. marking the config enabled) still belongs to the caller's future.
+pub struct DetachedApplier<A: ?Sized> {
+    inner: Arc<A>,
+}
+
+impl<A: ?Sized + Send + Sync + 'static> DetachedApplier<A> {
+    pub fn new(inner: Arc<A>) -> Self {
+        Self { inner }
+    }
+
+    async fn run<F, Fut>(&self, action: String, call: F) -> Result<ApplyOutcome, String>
+    where
+        F: FnOnce(Arc<A>) -> Fut,
+        Fut: std::future::Future<Output = Result<ApplyOutcome, String>> + Send + 'static,
+    {
+        tokio::spawn(call(self.inner.clone()))
+            .await
+            .map_err(|e| format!("{} did not finish: {}", action, e))?
+    }
+}
+
+#[async_trait]
+impl StaticIpApplier for DetachedApplier<dyn StaticIpApplier> {
+    async fn apply(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+        let config = config.clone();
+        let action = format!("Applying static IP config {}", config.id);
+        self.run(action, |inner| async move { inner.apply(&config).await }).await
+    }
+
+    async fn remove(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+        let config = config.clone();
+        let action = format!("Removing static IP config {}", config.id);
+        self.run(action, |inner| async move { inner.remove(&config).await }).await
+    }
+}
+
+#[async_trait]
+impl WifiApplier for DetachedApplier<dyn WifiApplier> {
+    async fn apply(&self, config: &WifiConfig) -> Result<ApplyOutcome, String> {
+        let config = config.clone();
+        let action = format!("Applying WiFi config {}", config.id);
+        self.run(action, |inner| async move { inner.apply(&config).await }).await
+    }
+
+    async fn disconnect(&self, config: &WifiConfig) -> Result<ApplyOutcome, String> {
+        let config = config.clone();
+        let action = format!("Disconnecting WiFi config {}", config.id);
+        self.run(action, |inner| async move { inner.disconnect(&config).await }).await
+    }
+}
+
+#[async_trait]
+impl ServiceRestarter for DetachedApplier<dyn ServiceRestarter> {
+    async fn restart(&self, unit: &str) -> Result<ApplyOutcome, String> {
+        let unit = unit.to_string();
+        let action = format!("Restarting {}", unit);
+        self.run(action, |inner| async move { inner.restart(&unit).await }).await
+    }
+}
+
+// Keeps a timestamped copy of every generated system config file for debugging.
+// The files hold WiFi passwords, so only their paths are ever logged.
+#[cfg_attr(not(feature = "apply-system"), allow(dead_code))]
+pub struct GeneratedFileArchive {
+    dir: PathBuf,
+}
+
+#[cfg_attr(not(feature = "apply-system"), allow(dead_code))]
+impl GeneratedFileArchive {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    // Best effort - a failed copy is logged and never fails the apply itself
+    pub async fn record(&self, file_name: &str, contents: &str) {
+        let path = self.dir.join(format!(
+            "{}-{}",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+            file_name
+        ));
+        match self.write(&path, contents).await {
+            Ok(()) => tracing::info!("Saved copy of generated {} to {}", file_name, path.display()),
+            Err(e) => tracing::warn!("Failed to save copy of generated {} to {}: {}", file_name, path.display(), e),
+        }
+    }
+
+    async fn write(&self, path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(path).await?;
+        file.write_all(contents.as_bytes()).await?;
+        file.flush().await
+    }
+}
+
+// Replace `path` with `contents` by writing a temporary file next to it and renaming it over the
+// old one, so readers never see a half-written file and a failed write keeps the previous one.
+// The file is created with `mode` from the start rather than chmod-ed afterwards.
+#[cfg_attr(not(feature = "apply-system"), allow(dead_code))]
+pub async fn write_file_atomically(path: &std::path::Path, contents: &str, mode: u32) -> Result<(), String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(mode);
+    #[cfg(not(unix))]
+    let _ = mode;
+    let mut file = options
+        .open(&temp_path)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    file.write_all(contents.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    file.sync_all()
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    tokio::fs::rename(&temp_path, path)
+        .await
+        .map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+// Render a systemd-networkd .network file for a static IP config
+#[cfg_attr(not(feature = "apply-system"), allow(dead_code))]
+pub fn render_networkd_file(config: &StaticIpConfig) -> String {
+    let mut file = format!(
+        "[Match]\nName={}\n\n[Network]\nAddress={}/{}\nGateway={}\nDNS={}\n",
+        config.interface_name, config.ip_address, config.prefix_len, config.gateway, config.dns_primary
+    );
+    if let Some(dns_secondary) = &config.dns_secondary {
+        file.push_str(&format!("DNS={}\n", dns_secondary));
+    }
+    if !config.search_domains.is_empty() {
+        file.push_str(&format!("Domains={}\n", config.search_domains.join(" ")));
+    }
+    file
+}
+
+fn hex_encode(value: &str) -> String {
+    value.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Render a wpa_supplicant network block for a WiFi config. The SSID is written as hex so no byte of
+// it can end the value early; keys are quoted passphrases unless they are raw hex keys, which
+// wpa_supplicant expects unquoted. Validation keeps control characters out of the passphrases.
+#[cfg_attr(not(feature = "apply-system"), allow(dead_code))]
+pub fn render_wpa_supplicant_block(config: &WifiConfig) -> String {
+    let is_hex_key = |len: &[usize]| {
+        len.contains(&config.password.len()) && config.password.chars().all(|c| c.is_ascii_hexdigit())
+    };
+    let mut block = format!("network={{\n    ssid={}\n", hex_encode(&config.ssid));
+    if config.hidden {
+        // Hidden networks don't broadcast their SSID, so probe for it explicitly
+        block.push_str("    scan_ssid=1\n");
+    }
+    if !config.auto_connect {
+        // Never joined automatically; the applier enables it explicitly when asked to connect
+        block.push_str("    disabled=1\n");
+    }
+    if let Some(band) = config.preferred_band {
+        let frequencies: Vec<String> = band.channel_frequencies().iter().map(|freq| freq.to_string()).collect();
+        block.push_str(&format!("    freq_list={}\n", frequencies.join(" ")));
+    }
+    match config.security_type {
+        WifiSecurityType::Open => block.push_str("    key_mgmt=NONE\n"),
+        WifiSecurityType::WEP => {
+            block.push_str("    key_mgmt=NONE\n");
+            if is_hex_key(&[10, 26]) {
+                block.push_str(&format!("    wep_key0={}\n", config.password));
+            } else {
+                block.push_str(&format!("    wep_key0=\"{}\"\n", config.password));
+            }
+        }
+        WifiSecurityType::WPA | WifiSecurityType::WPA2 => {
+            block.push_str("    key_mgmt=WPA-PSK\n");
+            if is_hex_key(&[64]) {
+                block.push_str(&format!("    psk={}\n", config.password));
+            } else {
+                block.push_str(&format!("    psk=\"{}\"\n", config.password));
+            }
+        }
+        WifiSecurityType::WPA3 => {
+            block.push_str("    key_mgmt=SAE\n");
+            block.push_str(&format!("    sae_password=\"{}\"\n", config.password));
+            block.push_str("    ieee80211w=2\n");
+        }
+    }
+    block.push_str("}\n");
+    block
+}
+
+#[cfg(feature = "apply-system")]
+pub use system::*;
+
+#[cfg(feature = "apply-system")]
+mod system {
+    use super::*;
+    use std::sync::Arc;
+
+    async fn run_command(program: &str, args: &[&str]) -> Result<(), String> {
+        let output = tokio::process::Command::new(program)
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} {} failed: {}",
+                program,
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    // Writes one systemd-networkd .network file per interface and reloads networkd
+    pub struct SystemdNetworkdApplier {
+        network_dir: PathBuf,
+        archive: Option<Arc<GeneratedFileArchive>>,
+    }
+
+    impl SystemdNetworkdApplier {
+        pub fn new(network_dir: impl Into<PathBuf>) -> Self {
+            Self {
+                network_dir: network_dir.into(),
+                archive: None,
+            }
+        }
+
+        pub fn with_archive(mut self, archive: Arc<GeneratedFileArchive>) -> Self {
+            self.archive = Some(archive);
+            self
+        }
+
+        fn file_path(&self, interface_name: &str) -> PathBuf {
+            self.network_dir.join(format!("10-homelabme-{}.network", interface_name))
+        }
+    }
+
+    #[async_trait]
+    impl StaticIpApplier for SystemdNetworkdApplier {
+        async fn apply(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+            let path = self.file_path(&config.interface_name);
+            let contents = render_networkd_file(config);
+            // No secrets in here, and systemd-networkd reads it as the unprivileged systemd-network user
+            write_file_atomically(&path, &contents, 0o644).await?;
+            if let Some(archive) = &self.archive
+                && let Some(file_name) = path.file_name()
+            {
+                archive.record(&file_name.to_string_lossy(), &contents).await;
+            }
+
+            run_command("networkctl", &["reload"]).await?;
+            run_command("networkctl", &["reconfigure", &config.interface_name]).await?;
+            Ok(ApplyOutcome {
+                applied: true,
+                already_active: false,
+                detail: Some(format!("wrote {}", path.display())),
+            })
+        }
+
+        async fn remove(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+            let path = self.file_path(&config.interface_name);
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to remove {}: {}", path.display(), e)),
+            }
+
+            run_command("networkctl", &["reload"]).await?;
+            run_command("networkctl", &["reconfigure", &config.interface_name]).await?;
+            Ok(ApplyOutcome {
+                applied: true,
+                already_active: false,
+                detail: Some(format!("removed {}", path.display())),
+            })
+        }
+    }
+
+    // Restarts systemd units with systemctl
+    pub struct SystemctlRestarter;
+
+    #[async_trait]
+    impl ServiceRestarter for SystemctlRestarter {
+        async fn restart(&self, unit: &str) -> Result<ApplyOutcome, String> {
+            run_command("systemctl", &["restart", unit]).await?;
+            Ok(ApplyOutcome {
+                applied: true,
+                already_active: false,
+                detail: Some(format!("restarted {}", unit)),
+            })
+        }
+    }
+
+    const WPA_SUPPLICANT_HEADER: &str = "ctrl_interface=DIR=/var/run/wpa_supplicant GROUP=netdev\nupdate_config=1\n";
+
+    // Writes the wpa_supplicant config for the wireless interface and asks wpa_supplicant to reload it
+    pub struct WpaSupplicantApplier {
+        interface_name: String,
+        config_dir: PathBuf,
+        archive: Option<Arc<GeneratedFileArchive>>,
+    }
+
+    impl WpaSupplicantApplier {
+        pub fn new(interface_name: impl Into<String>, config_dir: impl Into<PathBuf>) -> Self {
+            Self {
+                interface_name: interface_name.into(),
+                config_dir: config_dir.into(),
+                archive: None,
+            }
+        }
+
+        pub fn with_archive(mut self, archive: Arc<GeneratedFileArchive>) -> Self {
+            self.archive = Some(archive);
+            self
+        }
+
+        fn config_path(&self) -> PathBuf {
+            self.config_dir
+                .join(format!("wpa_supplicant-{}.conf", self.interface_name))
+        }
+    }
+
+    #[async_trait]
+    impl WifiApplier for WpaSupplicantApplier {
+        async fn apply(&self, config: &WifiConfig) -> Result<ApplyOutcome, String> {
+            let path = self.config_path();
+            let contents = format!(
+                "{}\n{}",
+                WPA_SUPPLICANT_HEADER,
+                render_wpa_supplicant_block(config)
+            );
+            // Holds the key, so only root may read it
+            write_file_atomically(&path, &contents, 0o600).await?;
+            if let Some(archive) = &self.archive
+                && let Some(file_name) = path.file_name()
+            {
+                archive.record(&file_name.to_string_lossy(), &contents).await;
+            }
+
+            run_command("wpa_cli", &["-i", &self.interface_name, "reconfigure"]).await?;
+            if !config.auto_connect {
+                // The only network block is id 0
+                run_command("wpa_cli", &["-i", &self.interface_name, "enable_network", "0"]).await?;
+            }
+            Ok(ApplyOutcome {
+                applied: true,
+                already_active: false,
+                detail: Some(format!("wrote {}", path.display())),
+            })
+        }
+
+        // Rewrite the config without any network block so wpa_supplicant drops the association
+        async fn disconnect(&self, config: &WifiConfig) -> Result<ApplyOutcome, String> {
+            let path = self.config_path();
+            write_file_atomically(&path, WPA_SUPPLICANT_HEADER, 0o600).await?;
+
+            run_command("wpa_cli", &["-i", &self.interface_name, "reconfigure"]).await?;
+            Ok(ApplyOutcome {
+                applied: true,
+                already_active: false,
+                detail: Some(format!("disconnected from {}", config.ssid)),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn wifi_config(ssid: &str, password: &str, security_type: WifiSecurityType) -> WifiConfig {
+        WifiConfig::new(ssid.to_string(), password.to_string(), security_type, false)
+    }
+
+    fn static_ip_config(search_domains: &[&str]) -> StaticIpConfig {
+        let search_domains = search_domains.iter().map(|domain| domain.to_string()).collect();
+        StaticIpConfig::new("eth0".to_string(), "192.168.1.60", "255.255.255.0", "192.168.1.1", "1.1.1.1", None, search_domains)
+            .unwrap()
+    }
+
+    #[test]
+    fn search_domains_become_one_domains_line() {
+        let file = render_networkd_file(&static_ip_config(&["home.lan", "lab.lan"]));
+        assert!(file.contains("\n[Network]\nAddress=192.168.1.60/24\n"));
+        assert!(file.ends_with("Domains=home.lan lab.lan\n"), "{}", file);
+
+        assert!(!render_networkd_file(&static_ip_config(&[])).contains("Domains="));
+    }
+
+    #[tokio::test]
+    async fn archive_keeps_a_private_copy_of_the_generated_file() {
+        let dir = temp_dir().join("generated");
+        let archive = GeneratedFileArchive::new(&dir);
+        archive.record("10-homelabme-eth0.network", &render_networkd_file(&static_ip_config(&[]))).await;
+
+        let entries: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+        assert_eq!(entries.len(), 1);
+        let name = entries[0].file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.ends_with("Z-10-homelabme-eth0.network"), "{}", name);
+        assert!(std::fs::read_to_string(&entries[0]).unwrap().contains("Name=eth0\n"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&entries[0]).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn hostile_ssid_cannot_add_directives() {
+        let config = wifi_config("evil\"\n}\nnetwork={\n ssid=\"x", "correct horse", WifiSecurityType::WPA2);
+        let block = render_wpa_supplicant_block(&config);
+
+        assert!(block.contains(&format!("    ssid={}\n", hex_encode(&config.ssid))));
+        assert_eq!(block.matches("network={").count(), 1);
+        assert_eq!(block.lines().filter(|line| line.trim_start().starts_with("ssid")).count(), 1);
+    }
+
+    #[test]
+    fn ssid_is_written_as_hex() {
+        let block = render_wpa_supplicant_block(&wifi_config("Home", "correct horse", WifiSecurityType::WPA2));
+        assert!(block.contains("    ssid=486f6d65\n"));
+    }
+
+    #[test]
+    fn hex_keys_are_written_unquoted() {
+        let psk = "0123456789abcdef".repeat(4);
+        let block = render_wpa_supplicant_block(&wifi_config("Home", &psk, WifiSecurityType::WPA2));
+        assert!(block.contains(&format!("    psk={}\n", psk)));
+
+        let block = render_wpa_supplicant_block(&wifi_config("Home", "0123456789", WifiSecurityType::WEP));
+        assert!(block.contains("    wep_key0=0123456789\n"));
+
+        let block = render_wpa_supplicant_block(&wifi_config("Home", "abcde", WifiSecurityType::WEP));
+        assert!(block.contains("    wep_key0=\"abcde\"\n"));
+
+        let block = render_wpa_supplicant_block(&wifi_config("Home", "correct horse", WifiSecurityType::WPA2));
+        assert!(block.contains("    psk=\"correct horse\"\n"));
+    }
+
+    #[test]
+    fn only_hidden_networks_are_probed_for() {
+        let mut config = wifi_config("Home", "correct horse", WifiSecurityType::WPA2);
+        assert!(!render_wpa_supplicant_block(&config).contains("scan_ssid"));
+
+        config.hidden = true;
+        assert!(render_wpa_supplicant_block(&config).contains("    scan_ssid=1\n"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn atomic_write_creates_the_file_with_the_given_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temp_dir().join("wpa_supplicant-wlan0.conf");
+        std::fs::write(&path, "old").unwrap();
+        write_file_atomically(&path, "new", 0o600).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert!(!path.with_file_name("wpa_supplicant-wlan0.conf.tmp").exists());
+    }
+
+    #[tokio::test]
+    async fn disabled_applier_reports_nothing_applied() {
+        let config = wifi_config("Home", "correct horse", WifiSecurityType::WPA2);
+        let outcome = WifiApplier::apply(&DisabledApplier, &config).await.unwrap();
+        assert!(!outcome.applied);
+        assert_eq!(outcome.detail.as_deref(), Some("system apply disabled"));
+    }
+
+    #[test]
+    fn manual_only_networks_are_written_disabled() {
+        let mut config = wifi_config("Home", "correct horse", WifiSecurityType::WPA2);
+        assert!(!render_wpa_supplicant_block(&config).contains("disabled=1"));
+
+        config.auto_connect = false;
+        assert!(render_wpa_supplicant_block(&config).contains("\n    disabled=1\n"));
+    }
+
+    #[test]
+    fn preferred_band_limits_the_frequencies() {
+        let mut config = wifi_config("Home", "correct horse", WifiSecurityType::WPA2);
+        assert!(!render_wpa_supplicant_block(&config).contains("freq_list"));
+
+        config.preferred_band = Some(WifiBand::Five);
+        let block = render_wpa_supplicant_block(&config);
+        let freq_list = block.lines().find_map(|line| line.trim().strip_prefix("freq_list=")).unwrap();
+        let frequencies: Vec<&str> = freq_list.split(' ').collect();
+        assert_eq!(frequencies.first(), Some(&"5180"));
+        assert!(frequencies.contains(&"5745"));
+        assert!(frequencies.iter().all(|freq| freq.starts_with('5')));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn detached_apply_finishes_after_the_caller_gives_up() {
+        let recording = Arc::new(crate::test_support::RecordingApplier::default());
+        recording.set_delay(std::time::Duration::from_secs(10));
+        let config = StaticIpConfig::new("eth0".to_string(), "192.168.1.60", "255.255.255.0", "192.168.1.1", "1.1.1.1", None, Vec::new())
+            .unwrap();
+        let applied = format!("apply static {}", config.id);
+
+        // Called directly, dropping the caller cuts the apply off
+        let bare: Arc<dyn StaticIpApplier> = recording.clone();
+        assert!(tokio::time::timeout(std::time::Duration::from_secs(1), bare.apply(&config)).await.is_err());
+        tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+        assert!(recording.calls().is_empty());
+
+        let inner: Arc<dyn StaticIpApplier> = recording.clone();
+        let detached = DetachedApplier::new(inner);
+        assert!(tokio::time::timeout(std::time::Duration::from_secs(1), detached.apply(&config)).await.is_err());
+        assert!(recording.calls().is_empty());
+        tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+        assert_eq!(recording.calls(), vec![applied]);
+    }
+}
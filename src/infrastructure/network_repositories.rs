@@ -7,6 +7,8 @@ use tokio::sync::RwLock;
 use network_interface::{NetworkInterface as SystemNetworkInterface, NetworkInterfaceConfig, Addr};
 use crate::domain::network_entities::*;
 use crate::domain::network_repositories::*;
+use crate::infrastructure::snapshot::Snapshotable;
+use crate::infrastructure::sysfs::{detect_sysfs_reader, SysfsReader};
 
 // In-memory WiFi configuration repository
 pub struct InMemoryWifiConfigRepository {
@@ -35,11 +37,25 @@ impl WifiConfigRepository for InMemoryWifiConfigRepository {
         Ok(())
     }
 
+    async fn insert_if_absent(&self, config: &WifiConfig) -> Result<bool, String> {
+        let mut storage = self.storage.write().await;
+        if storage.contains_key(&config.id) {
+            return Ok(false);
+        }
+        storage.insert(config.id.clone(), config.clone());
+        Ok(true)
+    }
+
     async fn find_all(&self) -> Result<Vec<WifiConfig>, String> {
         let storage = self.storage.read().await;
         Ok(storage.values().cloned().collect())
     }
 
+    async fn find_by_id(&self, id: &str) -> Result<Option<WifiConfig>, String> {
+        let storage = self.storage.read().await;
+        Ok(storage.get(id).cloned())
+    }
+
     async fn find_active(&self) -> Result<Option<WifiConfig>, String> {
         let storage = self.storage.read().await;
         Ok(storage.values().find(|config| config.is_active).cloned())
@@ -47,7 +63,10 @@ impl WifiConfigRepository for InMemoryWifiConfigRepository {
 
     async fn set_active(&self, id: &str) -> Result<(), String> {
         let mut storage = self.storage.write().await;
-        
+        if !storage.contains_key(id) {
+            return Err("WiFi config not found".to_string());
+        }
+
         // Deactivate all configs first
         for config in storage.values_mut() {
             config.is_active = false;
@@ -56,10 +75,8 @@ impl WifiConfigRepository for InMemoryWifiConfigRepository {
         // Activate the specified config
         if let Some(config) = storage.get_mut(id) {
             config.is_active = true;
-            Ok(())
-        } else {
-            Err("WiFi config not found".to_string())
         }
+        Ok(())
     }
 
     async fn delete(&self, id: &str) -> Result<(), String> {
@@ -69,6 +86,19 @@ impl WifiConfigRepository for InMemoryWifiConfigRepository {
     }
 }
 
+#[async_trait]
+impl Snapshotable for InMemoryWifiConfigRepository {
+    type Item = WifiConfig;
+
+    async fn snapshot(&self) -> Vec<WifiConfig> {
+        self.storage.read().await.values().cloned().collect()
+    }
+
+    async fn restore(&self, configs: Vec<WifiConfig>) {
+        *self.storage.write().await = configs.into_iter().map(|config| (config.id.clone(), config)).collect();
+    }
+}
+
 // In-memory Static IP configuration repository
 pub struct InMemoryStaticIpConfigRepository {
     storage: Arc<RwLock<HashMap<String, StaticIpConfig>>>,
@@ -96,25 +126,41 @@ impl StaticIpConfigRepository for InMemoryStaticIpConfigRepository {
         Ok(())
     }
 
+    async fn insert_if_absent(&self, config: &StaticIpConfig) -> Result<bool, String> {
+        let mut storage = self.storage.write().await;
+        if storage.contains_key(&config.id) {
+            return Ok(false);
+        }
+        storage.insert(config.id.clone(), config.clone());
+        Ok(true)
+    }
+
     async fn find_all(&self) -> Result<Vec<StaticIpConfig>, String> {
         let storage = self.storage.read().await;
         Ok(storage.values().cloned().collect())
     }
 
-    async fn enable(&self, id: &str) -> Result<(), String> {
+    async fn find_by_id(&self, id: &str) -> Result<Option<StaticIpConfig>, String> {
+        let storage = self.storage.read().await;
+        Ok(storage.get(id).cloned())
+    }
+
+    async fn enable(&self, id: &str, at: chrono::DateTime<chrono::Utc>) -> Result<(), String> {
         let mut storage = self.storage.write().await;
         if let Some(config) = storage.get_mut(id) {
             config.is_enabled = true;
+            config.enabled_at = Some(at);
             Ok(())
         } else {
             Err("Static IP config not found".to_string())
         }
     }
 
-    async fn disable(&self, id: &str) -> Result<(), String> {
+    async fn disable(&self, id: &str, at: chrono::DateTime<chrono::Utc>) -> Result<(), String> {
         let mut storage = self.storage.write().await;
         if let Some(config) = storage.get_mut(id) {
             config.is_enabled = false;
+            config.disabled_at = Some(at);
             Ok(())
         } else {
             Err("Static IP config not found".to_string())
@@ -128,12 +174,273 @@ impl StaticIpConfigRepository for InMemoryStaticIpConfigRepository {
     }
 }
 
+#[async_trait]
+impl Snapshotable for InMemoryStaticIpConfigRepository {
+    type Item = StaticIpConfig;
+
+    async fn snapshot(&self) -> Vec<StaticIpConfig> {
+        self.storage.read().await.values().cloned().collect()
+    }
+
+    async fn restore(&self, configs: Vec<StaticIpConfig>) {
+        *self.storage.write().await = configs.into_iter().map(|config| (config.id.clone(), config)).collect();
+    }
+}
+
+// In-memory network profile repository
+pub struct InMemoryNetworkProfileRepository {
+    storage: Arc<RwLock<HashMap<String, NetworkProfile>>>,
+}
+
+impl InMemoryNetworkProfileRepository {
+    pub fn new() -> Self {
+        Self {
+            storage: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryNetworkProfileRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl NetworkProfileRepository for InMemoryNetworkProfileRepository {
+    async fn save(&self, profile: &NetworkProfile) -> Result<(), String> {
+        let mut storage = self.storage.write().await;
+        storage.insert(profile.id.clone(), profile.clone());
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Result<Vec<NetworkProfile>, String> {
+        let storage = self.storage.read().await;
+        Ok(storage.values().cloned().collect())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<NetworkProfile>, String> {
+        let storage = self.storage.read().await;
+        Ok(storage.get(id).cloned())
+    }
+}
+
+// In-memory interface administrative state repository
+pub struct InMemoryInterfaceAdminStateRepository {
+    storage: Arc<RwLock<HashMap<String, InterfaceAdminState>>>,
+}
+
+impl InMemoryInterfaceAdminStateRepository {
+    pub fn new() -> Self {
+        Self {
+            storage: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryInterfaceAdminStateRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InterfaceAdminStateRepository for InMemoryInterfaceAdminStateRepository {
+    async fn save(&self, state: &InterfaceAdminState) -> Result<(), String> {
+        let mut storage = self.storage.write().await;
+        storage.insert(state.interface_name.clone(), state.clone());
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Result<Vec<InterfaceAdminState>, String> {
+        let storage = self.storage.read().await;
+        Ok(storage.values().cloned().collect())
+    }
+}
+
+// Desired interface states kept as a JSON array in a file under the data directory, so they
+// can be re-applied after a reboot. Written through a temporary file like the settings.
+pub struct JsonFileInterfaceAdminStateRepository {
+    path: std::path::PathBuf,
+    // Serializes read-modify-write cycles
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl JsonFileInterfaceAdminStateRepository {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl InterfaceAdminStateRepository for JsonFileInterfaceAdminStateRepository {
+    async fn save(&self, state: &InterfaceAdminState) -> Result<(), String> {
+        let _guard = self.write_lock.lock().await;
+        let mut states = self.find_all().await?;
+        states.retain(|existing| existing.interface_name != state.interface_name);
+        states.push(state.clone());
+        states.sort_by(|a, b| a.interface_name.cmp(&b.interface_name));
+
+        let contents = serde_json::to_string_pretty(&states)
+            .map_err(|e| format!("Failed to serialize interface states: {}", e))?;
+        let temp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&temp_path, contents)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+        tokio::fs::rename(&temp_path, &self.path)
+            .await
+            .map_err(|e| format!("Failed to replace {}: {}", self.path.display(), e))
+    }
+
+    async fn find_all(&self) -> Result<Vec<InterfaceAdminState>, String> {
+        match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| format!("Failed to parse {}: {}", self.path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(format!("Failed to read {}: {}", self.path.display(), e)),
+        }
+    }
+}
+
+// Settings kept as a flat JSON object in a file under the data directory. Writes go to a
+// temporary file first, so a crash mid-write leaves the previous settings intact.
+pub struct JsonFileSettingsRepository {
+    path: std::path::PathBuf,
+    // Serializes read-modify-write cycles
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl JsonFileSettingsRepository {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn load(&self) -> Result<std::collections::BTreeMap<String, String>, String> {
+        match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| format!("Failed to parse {}: {}", self.path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Default::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", self.path.display(), e)),
+        }
+    }
+}
+
+#[async_trait]
+impl SettingsRepository for JsonFileSettingsRepository {
+    async fn get(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.load().await?.remove(key))
+    }
+
+    async fn set(&self, key: &str, value: Option<String>) -> Result<(), String> {
+        let _guard = self.write_lock.lock().await;
+        let mut settings = self.load().await?;
+        match value {
+            Some(value) => settings.insert(key.to_string(), value),
+            None => settings.remove(key),
+        };
+
+        let contents = serde_json::to_string_pretty(&settings)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        let temp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&temp_path, contents)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+        tokio::fs::rename(&temp_path, &self.path)
+            .await
+            .map_err(|e| format!("Failed to replace {}: {}", self.path.display(), e))
+    }
+}
+
+// Event history kept as one JSON object per line in a file under the data directory.
+// Lines that fail to parse (e.g. a write cut short by a crash) are skipped.
+pub struct JsonlEventHistoryRepository {
+    path: std::path::PathBuf,
+    // Serializes appends so concurrent writers never interleave lines
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl JsonlEventHistoryRepository {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl EventHistoryRepository for JsonlEventHistoryRepository {
+    async fn append(&self, event: &HistoryEvent) -> Result<(), String> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut line = serde_json::to_string(event)
+            .map_err(|e| format!("Failed to serialize event: {}", e))?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| format!("Failed to open {}: {}", self.path.display(), e))?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+
+    async fn query(&self, filter: &EventHistoryFilter) -> Result<Vec<HistoryEvent>, String> {
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read {}: {}", self.path.display(), e)),
+        };
+
+        // Appended in time order, so walking backwards yields the newest matches first
+        Ok(contents
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<HistoryEvent>(line).ok())
+            .filter(|event| filter.matches(event))
+            .take(filter.limit)
+            .collect())
+    }
+}
+
+// IFF_UP in /sys/class/net/<name>/flags
+const IFF_UP: u32 = 0x1;
+
+const PROC_NET_ROUTE: &str = "/proc/net/route";
+// RTF_UP in the Flags column of /proc/net/route
+const RTF_UP: u32 = 0x1;
+
 // Real network interface repository using system interfaces
-pub struct SystemNetworkInterfaceRepository;
+pub struct SystemNetworkInterfaceRepository {
+    sysfs: Arc<dyn SysfsReader>,
+}
 
 impl SystemNetworkInterfaceRepository {
-    pub fn new() -> Self {
-        Self
+    pub fn new(sysfs: Arc<dyn SysfsReader>) -> Self {
+        Self { sysfs }
+    }
+
+    // Administrative up flag from sysfs, if available
+    async fn read_admin_up(&self, name: &str) -> Option<bool> {
+        let flags = self.sysfs.read_attribute(name, "flags").await?;
+        let flags = u32::from_str_radix(flags.trim_start_matches("0x"), 16).ok()?;
+        Some(flags & IFF_UP != 0)
+    }
+
+    // Carrier bit from sysfs. Reading it fails with EINVAL while the interface is administratively
+    // down, which the reader reports as `None` like any other unreadable attribute.
+    async fn read_carrier(&self, name: &str) -> Option<bool> {
+        parse_carrier(&self.sysfs.read_attribute(name, "carrier").await?)
     }
 
     fn determine_interface_type(name: &str) -> InterfaceType {
@@ -143,6 +450,8 @@ impl SystemNetworkInterfaceRepository {
             InterfaceType::Wireless
         } else if name.starts_with("eth") || name.starts_with("en") {
             InterfaceType::Ethernet
+        } else if name.starts_with("br") || name.starts_with("virbr") || name.starts_with("docker") {
+            InterfaceType::Bridge
         } else {
             InterfaceType::Other
         }
@@ -151,9 +460,21 @@ impl SystemNetworkInterfaceRepository {
 
 }
 
+// `address/prefix_len`, or the bare address when the netmask is unknown
+fn address_with_prefix(addr: &Addr) -> String {
+    let (address, prefix_len) = match addr {
+        Addr::V4(v4_addr) => (v4_addr.ip.to_string(), v4_addr.netmask.map(|mask| u32::from(mask).count_ones())),
+        Addr::V6(v6_addr) => (v6_addr.ip.to_string(), v6_addr.netmask.map(|mask| u128::from(mask).count_ones())),
+    };
+    match prefix_len {
+        Some(prefix_len) => format!("{}/{}", address, prefix_len),
+        None => address,
+    }
+}
+
 impl Default for SystemNetworkInterfaceRepository {
     fn default() -> Self {
-        Self::new()
+        Self::new(detect_sysfs_reader())
     }
 }
 
@@ -168,21 +489,32 @@ impl NetworkInterfaceRepository for SystemNetworkInterfaceRepository {
         // Group addresses by interface name
         for sys_interface in system_interfaces {
             let entry = interface_map.entry(sys_interface.name.clone()).or_insert_with(|| {
-                (Self::determine_interface_type(&sys_interface.name), Vec::new())
+                (Self::determine_interface_type(&sys_interface.name), None, Vec::new())
             });
-            entry.1.extend(sys_interface.addr);
+            if entry.1.is_none() {
+                entry.1 = sys_interface.mac_addr.as_deref().and_then(|mac| mac.parse::<MacAddress>().ok());
+            }
+            entry.2.extend(sys_interface.addr);
         }
 
         // Convert grouped interfaces to NetworkInterface structs
         let mut interfaces = Vec::new();
-        for (name, (interface_type, addresses)) in interface_map {
+        for (name, (interface_type, mac_address, addresses)) in interface_map {
             let mut ipv4_addresses = Vec::new();
             let mut ipv6_addresses = Vec::new();
+            let mut ipv4_cidrs = Vec::new();
+            let mut ipv6_cidrs = Vec::new();
 
             for addr in &addresses {
                 match addr {
-                    Addr::V4(v4_addr) => ipv4_addresses.push(v4_addr.ip.to_string()),
-                    Addr::V6(v6_addr) => ipv6_addresses.push(v6_addr.ip.to_string()),
+                    Addr::V4(v4_addr) => {
+                        ipv4_addresses.push(v4_addr.ip.to_string());
+                        ipv4_cidrs.push(address_with_prefix(addr));
+                    }
+                    Addr::V6(v6_addr) => {
+                        ipv6_addresses.push(v6_addr.ip.to_string());
+                        ipv6_cidrs.push(address_with_prefix(addr));
+                    }
                 }
             }
 
@@ -193,13 +525,31 @@ impl NetworkInterfaceRepository for SystemNetworkInterfaceRepository {
                 }
             });
 
+            // Fall back to sysfs for the MAC, and to "has an address" when sysfs has no flags
+            let mac_address = match mac_address {
+                Some(mac_address) => Some(mac_address),
+                None => self
+                    .sysfs
+                    .read_attribute(&name, "address")
+                    .await
+                    .and_then(|mac| mac.parse::<MacAddress>().ok()),
+            };
+            let is_up = self
+                .read_admin_up(&name)
+                .await
+                .unwrap_or(!ipv4_addresses.is_empty() || !ipv6_addresses.is_empty());
+            let has_carrier = self.read_carrier(&name).await;
+
             interfaces.push(NetworkInterface {
                 name,
                 interface_type,
-                mac_address: "N/A".to_string(),
-                is_up: !ipv4_addresses.is_empty() || !ipv6_addresses.is_empty(),
+                mac_address,
+                is_up,
+                has_carrier,
                 ipv4_addresses,
                 ipv6_addresses,
+                ipv4_cidrs,
+                ipv6_cidrs,
                 current_ip,
             });
         }
@@ -207,5 +557,583 @@ impl NetworkInterfaceRepository for SystemNetworkInterfaceRepository {
         Ok(interfaces)
     }
 
+    // The address listing covers every interface at once, so this is a filtered full lookup
+    async fn get_interface_by_name(&self, name: &str) -> Result<Option<NetworkInterface>, String> {
+        Ok(self.get_interfaces().await?.into_iter().find(|interface| interface.name == name))
+    }
+
+    async fn set_link_state(&self, name: &str, up: bool) -> Result<ApplyOutcome, String> {
+        // Same gate as the appliers: a dev build must never take a real interface down
+        if !cfg!(feature = "apply-system") {
+            return Ok(ApplyOutcome {
+                applied: false,
+                already_active: false,
+                detail: Some("system apply disabled".to_string()),
+            });
+        }
+        let state = if up { "up" } else { "down" };
+        let output = tokio::process::Command::new("ip")
+            .args(["link", "set", "dev", name, state])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run ip link: {}", e))?;
+
+        if output.status.success() {
+            Ok(ApplyOutcome { applied: true, already_active: false, detail: None })
+        } else {
+            Err(format!(
+                "Failed to set {} {}: {}",
+                name,
+                state,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    async fn get_wifi_link_status(&self, name: &str) -> Result<WifiLinkStatus, String> {
+        let output = match tokio::process::Command::new("iw")
+            .args(["dev", name, "link"])
+            .output()
+            .await
+        {
+            Ok(output) => output,
+            // No `iw` installed - report the link as down rather than failing
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(WifiLinkStatus::default()),
+            Err(e) => return Err(format!("Failed to run iw: {}", e)),
+        };
+
+        // iw exits non-zero for unknown or non-wireless interfaces
+        if !output.status.success() {
+            return Ok(WifiLinkStatus::default());
+        }
+        Ok(parse_iw_link(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    async fn get_default_route(&self) -> Result<DefaultRoute, String> {
+        match tokio::fs::read_to_string(PROC_NET_ROUTE).await {
+            Ok(contents) => Ok(parse_proc_net_route(&contents)),
+            // No procfs (e.g. macOS) - report no default route
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DefaultRoute::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", PROC_NET_ROUTE, e)),
+        }
+    }
+
+}
+
+// Contents of /sys/class/net/<name>/carrier: "1" with a link, "0" without
+pub fn parse_carrier(contents: &str) -> Option<bool> {
+    match contents.trim() {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
+
+// Parse the output of `iw dev <name> link`:
+//
+//   Connected to aa:bb:cc:dd:ee:ff (on wlan0)
+//           SSID: MyNetwork
+//           freq: 5180
+//           signal: -52 dBm
+//           tx bitrate: 433.3 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 1
+pub fn parse_iw_link(output: &str) -> WifiLinkStatus {
+    let mut status = WifiLinkStatus::default();
+
+    for line in output.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("Connected to ") {
+            status.connected = true;
+            status.bssid = rest.split_whitespace().next().and_then(|mac| mac.parse().ok());
+        } else if let Some(ssid) = line.strip_prefix("SSID: ") {
+            status.ssid = Some(ssid.to_string());
+        } else if let Some(freq) = line.strip_prefix("freq: ") {
+            // Newer iw versions print the frequency with a decimal part
+            status.freq_mhz = freq.trim().parse::<f64>().ok().map(|freq| freq.round() as u32);
+        } else if let Some(signal) = line.strip_prefix("signal: ") {
+            status.signal_dbm = signal.split_whitespace().next().and_then(|dbm| dbm.parse().ok());
+        } else if let Some(bitrate) = line.strip_prefix("tx bitrate: ") {
+            status.tx_bitrate = Some(bitrate.to_string());
+        }
+    }
+
+    if !status.connected {
+        return WifiLinkStatus::default();
+    }
+    status
+}
+
+// Parse /proc/net/route and pick the default route with the lowest metric:
+//
+//   Iface   Destination  Gateway   Flags  RefCnt  Use  Metric  Mask      MTU  Window  IRTT
+//   eth0    00000000     0101A8C0  0003   0       0    100     00000000  0    0       0
+//
+// Addresses are the raw network-order bytes printed as a host-order hex number.
+pub fn parse_proc_net_route(contents: &str) -> DefaultRoute {
+    let mut best: Option<(u32, DefaultRoute)> = None;
+
+    for line in contents.lines().skip(1) {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 8 {
+            continue;
+        }
+        let hex = |value: &str| u32::from_str_radix(value, 16).ok();
+        let (Some(destination), Some(gateway), Some(flags), Some(mask)) =
+            (hex(columns[1]), hex(columns[2]), hex(columns[3]), hex(columns[7]))
+        else {
+            continue;
+        };
+        if destination != 0 || mask != 0 || flags & RTF_UP == 0 {
+            continue;
+        }
+
+        let metric = columns[6].parse().unwrap_or(u32::MAX);
+        if best.as_ref().is_some_and(|(best_metric, _)| *best_metric <= metric) {
+            continue;
+        }
+        // A zero gateway means the default route is directly on the link
+        let gateway = (gateway != 0)
+            .then(|| std::net::Ipv4Addr::from(gateway.to_ne_bytes()).to_string());
+        best = Some((metric, DefaultRoute {
+            gateway,
+            interface: Some(columns[0].to_string()),
+        }));
+    }
+
+    best.map(|(_, route)| route).unwrap_or_default()
+}
+
+// Interface counters read from /sys/class/net/<name>/statistics
+pub struct SysfsInterfaceStatsRepository {
+    sysfs: Arc<dyn SysfsReader>,
+}
+
+impl SysfsInterfaceStatsRepository {
+    pub fn new(sysfs: Arc<dyn SysfsReader>) -> Self {
+        Self { sysfs }
+    }
+
+    async fn read_counter(&self, interface_name: &str, counter: &str) -> Result<u64, String> {
+        let attribute = format!("statistics/{}", counter);
+        let contents = self
+            .sysfs
+            .read_attribute(interface_name, &attribute)
+            .await
+            .ok_or_else(|| format!("Statistics are not available for {}", interface_name))?;
+        contents
+            .parse()
+            .map_err(|_| format!("Invalid {} value for {}", counter, interface_name))
+    }
+}
+
+impl Default for SysfsInterfaceStatsRepository {
+    fn default() -> Self {
+        Self::new(detect_sysfs_reader())
+    }
+}
+
+#[async_trait]
+impl InterfaceStatsRepository for SysfsInterfaceStatsRepository {
+    async fn get_stats(&self, interface_name: &str) -> Result<InterfaceStats, String> {
+        if interface_name.is_empty() || interface_name.contains('/') || interface_name.starts_with('.') {
+            return Err(format!("Invalid interface name: {}", interface_name));
+        }
+
+        Ok(InterfaceStats {
+            rx_bytes: self.read_counter(interface_name, "rx_bytes").await?,
+            tx_bytes: self.read_counter(interface_name, "tx_bytes").await?,
+        })
+    }
+}
+
+// Scans with the platform tools wrapped by `wifiscanner`
+#[derive(Default)]
+pub struct SystemWifiScanner {
+    // Held by the blocking scan thread, so only one scan drives the radio at a time
+    scan_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl SystemWifiScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl WifiScanner for SystemWifiScanner {
+    async fn scan(&self) -> Result<Vec<ScannedWifiNetwork>, String> {
+        // The scan blocks, so run it off the runtime. If the caller times out the blocking
+        // thread is abandoned and finishes in the background, releasing the lock when it does;
+        // a scan started meanwhile waits for it instead of running a second one alongside.
+        let scan_guard = self.scan_lock.clone().lock_owned().await;
+        let networks = tokio::task::spawn_blocking(move || {
+            let _scan_guard = scan_guard;
+            wifiscanner::scan()
+        })
+        .await
+        .map_err(|e| format!("WiFi scan task failed: {}", e))?
+        .map_err(|e| format!("WiFi scan failed: {:?}", e))?;
+
+        Ok(networks
+            .into_iter()
+            .map(|network| ScannedWifiNetwork {
+                ssid: network.ssid,
+                mac: if network.mac.is_empty() { "Unknown".to_string() } else { network.mac },
+                signal_level: None,
+                signal_raw: network.signal_level,
+                channel: network.channel,
+                channel_number: None,
+                band: None,
+                security: network.security,
+            })
+            .collect())
+    }
+}
+
+// Runs a probe command; exit status 0 means the address answered
+async fn run_probe(program: &str, args: &[&str]) -> Result<bool, String> {
+    let status = tokio::process::Command::new(program)
+        .args(args)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .status()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    Ok(status.success())
+}
+
+// ICMP echo via the setuid `ping`, so no extra privileges are needed. Hosts that drop
+// pings look free; ARP probing (`apply-system`) does not have that blind spot.
+#[cfg_attr(feature = "apply-system", allow(dead_code))]
+pub struct PingIpProber;
+
+#[async_trait]
+impl IpProber for PingIpProber {
+    fn method(&self) -> &'static str {
+        "icmp"
+    }
+
+    async fn probe(&self, interface_name: &str, ip_address: std::net::Ipv4Addr) -> Result<bool, String> {
+        let ip = ip_address.to_string();
+        run_probe("ping", &["-c", "1", "-W", "2", "-I", interface_name, &ip]).await
+    }
+}
 
-}
\ No newline at end of file
+// Plain DNS over UDP port 53 to each server, so the timing is the resolver's and not a local cache's
+pub struct UdpDnsProber {
+    resolv_conf: std::path::PathBuf,
+}
+
+impl UdpDnsProber {
+    pub fn new() -> Self {
+        Self {
+            resolv_conf: std::path::PathBuf::from("/etc/resolv.conf"),
+        }
+    }
+}
+
+impl Default for UdpDnsProber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// `nameserver` lines of a resolv.conf; scoped IPv6 addresses (fe80::1%eth0) are skipped
+pub fn parse_resolv_conf_nameservers(contents: &str) -> Vec<std::net::IpAddr> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            (fields.next() == Some("nameserver")).then(|| fields.next()).flatten()
+        })
+        .filter_map(|server| server.parse().ok())
+        .collect()
+}
+
+// A recursion-desired query for the A record of `hostname`
+pub fn encode_dns_query(id: u16, hostname: &str) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(hostname.len() + 18);
+    packet.extend_from_slice(&id.to_be_bytes());
+    // Flags: RD; then one question and no other records
+    packet.extend_from_slice(&[0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    for label in hostname.trim_end_matches('.').split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    // QTYPE A, QCLASS IN
+    packet.extend_from_slice(&[0x00, 0x01, 0x00, 0x01]);
+    packet
+}
+
+#[async_trait]
+impl DnsProber for UdpDnsProber {
+    async fn system_resolvers(&self) -> Result<Vec<std::net::IpAddr>, String> {
+        let contents = tokio::fs::read_to_string(&self.resolv_conf)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", self.resolv_conf.display(), e))?;
+        Ok(parse_resolv_conf_nameservers(&contents))
+    }
+
+    async fn query(&self, server: std::net::IpAddr, hostname: &str, timeout: std::time::Duration) -> Result<std::time::Duration, String> {
+        let local: std::net::SocketAddr = if server.is_ipv4() {
+            (std::net::Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = tokio::net::UdpSocket::bind(local)
+            .await
+            .map_err(|e| format!("Failed to open a UDP socket: {}", e))?;
+        socket
+            .connect((server, 53))
+            .await
+            .map_err(|e| format!("Failed to reach {}: {}", server, e))?;
+
+        let random = uuid::Uuid::new_v4();
+        let id = u16::from_be_bytes([random.as_bytes()[0], random.as_bytes()[1]]);
+        let query = encode_dns_query(id, hostname);
+        let started = std::time::Instant::now();
+        socket
+            .send(&query)
+            .await
+            .map_err(|e| format!("Failed to send query to {}: {}", server, e))?;
+        // Skip stray datagrams until the reply to this query (same id, QR bit set)
+        let reply = async {
+            let mut buffer = [0u8; 512];
+            loop {
+                let len = socket.recv(&mut buffer).await?;
+                if len >= 3 && buffer[..2] == id.to_be_bytes() && buffer[2] & 0x80 != 0 {
+                    return Ok::<_, std::io::Error>(started.elapsed());
+                }
+            }
+        };
+        tokio::time::timeout(timeout, reply)
+            .await
+            .map_err(|_| format!("No answer from {} within {}ms", server, timeout.as_millis()))?
+            .map_err(|e| format!("Failed to receive from {}: {}", server, e))
+    }
+}
+
+// ARP duplicate address detection with `arping -D`; needs CAP_NET_RAW
+#[cfg(feature = "apply-system")]
+pub struct ArpingIpProber;
+
+#[cfg(feature = "apply-system")]
+#[async_trait]
+impl IpProber for ArpingIpProber {
+    fn method(&self) -> &'static str {
+        "arp"
+    }
+
+    async fn probe(&self, interface_name: &str, ip_address: std::net::Ipv4Addr) -> Result<bool, String> {
+        // In DAD mode arping exits 0 when nobody replied, so success means the address is free
+        let ip = ip_address.to_string();
+        let free = run_probe("arping", &["-D", "-c", "1", "-w", "2", "-I", interface_name, &ip]).await?;
+        Ok(!free)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::network_services::NetworkConfigService;
+    use crate::test_support::{fake_sysfs, temp_dir, TestNetwork};
+
+    #[test]
+    fn iw_link_output_is_parsed() {
+        let output = "Connected to AA:BB:CC:DD:EE:FF (on wlan0)
+\tSSID: MyNetwork
+\tfreq: 5180.0
+\tRX: 1234 bytes (10 packets)
+\tsignal: -52 dBm
+\ttx bitrate: 433.3 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 1
+";
+        let status = parse_iw_link(output);
+        assert!(status.connected);
+        assert_eq!(status.ssid.as_deref(), Some("MyNetwork"));
+        assert_eq!(status.bssid.map(|mac| mac.to_string()).as_deref(), Some("aa:bb:cc:dd:ee:ff"));
+        assert_eq!(status.freq_mhz, Some(5180));
+        assert_eq!(status.signal_dbm, Some(-52));
+        assert_eq!(status.tx_bitrate.as_deref(), Some("433.3 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 1"));
+    }
+
+    #[test]
+    fn iw_link_output_without_a_connection_is_empty() {
+        let status = parse_iw_link("Not connected.\n");
+        assert!(!status.connected);
+        assert!(status.ssid.is_none());
+        assert!(status.signal_dbm.is_none());
+    }
+
+    const ROUTE_HEADER: &str =
+        "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n";
+
+    #[test]
+    fn proc_net_route_default_route_with_the_lowest_metric_wins() {
+        let table = format!(
+            "{}{}{}{}{}",
+            ROUTE_HEADER,
+            "wlan0\t00000000\t0100000A\t0003\t0\t0\t600\t00000000\t0\t0\t0\n",
+            "eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0\n",
+            "eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0\n",
+            // Not up, so ignored despite the better metric
+            "eth1\t00000000\t01010A0A\t0002\t0\t0\t10\t00000000\t0\t0\t0\n",
+        );
+        let route = parse_proc_net_route(&table);
+        assert_eq!(route.interface.as_deref(), Some("eth0"));
+        assert_eq!(route.gateway.as_deref(), Some("192.168.1.1"));
+    }
+
+    #[test]
+    fn proc_net_route_without_a_default_route_is_empty() {
+        let on_link = format!("{}wg0\t00000000\t00000000\t0001\t0\t0\t0\t00000000\t0\t0\t0\n", ROUTE_HEADER);
+        let route = parse_proc_net_route(&on_link);
+        assert_eq!(route.interface.as_deref(), Some("wg0"));
+        assert!(route.gateway.is_none());
+
+        let route = parse_proc_net_route(&format!("{}garbage line\n", ROUTE_HEADER));
+        assert!(route.interface.is_none() && route.gateway.is_none());
+    }
+
+    #[tokio::test]
+    async fn interface_admin_states_survive_a_restart() {
+        let path = temp_dir().join("interface_states.json");
+        let repository = JsonFileInterfaceAdminStateRepository::new(&path);
+        repository.save(&InterfaceAdminState::new("eth1".to_string(), false)).await.unwrap();
+        repository.save(&InterfaceAdminState::new("eth0".to_string(), true)).await.unwrap();
+        // A later change to the same interface replaces the earlier one
+        repository.save(&InterfaceAdminState::new("eth1".to_string(), true)).await.unwrap();
+
+        let reopened = JsonFileInterfaceAdminStateRepository::new(&path);
+        let states = reopened.find_all().await.unwrap();
+        let summary: Vec<(&str, bool)> = states
+            .iter()
+            .map(|state| (state.interface_name.as_str(), state.desired_up))
+            .collect();
+        assert_eq!(summary, vec![("eth0", true), ("eth1", true)]);
+    }
+
+    #[tokio::test]
+    async fn missing_interface_state_file_means_no_states() {
+        let repository = JsonFileInterfaceAdminStateRepository::new(temp_dir().join("missing.json"));
+        assert!(repository.find_all().await.unwrap().is_empty());
+    }
+
+    #[cfg(not(feature = "apply-system"))]
+    #[tokio::test]
+    async fn link_state_is_not_touched_without_apply_system() {
+        let repository = SystemNetworkInterfaceRepository::new(Arc::new(crate::infrastructure::sysfs::NoSysfsReader));
+        let outcome = repository.set_link_state("lo", false).await.unwrap();
+        assert!(!outcome.applied);
+        assert_eq!(outcome.detail.as_deref(), Some("system apply disabled"));
+    }
+
+    #[tokio::test]
+    async fn stats_are_read_from_sysfs_counters() {
+        let sysfs = fake_sysfs(&[("eth0", "statistics/rx_bytes", "1234\n"), ("eth0", "statistics/tx_bytes", "5678\n")]);
+        let stats = SysfsInterfaceStatsRepository::new(sysfs).get_stats("eth0").await.unwrap();
+        assert_eq!((stats.rx_bytes, stats.tx_bytes), (1234, 5678));
+    }
+
+    #[tokio::test]
+    async fn unreadable_sysfs_counters_are_errors_not_panics() {
+        let missing = SysfsInterfaceStatsRepository::new(Arc::new(crate::infrastructure::sysfs::NoSysfsReader));
+        assert_eq!(missing.get_stats("eth0").await.unwrap_err(), "Statistics are not available for eth0");
+
+        let garbage = SysfsInterfaceStatsRepository::new(fake_sysfs(&[
+            ("eth0", "statistics/rx_bytes", "lots"),
+            ("eth0", "statistics/tx_bytes", "1"),
+        ]));
+        assert_eq!(garbage.get_stats("eth0").await.unwrap_err(), "Invalid rx_bytes value for eth0");
+        assert!(garbage.get_stats("../eth0").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn interfaces_without_sysfs_counters_are_left_out_of_the_bulk_stats() {
+        let mut network = TestNetwork::new();
+        network.stats = Arc::new(SysfsInterfaceStatsRepository::new(fake_sysfs(&[
+            ("eth0", "statistics/rx_bytes", "10"),
+            ("eth0", "statistics/tx_bytes", "20"),
+            ("wlan0", "statistics/rx_bytes", "30\n"),
+            ("wlan0", "statistics/tx_bytes", "40\n"),
+        ])));
+        let stats = network.service().get_all_interface_stats().await.unwrap();
+        let counters: Vec<(&str, u64, u64)> =
+            stats.iter().map(|(name, stats)| (name.as_str(), stats.rx_bytes, stats.tx_bytes)).collect();
+        // lo has no counters in the fake sysfs
+        assert_eq!(counters, vec![("eth0", 10, 20), ("wlan0", 30, 40)]);
+    }
+
+    #[tokio::test]
+    async fn wifi_configs_are_found_by_id() {
+        let repository = InMemoryWifiConfigRepository::new();
+        let config = WifiConfig::new("Home".to_string(), "correct horse".to_string(), WifiSecurityType::WPA2, false);
+        repository.save(&config).await.unwrap();
+
+        let found = repository.find_by_id(&config.id).await.unwrap().unwrap();
+        assert_eq!(found.ssid, "Home");
+        assert!(repository.find_by_id("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn event_history_is_queried_by_type_and_time() {
+        let repository = JsonlEventHistoryRepository::new(temp_dir().join("events.jsonl"));
+        let at = |minute: i64| chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000 + minute * 60, 0).unwrap();
+        for (minute, event_type, subject) in [
+            (0, HistoryEventType::WifiCreated, "old"),
+            (1, HistoryEventType::InterfaceAdded, "eth1"),
+            (2, HistoryEventType::WifiCreated, "new"),
+        ] {
+            let mut event = HistoryEvent::new(event_type, subject, None);
+            event.timestamp = at(minute);
+            repository.append(&event).await.unwrap();
+        }
+        let subjects = |events: Vec<HistoryEvent>| events.into_iter().map(|event| event.subject).collect::<Vec<_>>();
+
+        let filter = EventHistoryFilter { since: None, event_types: Some(vec![HistoryEventType::WifiCreated]), limit: 10 };
+        assert_eq!(subjects(repository.query(&filter).await.unwrap()), vec!["new", "old"]);
+        let filter = EventHistoryFilter { since: Some(at(1)), event_types: None, limit: 10 };
+        assert_eq!(subjects(repository.query(&filter).await.unwrap()), vec!["new", "eth1"]);
+        let filter = EventHistoryFilter { since: None, event_types: None, limit: 1 };
+        assert_eq!(subjects(repository.query(&filter).await.unwrap()), vec!["new"]);
+    }
+
+    #[tokio::test]
+    async fn carrier_is_read_from_sysfs() {
+        let root = temp_dir();
+        for (interface_name, carrier) in [("eth0", "1\n"), ("eth1", "0\n")] {
+            std::fs::create_dir_all(root.join(interface_name)).unwrap();
+            std::fs::write(root.join(interface_name).join("carrier"), carrier).unwrap();
+        }
+        // Stands in for the EINVAL the kernel returns for a downed interface: the read fails
+        std::fs::create_dir_all(root.join("eth2").join("carrier")).unwrap();
+        let repository = SystemNetworkInterfaceRepository::new(Arc::new(crate::infrastructure::sysfs::LinuxSysfsReader::with_root(root)));
+
+        assert_eq!(repository.read_carrier("eth0").await, Some(true));
+        assert_eq!(repository.read_carrier("eth1").await, Some(false));
+        assert_eq!(repository.read_carrier("eth2").await, None);
+        assert_eq!(parse_carrier("garbage"), None);
+    }
+
+    #[test]
+    fn prefix_length_comes_from_the_netmask() {
+        use network_interface::{V4IfAddr, V6IfAddr};
+        let v4 = |netmask: Option<&str>| {
+            Addr::V4(V4IfAddr {
+                ip: "192.168.1.10".parse().unwrap(),
+                broadcast: None,
+                netmask: netmask.map(|mask| mask.parse().unwrap()),
+            })
+        };
+        assert_eq!(address_with_prefix(&v4(Some("255.255.255.0"))), "192.168.1.10/24");
+        assert_eq!(address_with_prefix(&v4(Some("255.255.240.0"))), "192.168.1.10/20");
+        assert_eq!(address_with_prefix(&v4(Some("255.255.255.255"))), "192.168.1.10/32");
+        assert_eq!(address_with_prefix(&v4(None)), "192.168.1.10");
+
+        let v6 = Addr::V6(V6IfAddr {
+            ip: "fe80::1".parse().unwrap(),
+            broadcast: None,
+            netmask: Some("ffff:ffff:ffff:ffff::".parse().unwrap()),
+        });
+        assert_eq!(address_with_prefix(&v6), "fe80::1/64");
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use crate::domain::entities::Greeting;
 use crate::domain::repositories::GreetingRepository;
+use crate::infrastructure::snapshot::Snapshotable;
 
 // In-memory repository implementation
 pub struct InMemoryGreetingRepository {
@@ -38,4 +39,43 @@ impl GreetingRepository for InMemoryGreetingRepository {
         let storage = self.storage.read().await;
         Ok(storage.values().cloned().collect())
     }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Greeting>, String> {
+        let storage = self.storage.read().await;
+        Ok(storage.get(id).cloned())
+    }
+
+    async fn find_by_message_language(&self, message: &str, language: &str) -> Result<Option<Greeting>, String> {
+        let storage = self.storage.read().await;
+        Ok(storage
+            .values()
+            .find(|g| g.message == message && g.language == language)
+            .cloned())
+    }
+
+    async fn save_if_absent(&self, greeting: &Greeting) -> Result<Greeting, String> {
+        // Lookup and insert under one write lock so concurrent identical creates can't both insert
+        let mut storage = self.storage.write().await;
+        if let Some(existing) = storage
+            .values()
+            .find(|g| g.message == greeting.message && g.language == greeting.language)
+        {
+            return Ok(existing.clone());
+        }
+        storage.insert(greeting.id.clone(), greeting.clone());
+        Ok(greeting.clone())
+    }
+}
+
+#[async_trait]
+impl Snapshotable for InMemoryGreetingRepository {
+    type Item = Greeting;
+
+    async fn snapshot(&self) -> Vec<Greeting> {
+        self.storage.read().await.values().cloned().collect()
+    }
+
+    async fn restore(&self, greetings: Vec<Greeting>) {
+        *self.storage.write().await = greetings.into_iter().map(|greeting| (greeting.id.clone(), greeting)).collect();
+    }
 }
\ No newline at end of file
@@ -0,0 +1,380 @@
+// Simulation mode - fake interfaces, WiFi networks and appliers for demos and UI work
+// without WiFi hardware; nothing here touches the live system
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::domain::network_appliers::*;
+use crate::domain::network_entities::*;
+use crate::domain::network_repositories::*;
+use crate::domain::system_entities::{IpForwarding, ProcessPrivileges};
+use crate::domain::system_repositories::{HostnameRepository, IpForwardingRepository, PrivilegeProbe};
+
+// Simulated byte rates for interfaces that are up
+const SIM_RX_BYTES_PER_SEC: u64 = 125_000;
+const SIM_TX_BYTES_PER_SEC: u64 = 25_000;
+
+// What the simulation reports; missing sections of a JSON fixture fall back to the built-in ones
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationFixture {
+    pub interfaces: Vec<NetworkInterface>,
+    pub networks: Vec<ScannedWifiNetwork>,
+    pub default_route: DefaultRoute,
+    // What `iw dev <name> link` reports for every wireless interface; not connected by default
+    pub wifi_link: WifiLinkStatus,
+}
+
+impl SimulationFixture {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read simulation fixture {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid simulation fixture {}: {}", path.display(), e))
+    }
+}
+
+impl Default for SimulationFixture {
+    fn default() -> Self {
+        let interface = |name: &str, interface_type, mac: &str, ipv4: Option<&str>| NetworkInterface {
+            name: name.to_string(),
+            interface_type,
+            mac_address: mac.parse().ok(),
+            is_up: true,
+            has_carrier: Some(true),
+            ipv4_addresses: ipv4.into_iter().map(str::to_string).collect(),
+            ipv6_addresses: Vec::new(),
+            ipv4_cidrs: ipv4.into_iter().map(|ip| format!("{}/24", ip)).collect(),
+            ipv6_cidrs: Vec::new(),
+            current_ip: ipv4.map(str::to_string),
+        };
+        let network = |ssid: &str, mac: &str, signal_raw: &str, channel: &str, security: &str| ScannedWifiNetwork {
+            ssid: ssid.to_string(),
+            mac: mac.to_string(),
+            signal_level: None,
+            signal_raw: signal_raw.to_string(),
+            channel: channel.to_string(),
+            channel_number: None,
+            band: None,
+            security: security.to_string(),
+        };
+
+        Self {
+            interfaces: vec![
+                interface("lo", InterfaceType::Loopback, "00:00:00:00:00:00", Some("127.0.0.1")),
+                interface("eth0", InterfaceType::Ethernet, "02:00:00:00:00:01", Some("192.168.1.50")),
+                interface("wlan0", InterfaceType::Wireless, "02:00:00:00:00:02", None),
+            ],
+            networks: vec![
+                network("HomeNetwork", "02:00:00:00:10:01", "-45", "36", "WPA2"),
+                network("Neighbor", "02:00:00:00:10:02", "-71", "6", "WPA2"),
+                network("CoffeeShop", "02:00:00:00:10:03", "22%", "11", "Open"),
+            ],
+            default_route: DefaultRoute {
+                gateway: Some("192.168.1.1".to_string()),
+                interface: Some("eth0".to_string()),
+            },
+            wifi_link: WifiLinkStatus::default(),
+        }
+    }
+}
+
+// Canned interfaces whose link state can be toggled; also serves steadily growing traffic counters
+pub struct SimulatedNetworkInterfaceRepository {
+    interfaces: Arc<RwLock<Vec<NetworkInterface>>>,
+    default_route: DefaultRoute,
+    wifi_link: WifiLinkStatus,
+    started: std::time::Instant,
+}
+
+impl SimulatedNetworkInterfaceRepository {
+    pub fn new(interfaces: Vec<NetworkInterface>, default_route: DefaultRoute) -> Self {
+        Self {
+            interfaces: Arc::new(RwLock::new(interfaces)),
+            default_route,
+            wifi_link: WifiLinkStatus::default(),
+            started: std::time::Instant::now(),
+        }
+    }
+
+    pub fn with_wifi_link(mut self, wifi_link: WifiLinkStatus) -> Self {
+        self.wifi_link = wifi_link;
+        self
+    }
+}
+
+#[async_trait]
+impl NetworkInterfaceRepository for SimulatedNetworkInterfaceRepository {
+    async fn get_interfaces(&self) -> Result<Vec<NetworkInterface>, String> {
+        Ok(self.interfaces.read().await.clone())
+    }
+
+    async fn get_interface_by_name(&self, name: &str) -> Result<Option<NetworkInterface>, String> {
+        Ok(self.interfaces.read().await.iter().find(|interface| interface.name == name).cloned())
+    }
+
+    async fn set_link_state(&self, name: &str, up: bool) -> Result<ApplyOutcome, String> {
+        let mut interfaces = self.interfaces.write().await;
+        let interface = interfaces
+            .iter_mut()
+            .find(|interface| interface.name == name)
+            .ok_or_else(|| format!("Interface {} not found", name))?;
+        interface.is_up = up;
+        // Like the kernel, which has no carrier reading for a downed interface
+        interface.has_carrier = up.then_some(true);
+        let action = format!("ip link set dev {} {}", name, if up { "up" } else { "down" });
+        tracing::info!("[sim] {}", action);
+        Ok(ApplyOutcome {
+            applied: true,
+            already_active: false,
+            detail: Some(format!("simulated: {}", action)),
+        })
+    }
+
+    async fn get_wifi_link_status(&self, _name: &str) -> Result<WifiLinkStatus, String> {
+        Ok(self.wifi_link.clone())
+    }
+
+    async fn get_default_route(&self) -> Result<DefaultRoute, String> {
+        Ok(self.default_route.clone())
+    }
+}
+
+#[async_trait]
+impl InterfaceStatsRepository for SimulatedNetworkInterfaceRepository {
+    async fn get_stats(&self, interface_name: &str) -> Result<InterfaceStats, String> {
+        let interfaces = self.interfaces.read().await;
+        let interface = interfaces
+            .iter()
+            .find(|interface| interface.name == interface_name)
+            .ok_or_else(|| format!("Interface {} not found", interface_name))?;
+
+        let elapsed_ms = if interface.is_up { self.started.elapsed().as_millis() as u64 } else { 0 };
+        Ok(InterfaceStats {
+            rx_bytes: elapsed_ms * SIM_RX_BYTES_PER_SEC / 1000,
+            tx_bytes: elapsed_ms * SIM_TX_BYTES_PER_SEC / 1000,
+        })
+    }
+}
+
+pub struct SimulatedWifiScanner {
+    networks: Vec<ScannedWifiNetwork>,
+}
+
+impl SimulatedWifiScanner {
+    pub fn new(networks: Vec<ScannedWifiNetwork>) -> Self {
+        Self { networks }
+    }
+}
+
+#[async_trait]
+impl WifiScanner for SimulatedWifiScanner {
+    async fn scan(&self) -> Result<Vec<ScannedWifiNetwork>, String> {
+        Ok(self.networks.clone())
+    }
+}
+
+// Simulated addresses answer when an interface holds them or they are the default gateway
+pub struct SimulatedIpProber {
+    interfaces: Arc<SimulatedNetworkInterfaceRepository>,
+}
+
+impl SimulatedIpProber {
+    pub fn new(interfaces: Arc<SimulatedNetworkInterfaceRepository>) -> Self {
+        Self { interfaces }
+    }
+}
+
+#[async_trait]
+impl IpProber for SimulatedIpProber {
+    fn method(&self) -> &'static str {
+        "simulated"
+    }
+
+    async fn probe(&self, _interface_name: &str, ip_address: std::net::Ipv4Addr) -> Result<bool, String> {
+        let ip = ip_address.to_string();
+        let held = self.interfaces.interfaces.read().await
+            .iter()
+            .any(|interface| interface.ipv4_addresses.contains(&ip));
+        Ok(held || self.interfaces.default_route.gateway.as_deref() == Some(ip.as_str()))
+    }
+}
+
+// Every resolver answers after a few milliseconds, varying per query
+pub struct SimulatedDnsProber;
+
+#[async_trait]
+impl DnsProber for SimulatedDnsProber {
+    async fn system_resolvers(&self) -> Result<Vec<std::net::IpAddr>, String> {
+        Ok(vec![std::net::IpAddr::from([192, 168, 1, 1])])
+    }
+
+    async fn query(&self, _server: std::net::IpAddr, _hostname: &str, _timeout: std::time::Duration) -> Result<std::time::Duration, String> {
+        let jitter = u64::from(uuid::Uuid::new_v4().as_bytes()[0]) * 40;
+        let latency = std::time::Duration::from_micros(5_000 + jitter);
+        tokio::time::sleep(latency).await;
+        Ok(latency)
+    }
+}
+
+// Logs what a real applier would do and reports success
+pub struct SimulatedApplier;
+
+impl SimulatedApplier {
+    fn outcome(action: String) -> ApplyOutcome {
+        tracing::info!("[sim] {}", action);
+        ApplyOutcome {
+            applied: true,
+            already_active: false,
+            detail: Some(format!("simulated: {}", action)),
+        }
+    }
+}
+
+#[async_trait]
+impl StaticIpApplier for SimulatedApplier {
+    async fn apply(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome(format!(
+            "would set {}/{} on {}",
+            config.ip_address,
+            config.prefix_len,
+            config.interface_name
+        )))
+    }
+
+    async fn remove(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome(format!("would remove static IP from {}", config.interface_name)))
+    }
+}
+
+#[async_trait]
+impl WifiApplier for SimulatedApplier {
+    async fn apply(&self, config: &WifiConfig) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome(format!("would connect to {}", config.ssid)))
+    }
+
+    async fn disconnect(&self, config: &WifiConfig) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome(format!("would disconnect from {}", config.ssid)))
+    }
+}
+
+#[async_trait]
+impl ServiceRestarter for SimulatedApplier {
+    async fn restart(&self, unit: &str) -> Result<ApplyOutcome, String> {
+        Ok(Self::outcome(format!("would restart {}", unit)))
+    }
+}
+
+// Hostname kept in memory, so renaming the demo box never touches the real one
+pub struct SimulatedHostnameRepository {
+    hostname: RwLock<String>,
+}
+
+impl SimulatedHostnameRepository {
+    pub fn new() -> Self {
+        Self {
+            hostname: RwLock::new("homelab-sim".to_string()),
+        }
+    }
+}
+
+impl Default for SimulatedHostnameRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HostnameRepository for SimulatedHostnameRepository {
+    async fn get(&self) -> Result<String, String> {
+        Ok(self.hostname.read().await.clone())
+    }
+
+    async fn set(&self, hostname: &str) -> Result<(), String> {
+        tracing::info!("[sim] hostnamectl set-hostname {}", hostname);
+        *self.hostname.write().await = hostname.to_string();
+        Ok(())
+    }
+}
+
+// Forwarding flags kept in memory, off at start
+#[derive(Default)]
+pub struct SimulatedIpForwardingRepository {
+    forwarding: RwLock<IpForwarding>,
+}
+
+#[async_trait]
+impl IpForwardingRepository for SimulatedIpForwardingRepository {
+    async fn get(&self) -> Result<IpForwarding, String> {
+        Ok(*self.forwarding.read().await)
+    }
+
+    async fn set(&self, forwarding: &IpForwarding) -> Result<(), String> {
+        tracing::info!("[sim] sysctl net.ipv4.ip_forward={} net.ipv6.conf.all.forwarding={}", u8::from(forwarding.ipv4), u8::from(forwarding.ipv6));
+        *self.forwarding.write().await = *forwarding;
+        Ok(())
+    }
+}
+
+// Simulated changes always go through, so report the privileges a real deployment would have
+pub struct SimulatedPrivilegeProbe;
+
+#[async_trait]
+impl PrivilegeProbe for SimulatedPrivilegeProbe {
+    async fn probe(&self) -> Result<ProcessPrivileges, String> {
+        Ok(ProcessPrivileges {
+            running_as_root: true,
+            has_net_admin: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[tokio::test]
+    async fn built_in_fixture_serves_the_canned_interfaces_and_networks() {
+        let fixture = SimulationFixture::default();
+        let repository = SimulatedNetworkInterfaceRepository::new(fixture.interfaces, fixture.default_route);
+        let interfaces = repository.get_interfaces().await.unwrap();
+        let names: Vec<&str> = interfaces.iter().map(|interface| interface.name.as_str()).collect();
+        assert_eq!(names, vec!["lo", "eth0", "wlan0"]);
+        assert_eq!(interfaces[1].ipv4_cidrs, vec!["192.168.1.50/24"]);
+        assert_eq!(repository.get_default_route().await.unwrap().gateway.as_deref(), Some("192.168.1.1"));
+
+        let networks = SimulatedWifiScanner::new(fixture.networks).scan().await.unwrap();
+        let ssids: Vec<&str> = networks.iter().map(|network| network.ssid.as_str()).collect();
+        assert_eq!(ssids, vec!["HomeNetwork", "Neighbor", "CoffeeShop"]);
+    }
+
+    #[tokio::test]
+    async fn fixture_file_replaces_only_the_sections_it_has() {
+        let path = temp_dir().join("fixture.json");
+        std::fs::write(
+            &path,
+            r#"{"interfaces": [{"name": "enp3s0", "interface_type": "Ethernet", "mac_address": null,
+                "is_up": false, "has_carrier": null, "ipv4_addresses": [], "ipv6_addresses": [], "current_ip": null}]}"#,
+        )
+        .unwrap();
+        let fixture = SimulationFixture::from_file(&path).unwrap();
+        assert_eq!(fixture.interfaces.len(), 1);
+        assert_eq!(fixture.interfaces[0].name, "enp3s0");
+        assert_eq!(fixture.networks.len(), SimulationFixture::default().networks.len());
+
+        std::fs::write(&path, r#"{"interfaces": [], "routers": []}"#).unwrap();
+        assert!(SimulationFixture::from_file(&path).unwrap_err().starts_with("Invalid simulation fixture"));
+    }
+
+    #[tokio::test]
+    async fn link_state_changes_are_remembered() {
+        let fixture = SimulationFixture::default();
+        let repository = SimulatedNetworkInterfaceRepository::new(fixture.interfaces, fixture.default_route);
+        repository.set_link_state("eth0", false).await.unwrap();
+        assert!(!repository.get_interface_by_name("eth0").await.unwrap().unwrap().is_up);
+        assert!(repository.set_link_state("eth9", false).await.is_err());
+    }
+}
@@ -0,0 +1,172 @@
+// Snapshot of the in-memory repositories - written on graceful shutdown and loaded at startup,
+// so configs survive a restart without persisting every change. A crash loses whatever changed
+// since the last clean shutdown.
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use crate::domain::entities::Greeting;
+use crate::domain::network_entities::{StaticIpConfig, WifiConfig};
+use crate::infrastructure::network_repositories::{InMemoryStaticIpConfigRepository, InMemoryWifiConfigRepository};
+use crate::infrastructure::repositories::InMemoryGreetingRepository;
+
+// A repository whose whole contents can be dumped and put back
+#[async_trait]
+pub trait Snapshotable: Send + Sync {
+    type Item: Serialize + DeserializeOwned + Send;
+
+    async fn snapshot(&self) -> Vec<Self::Item>;
+    // Replaces everything currently stored
+    async fn restore(&self, items: Vec<Self::Item>);
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SnapshotFile {
+    #[serde(default)]
+    wifi_configs: Vec<WifiConfig>,
+    #[serde(default)]
+    static_ip_configs: Vec<StaticIpConfig>,
+    #[serde(default)]
+    greetings: Vec<Greeting>,
+}
+
+pub struct RepositorySnapshot {
+    path: PathBuf,
+    wifi_configs: Arc<InMemoryWifiConfigRepository>,
+    static_ip_configs: Arc<InMemoryStaticIpConfigRepository>,
+    greetings: Arc<InMemoryGreetingRepository>,
+}
+
+impl RepositorySnapshot {
+    pub fn new(
+        path: impl Into<PathBuf>,
+        wifi_configs: Arc<InMemoryWifiConfigRepository>,
+        static_ip_configs: Arc<InMemoryStaticIpConfigRepository>,
+        greetings: Arc<InMemoryGreetingRepository>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            wifi_configs,
+            static_ip_configs,
+            greetings,
+        }
+    }
+
+    // Load the snapshot into the repositories; returns false when there is none yet. One that
+    // can't be parsed is moved aside, so the next save does not overwrite it.
+    pub async fn restore(&self) -> Result<bool, String> {
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(format!("Failed to read {}: {}", self.path.display(), e)),
+        };
+        let snapshot: SnapshotFile = match serde_json::from_str(&contents) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                let bad_path = self.path.with_extension("json.bad");
+                let moved = match tokio::fs::rename(&self.path, &bad_path).await {
+                    Ok(()) => format!("moved to {}", bad_path.display()),
+                    Err(e) => format!("could not be moved aside: {}", e),
+                };
+                return Err(format!("Failed to parse {} ({}): {}", self.path.display(), moved, e));
+            }
+        };
+        self.wifi_configs.restore(snapshot.wifi_configs).await;
+        self.static_ip_configs.restore(snapshot.static_ip_configs).await;
+        self.greetings.restore(snapshot.greetings).await;
+        Ok(true)
+    }
+
+    // Written to a temporary file and renamed, so a failed write keeps the previous snapshot.
+    // It holds WiFi passwords, hence mode 0600.
+    pub async fn save(&self) -> Result<(), String> {
+        use tokio::io::AsyncWriteExt;
+
+        let snapshot = SnapshotFile {
+            wifi_configs: self.wifi_configs.snapshot().await,
+            static_ip_configs: self.static_ip_configs.snapshot().await,
+            greetings: self.greetings.snapshot().await,
+        };
+        let contents = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+
+        let temp_path = self.path.with_extension("json.tmp");
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options
+            .open(&temp_path)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+        file.write_all(contents.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+        file.sync_all()
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+        tokio::fs::rename(&temp_path, &self.path)
+            .await
+            .map_err(|e| format!("Failed to replace {}: {}", self.path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::network_repositories::{StaticIpConfigRepository, WifiConfigRepository};
+    use crate::domain::network_services::NetworkConfigService;
+    use crate::domain::repositories::GreetingRepository;
+    use crate::test_support::*;
+
+    fn snapshot_in(path: &std::path::Path, network: &TestNetwork, greetings: Arc<InMemoryGreetingRepository>) -> RepositorySnapshot {
+        RepositorySnapshot::new(path, network.wifi_repository.clone(), network.static_ip_repository.clone(), greetings)
+    }
+
+    #[tokio::test]
+    async fn saved_snapshot_restores_every_repository() {
+        let path = temp_dir().join("snapshot.json");
+        let network = TestNetwork::new();
+        let service = network.service();
+        let wifi = service.create_wifi_config(wifi("Home")).await.unwrap();
+        let static_ip = service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        let greetings = Arc::new(InMemoryGreetingRepository::new());
+        let greeting = Greeting::new("Hello".to_string(), "en".to_string());
+        greetings.save(&greeting).await.unwrap();
+
+        let snapshot = snapshot_in(&path, &network, greetings);
+        assert!(!snapshot.restore().await.unwrap());
+        snapshot.save().await.unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        let restarted = TestNetwork::new();
+        let greetings = Arc::new(InMemoryGreetingRepository::new());
+        assert!(snapshot_in(&path, &restarted, greetings.clone()).restore().await.unwrap());
+        let restored_wifi = restarted.wifi_repository.find_by_id(&wifi.id).await.unwrap().unwrap();
+        assert_eq!(restored_wifi.ssid, "Home");
+        assert_eq!(restored_wifi.password, wifi.password);
+        let restored_static_ip = restarted.static_ip_repository.find_all().await.unwrap();
+        assert_eq!(restored_static_ip.len(), 1);
+        assert_eq!(restored_static_ip[0].id, static_ip.id);
+        assert_eq!(restored_static_ip[0].ip_address, static_ip.ip_address);
+        assert_eq!(greetings.find_by_id(&greeting.id).await.unwrap().unwrap().message, "Hello");
+    }
+
+    #[tokio::test]
+    async fn unreadable_snapshot_is_moved_aside() {
+        let path = temp_dir().join("snapshot.json");
+        std::fs::write(&path, "{ not json").unwrap();
+        let network = TestNetwork::new();
+        let snapshot = snapshot_in(&path, &network, Arc::new(InMemoryGreetingRepository::new()));
+
+        assert!(snapshot.restore().await.is_err());
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(path.with_extension("json.bad")).unwrap(), "{ not json");
+    }
+}
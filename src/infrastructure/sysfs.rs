@@ -0,0 +1,67 @@
+// sysfs access - per-interface attributes under /sys/class/net
+// Platforms without sysfs (e.g. macOS during development) get a reader that always returns `None`
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const SYS_CLASS_NET: &str = "/sys/class/net";
+
+#[async_trait]
+pub trait SysfsReader: Send + Sync {
+    // Trimmed contents of <interface>/<attribute>, or `None` when it can't be read
+    async fn read_attribute(&self, interface_name: &str, attribute: &str) -> Option<String>;
+}
+
+// Reads the real sysfs tree (or a copy of it rooted elsewhere)
+pub struct LinuxSysfsReader {
+    root: PathBuf,
+}
+
+impl LinuxSysfsReader {
+    pub fn new() -> Self {
+        Self::with_root(SYS_CLASS_NET)
+    }
+
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl Default for LinuxSysfsReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SysfsReader for LinuxSysfsReader {
+    async fn read_attribute(&self, interface_name: &str, attribute: &str) -> Option<String> {
+        // Keep lookups inside the interface directory
+        if interface_name.is_empty() || interface_name.contains('/') || interface_name.starts_with('.') {
+            return None;
+        }
+        let contents = tokio::fs::read_to_string(self.root.join(interface_name).join(attribute))
+            .await
+            .ok()?;
+        Some(contents.trim().to_string())
+    }
+}
+
+pub struct NoSysfsReader;
+
+#[async_trait]
+impl SysfsReader for NoSysfsReader {
+    async fn read_attribute(&self, _interface_name: &str, _attribute: &str) -> Option<String> {
+        None
+    }
+}
+
+// Pick the reader for the running system
+pub fn detect_sysfs_reader() -> Arc<dyn SysfsReader> {
+    if std::path::Path::new(SYS_CLASS_NET).is_dir() {
+        Arc::new(LinuxSysfsReader::new())
+    } else {
+        Arc::new(NoSysfsReader)
+    }
+}
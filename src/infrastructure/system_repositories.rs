@@ -0,0 +1,269 @@
+// System repository implementations
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+use crate::domain::system_entities::{IpForwarding, ProcessPrivileges};
+use crate::domain::system_repositories::{HostnameRepository, IpForwardingRepository, PrivilegeProbe};
+
+// The kernel's current hostname, which can differ from /etc/hostname until the next boot
+const KERNEL_HOSTNAME: &str = "/proc/sys/kernel/hostname";
+
+// Reads the live hostname and persists changes to /etc/hostname. Setting it also renames the
+// running system through hostnamectl, so it is only used with `apply-system`.
+pub struct EtcHostnameRepository {
+    path: PathBuf,
+}
+
+impl EtcHostnameRepository {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Default for EtcHostnameRepository {
+    fn default() -> Self {
+        Self::new("/etc/hostname")
+    }
+}
+
+#[async_trait]
+impl HostnameRepository for EtcHostnameRepository {
+    async fn get(&self) -> Result<String, String> {
+        let contents = match tokio::fs::read_to_string(KERNEL_HOSTNAME).await {
+            Ok(contents) => contents,
+            Err(_) => tokio::fs::read_to_string(&self.path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", self.path.display(), e))?,
+        };
+        Ok(contents.trim().to_string())
+    }
+
+    async fn set(&self, hostname: &str) -> Result<(), String> {
+        tokio::fs::write(&self.path, format!("{}\n", hostname))
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+
+        let output = tokio::process::Command::new("hostnamectl")
+            .args(["set-hostname", hostname])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run hostnamectl: {}", e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "hostnamectl set-hostname {} failed: {}",
+                hostname,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+}
+
+const IPV4_FORWARD: &str = "net/ipv4/ip_forward";
+const IPV6_FORWARD: &str = "net/ipv6/conf/all/forwarding";
+
+// Reads and writes the forwarding sysctls under /proc/sys, and keeps a sysctl.d drop-in in step
+// so the setting survives a reboot. Both paths are injectable to point them at a scratch tree.
+pub struct ProcSysIpForwardingRepository {
+    proc_sys: PathBuf,
+    drop_in: PathBuf,
+}
+
+impl ProcSysIpForwardingRepository {
+    pub fn new(proc_sys: impl Into<PathBuf>, drop_in: impl Into<PathBuf>) -> Self {
+        Self {
+            proc_sys: proc_sys.into(),
+            drop_in: drop_in.into(),
+        }
+    }
+
+    async fn read_flag(&self, key: &str) -> Result<Option<bool>, String> {
+        let path = self.proc_sys.join(key);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => Ok(Some(contents.trim() != "0")),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    async fn write_flag(&self, key: &str, enabled: bool) -> Result<(), String> {
+        let path = self.proc_sys.join(key);
+        tokio::fs::write(&path, if enabled { "1\n" } else { "0\n" })
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+impl Default for ProcSysIpForwardingRepository {
+    fn default() -> Self {
+        Self::new("/proc/sys", "/etc/sysctl.d/90-homelabme-forwarding.conf")
+    }
+}
+
+#[async_trait]
+impl IpForwardingRepository for ProcSysIpForwardingRepository {
+    // A kernel without IPv6 has no IPv6 sysctl; it does not forward IPv6
+    async fn get(&self) -> Result<IpForwarding, String> {
+        Ok(IpForwarding {
+            ipv4: self.read_flag(IPV4_FORWARD).await?.unwrap_or(false),
+            ipv6: self.read_flag(IPV6_FORWARD).await?.unwrap_or(false),
+        })
+    }
+
+    async fn set(&self, forwarding: &IpForwarding) -> Result<(), String> {
+        let has_ipv6 = self.read_flag(IPV6_FORWARD).await?.is_some();
+        if forwarding.ipv6 && !has_ipv6 {
+            return Err("IPv6 is not available on this system".to_string());
+        }
+        self.write_flag(IPV4_FORWARD, forwarding.ipv4).await?;
+        if has_ipv6 {
+            self.write_flag(IPV6_FORWARD, forwarding.ipv6).await?;
+        }
+
+        let mut drop_in = format!(
+            "# Written by homelabme\nnet.ipv4.ip_forward = {}\n",
+            u8::from(forwarding.ipv4)
+        );
+        if has_ipv6 {
+            drop_in.push_str(&format!("net.ipv6.conf.all.forwarding = {}\n", u8::from(forwarding.ipv6)));
+        }
+        tokio::fs::write(&self.drop_in, drop_in).await.map_err(|e| {
+            format!(
+                "IP forwarding was changed but will not survive a reboot: failed to write {}: {}",
+                self.drop_in.display(),
+                e
+            )
+        })
+    }
+}
+
+// Bit of CAP_NET_ADMIN in the capability masks, from linux/capability.h
+const CAP_NET_ADMIN: u32 = 12;
+
+// Effective UID and capabilities of this process from /proc/self/status
+pub struct ProcStatusPrivilegeProbe {
+    status_path: PathBuf,
+}
+
+impl ProcStatusPrivilegeProbe {
+    pub fn new(status_path: impl Into<PathBuf>) -> Self {
+        Self {
+            status_path: status_path.into(),
+        }
+    }
+}
+
+impl Default for ProcStatusPrivilegeProbe {
+    fn default() -> Self {
+        Self::new("/proc/self/status")
+    }
+}
+
+#[async_trait]
+impl PrivilegeProbe for ProcStatusPrivilegeProbe {
+    async fn probe(&self) -> Result<ProcessPrivileges, String> {
+        let status = tokio::fs::read_to_string(&self.status_path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", self.status_path.display(), e))?;
+        parse_proc_status_privileges(&status)
+    }
+}
+
+// `Uid:` lists the real, effective, saved and filesystem UIDs; `CapEff:` is a hex bit mask
+pub fn parse_proc_status_privileges(status: &str) -> Result<ProcessPrivileges, String> {
+    let field = |name: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+            .map(str::trim)
+            .ok_or_else(|| format!("No {} line in process status", name))
+    };
+
+    let effective_uid = field("Uid")?
+        .split_whitespace()
+        .nth(1)
+        .and_then(|uid| uid.parse::<u32>().ok())
+        .ok_or_else(|| "Malformed Uid line in process status".to_string())?;
+    let effective_caps = u64::from_str_radix(field("CapEff")?, 16)
+        .map_err(|_| "Malformed CapEff line in process status".to_string())?;
+
+    Ok(ProcessPrivileges {
+        running_as_root: effective_uid == 0,
+        has_net_admin: effective_caps & (1 << CAP_NET_ADMIN) != 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    // A scratch /proc/sys with the IPv4 sysctl, and the IPv6 one unless `ipv6` is None
+    fn proc_sys(ipv4: &str, ipv6: Option<&str>) -> (PathBuf, ProcSysIpForwardingRepository) {
+        let root = temp_dir();
+        let proc_sys = root.join("proc/sys");
+        std::fs::create_dir_all(proc_sys.join("net/ipv4")).unwrap();
+        std::fs::write(proc_sys.join(IPV4_FORWARD), ipv4).unwrap();
+        if let Some(ipv6) = ipv6 {
+            std::fs::create_dir_all(proc_sys.join("net/ipv6/conf/all")).unwrap();
+            std::fs::write(proc_sys.join(IPV6_FORWARD), ipv6).unwrap();
+        }
+        let repository = ProcSysIpForwardingRepository::new(&proc_sys, root.join("90-homelabme-forwarding.conf"));
+        (root, repository)
+    }
+
+    #[tokio::test]
+    async fn forwarding_is_written_to_proc_sys_and_the_drop_in() {
+        let (root, repository) = proc_sys("0\n", Some("0\n"));
+        assert_eq!(repository.get().await.unwrap(), IpForwarding { ipv4: false, ipv6: false });
+
+        repository.set(&IpForwarding { ipv4: true, ipv6: false }).await.unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("proc/sys").join(IPV4_FORWARD)).unwrap(), "1\n");
+        assert_eq!(std::fs::read_to_string(root.join("proc/sys").join(IPV6_FORWARD)).unwrap(), "0\n");
+        assert_eq!(
+            std::fs::read_to_string(root.join("90-homelabme-forwarding.conf")).unwrap(),
+            "# Written by homelabme\nnet.ipv4.ip_forward = 1\nnet.ipv6.conf.all.forwarding = 0\n"
+        );
+        assert_eq!(repository.get().await.unwrap(), IpForwarding { ipv4: true, ipv6: false });
+    }
+
+    #[tokio::test]
+    async fn forwarding_without_ipv6_leaves_it_out() {
+        let (root, repository) = proc_sys("1\n", None);
+        assert_eq!(repository.get().await.unwrap(), IpForwarding { ipv4: true, ipv6: false });
+
+        assert!(repository.set(&IpForwarding { ipv4: true, ipv6: true }).await.is_err());
+        repository.set(&IpForwarding { ipv4: false, ipv6: false }).await.unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("proc/sys").join(IPV4_FORWARD)).unwrap(), "0\n");
+        assert_eq!(
+            std::fs::read_to_string(root.join("90-homelabme-forwarding.conf")).unwrap(),
+            "# Written by homelabme\nnet.ipv4.ip_forward = 0\n"
+        );
+    }
+
+    fn proc_status(uid: &str, cap_eff: &str) -> String {
+        format!("Name:\thomelabme\nUmask:\t0022\nUid:\t{0}\t{0}\t{0}\t{0}\nGid:\t1000\t1000\t1000\t1000\nCapEff:\t{1}\n", uid, cap_eff)
+    }
+
+    #[tokio::test]
+    async fn privileges_are_read_from_the_process_status() {
+        let path = temp_dir().join("status");
+        std::fs::write(&path, proc_status("0", "000001ffffffffff")).unwrap();
+        let root = ProcStatusPrivilegeProbe::new(&path).probe().await.unwrap();
+        assert_eq!(root, ProcessPrivileges { running_as_root: true, has_net_admin: true });
+
+        std::fs::write(&path, proc_status("1000", "0000000000000000")).unwrap();
+        let user = ProcStatusPrivilegeProbe::new(&path).probe().await.unwrap();
+        assert_eq!(user, ProcessPrivileges { running_as_root: false, has_net_admin: false });
+        assert!(!user.can_manage_network());
+
+        // A non-root service granted CAP_NET_ADMIN (bit 12) alone
+        let granted = parse_proc_status_privileges(&proc_status("1000", "0000000000001000")).unwrap();
+        assert_eq!(granted, ProcessPrivileges { running_as_root: false, has_net_admin: true });
+        assert!(granted.can_manage_network());
+
+        assert!(parse_proc_status_privileges("Name:\thomelabme\n").is_err());
+        assert!(ProcStatusPrivilegeProbe::new(temp_dir().join("missing")).probe().await.is_err());
+    }
+}
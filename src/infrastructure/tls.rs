@@ -0,0 +1,35 @@
+// HTTPS listener - serves the router over TLS (rustls) when a certificate and key are configured
+
+use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+use std::net::SocketAddr;
+use std::path::Path;
+
+// Read a PEM certificate chain and private key, failing with a message that names both files
+pub async fn load(cert: &Path, key: &Path) -> Result<RustlsConfig, String> {
+    // rustls needs a process-wide crypto provider; installing it twice is harmless
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    RustlsConfig::from_pem_file(cert, key).await.map_err(|e| {
+        format!(
+            "Failed to load TLS certificate {} / key {}: {}",
+            cert.display(),
+            key.display(),
+            e
+        )
+    })
+}
+
+pub async fn serve(bind_address: &str, tls: RustlsConfig, app: Router) -> Result<(), String> {
+    let listener = std::net::TcpListener::bind(bind_address)
+        .map_err(|e| format!("Failed to bind {}: {}", bind_address, e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to configure listener on {}: {}", bind_address, e))?;
+
+    // Connection info gives the admin allowlist the real peer address
+    axum_server::from_tcp_rustls(listener, tls)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .map_err(|e| format!("HTTPS server failed: {}", e))
+}
@@ -0,0 +1,98 @@
+// Unix domain socket listener - for running behind a local reverse proxy without a TCP port.
+// axum::serve only accepts TCP listeners, so connections are handed to hyper directly.
+
+use axum::Router;
+use hyper_util::rt::TokioIo;
+use hyper_util::service::TowerToHyperService;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::Path;
+use tokio::net::UnixListener;
+
+// Owner and group (e.g. the reverse proxy's group) may connect; nobody else
+const SOCKET_MODE: u32 = 0o660;
+
+// Bind the socket, replacing a stale one left behind by a previous run.
+// Refuses to remove anything at `path` that is not a socket.
+pub fn bind(path: &Path) -> Result<UnixListener, String> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            std::fs::remove_file(path)
+                .map_err(|e| format!("Failed to remove stale socket {}: {}", path.display(), e))?;
+        }
+        Ok(_) => return Err(format!("{} exists and is not a socket", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(format!("Failed to inspect {}: {}", path.display(), e)),
+    }
+
+    let listener = UnixListener::bind(path)
+        .map_err(|e| format!("Failed to bind {}: {}", path.display(), e))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(SOCKET_MODE))
+        .map_err(|e| format!("Failed to set permissions on {}: {}", path.display(), e))?;
+    Ok(listener)
+}
+
+// Serve HTTP/1.1 on every accepted connection until the process exits.
+// No ConnectInfo is attached, since a Unix socket peer has no IP address.
+pub async fn serve(listener: UnixListener, app: Router) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                tracing::warn!("Failed to accept Unix socket connection: {}", e);
+                continue;
+            }
+        };
+
+        let service = TowerToHyperService::new(app.clone());
+        tokio::spawn(async move {
+            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!("Unix socket connection closed with error: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{temp_dir, TestApp};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn requests_are_served_over_the_socket() {
+        let path = temp_dir().join("homelabme.sock");
+        let listener = bind(&path).unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, SOCKET_MODE);
+        let server = tokio::spawn(serve(listener, TestApp::new().router()));
+
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        stream
+            .write_all(b"GET /api/version HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        server.abort();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains(&format!("\"version\":\"{}\"", env!("CARGO_PKG_VERSION"))), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn stale_sockets_are_replaced_but_other_files_are_kept() {
+        let dir = temp_dir();
+        let socket = dir.join("stale.sock");
+        drop(bind(&socket).unwrap());
+        // The previous listener is gone but its socket file is still there
+        assert!(socket.exists());
+        bind(&socket).unwrap();
+
+        let file = dir.join("not-a-socket");
+        std::fs::write(&file, "keep me").unwrap();
+        assert!(bind(&file).unwrap_err().ends_with("exists and is not a socket"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "keep me");
+    }
+}
@@ -1,17 +1,39 @@
 // Web infrastructure - Axum handlers and routing
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{Html, Json},
-    routing::{get, post, delete},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Json, Response,
+    },
+    middleware,
+    routing::{get, post, put, delete},
     Router,
 };
+use std::convert::Infallible;
 use std::sync::Arc;
+use tokio_stream::wrappers::BroadcastStream;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::timeout::TimeoutLayer;
+use tokio_stream::{Stream, StreamExt};
+use crate::application::interface_monitor::InterfaceMonitor;
 use crate::application::use_cases::*;
 use crate::application::dto::*;
 use crate::application::network_use_cases::*;
 use crate::application::network_dto::*;
+use crate::application::system_use_cases::*;
+use crate::application::system_dto::*;
+use crate::domain::network_entities::{InterfaceEvent, WifiSecurityType};
+use crate::domain::errors::GreetingError;
+use crate::domain::network_errors::NetworkError;
+use crate::infrastructure::log_buffer::{LogBuffer, LogRecord, LOG_BUFFER_CAPACITY};
+use crate::infrastructure::middleware::{
+    admin_allowlist_middleware, cache_control_middleware, idempotency_middleware, metrics_middleware, pretty_json_middleware,
+    problem_json_middleware, rate_limit_middleware, require_admin_token_middleware, ProblemDetails,
+    request_id_middleware, AdminAllowlist, AdminToken, IdempotencyCache, RateLimiter, IDEMPOTENCY_KEY_HEADER,
+};
+use crate::infrastructure::metrics::RequestMetrics;
 
 // Application state containing use cases
 #[derive(Clone)]
@@ -19,48 +41,287 @@ pub struct AppState {
     pub get_default_greeting_use_case: Arc<dyn GetDefaultGreetingUseCase>,
     pub create_greeting_use_case: Arc<dyn CreateGreetingUseCase>,
     pub list_greetings_use_case: Arc<dyn ListGreetingsUseCase>,
+    pub render_greeting_use_case: Arc<dyn RenderGreetingUseCase>,
     // Network use cases
     pub get_network_settings_use_case: Arc<dyn GetNetworkSettingsUseCase>,
     pub create_wifi_config_use_case: Arc<dyn CreateWifiConfigUseCase>,
+    pub validate_wifi_config_use_case: Arc<dyn ValidateWifiConfigUseCase>,
+    pub get_wifi_config_use_case: Arc<dyn GetWifiConfigUseCase>,
+    pub list_wifi_configs_use_case: Arc<dyn ListWifiConfigsUseCase>,
     pub activate_wifi_config_use_case: Arc<dyn ActivateWifiConfigUseCase>,
+    pub switch_wifi_use_case: Arc<dyn SwitchWifiUseCase>,
+    pub connect_best_wifi_use_case: Arc<dyn ConnectBestWifiUseCase>,
     pub delete_wifi_config_use_case: Arc<dyn DeleteWifiConfigUseCase>,
+    pub set_wifi_auto_connect_use_case: Arc<dyn SetWifiAutoConnectUseCase>,
+    pub list_static_ip_configs_use_case: Arc<dyn ListStaticIpConfigsUseCase>,
     pub create_static_ip_config_use_case: Arc<dyn CreateStaticIpConfigUseCase>,
+    pub batch_create_static_ip_configs_use_case: Arc<dyn BatchCreateStaticIpConfigsUseCase>,
+    pub validate_static_ip_config_use_case: Arc<dyn ValidateStaticIpConfigUseCase>,
+    pub precheck_static_ip_config_use_case: Arc<dyn PrecheckStaticIpConfigUseCase>,
     pub enable_static_ip_config_use_case: Arc<dyn EnableStaticIpConfigUseCase>,
+    pub reorder_static_ip_dns_use_case: Arc<dyn ReorderStaticIpDnsUseCase>,
+    pub replace_static_ip_dns_use_case: Arc<dyn ReplaceStaticIpDnsUseCase>,
+    pub confirm_static_ip_config_use_case: Arc<dyn ConfirmStaticIpConfigUseCase>,
     pub disable_static_ip_config_use_case: Arc<dyn DisableStaticIpConfigUseCase>,
     pub delete_static_ip_config_use_case: Arc<dyn DeleteStaticIpConfigUseCase>,
+    pub set_interface_admin_state_use_case: Arc<dyn SetInterfaceAdminStateUseCase>,
+    pub get_interface_rate_use_case: Arc<dyn GetInterfaceRateUseCase>,
+    pub get_all_interface_stats_use_case: Arc<dyn GetAllInterfaceStatsUseCase>,
+    pub get_grouped_network_interfaces_use_case: Arc<dyn GetGroupedNetworkInterfacesUseCase>,
+    pub get_network_interface_use_case: Arc<dyn GetNetworkInterfaceUseCase>,
+    pub get_wifi_link_status_use_case: Arc<dyn GetWifiLinkStatusUseCase>,
+    pub sample_wifi_signal_use_case: Arc<dyn SampleWifiSignalUseCase>,
+    pub get_default_route_use_case: Arc<dyn GetDefaultRouteUseCase>,
+    pub probe_ip_use_case: Arc<dyn ProbeIpUseCase>,
+    pub get_dns_latency_use_case: Arc<dyn GetDnsLatencyUseCase>,
+    pub get_address_conflicts_use_case: Arc<dyn GetAddressConflictsUseCase>,
+    pub create_network_profile_use_case: Arc<dyn CreateNetworkProfileUseCase>,
+    pub list_network_profiles_use_case: Arc<dyn ListNetworkProfilesUseCase>,
+    pub activate_profile_use_case: Arc<dyn ActivateProfileUseCase>,
+    pub get_interface_overview_use_case: Arc<dyn GetInterfaceOverviewUseCase>,
+    pub export_netplan_use_case: Arc<dyn ExportNetplanUseCase>,
     pub scan_wifi_networks_use_case: Arc<dyn ScanWifiNetworksUseCase>,
+    pub get_network_audit_use_case: Arc<dyn GetNetworkAuditUseCase>,
+    pub import_configs_use_case: Arc<dyn ImportConfigsUseCase>,
+    pub restart_networking_use_case: Arc<dyn RestartNetworkingUseCase>,
+    pub get_event_history_use_case: Arc<dyn GetEventHistoryUseCase>,
+    pub get_network_preferences_use_case: Arc<dyn GetNetworkPreferencesUseCase>,
+    pub set_network_preferences_use_case: Arc<dyn SetNetworkPreferencesUseCase>,
+    pub get_current_network_use_case: Arc<dyn GetCurrentNetworkUseCase>,
+    pub get_hostname_use_case: Arc<dyn GetHostnameUseCase>,
+    pub set_hostname_use_case: Arc<dyn SetHostnameUseCase>,
+    pub get_ip_forwarding_use_case: Arc<dyn GetIpForwardingUseCase>,
+    pub set_ip_forwarding_use_case: Arc<dyn SetIpForwardingUseCase>,
+    pub get_capabilities_use_case: Arc<dyn GetCapabilitiesUseCase>,
+    pub interface_monitor: Arc<InterfaceMonitor>,
+    pub log_buffer: Arc<LogBuffer>,
+    pub admin_allowlist: Arc<AdminAllowlist>,
+    pub admin_token: Arc<AdminToken>,
+    pub idempotency_cache: Arc<IdempotencyCache>,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub request_metrics: Arc<RequestMetrics>,
+    // Browser origins allowed to call the API cross-origin; empty adds no CORS headers
+    pub cors_origins: Vec<HeaderValue>,
+    // Serve the settings page at / and Prometheus metrics at /metrics
+    pub web_ui: bool,
+    pub metrics_endpoint: bool,
+    // Limit for a single request outside the streaming endpoints; `None` never times out
+    pub request_timeout: Option<std::time::Duration>,
+    // Process start, for GET /api/version
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub started: std::time::Instant,
+}
+
+// Error returned by handlers that report why a request failed as `{"error": "..."}`, or as RFC 7807
+// problem details for clients that accept `application/problem+json` (see `problem_json_middleware`)
+pub struct ApiError {
+    status: StatusCode,
+    // Last segment of the problem `type` URI; part of the API, so never rename one
+    kind: &'static str,
+    message: String,
+}
+
+impl From<NetworkError> for ApiError {
+    fn from(error: NetworkError) -> Self {
+        let (status, kind) = match &error {
+            NetworkError::Validation(_) => (StatusCode::BAD_REQUEST, "validation"),
+            NetworkError::NotFound(_) => (StatusCode::NOT_FOUND, "not-found"),
+            NetworkError::Conflict(_) => (StatusCode::CONFLICT, "conflict"),
+            NetworkError::Forbidden(_) => (StatusCode::FORBIDDEN, "forbidden"),
+            NetworkError::Timeout(_) => (StatusCode::GATEWAY_TIMEOUT, "timeout"),
+            NetworkError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+        };
+        Self { status, kind, message: error.to_string() }
+    }
+}
+
+impl From<GreetingError> for ApiError {
+    fn from(error: GreetingError) -> Self {
+        let (status, kind) = match &error {
+            GreetingError::Validation(_) => (StatusCode::BAD_REQUEST, "validation"),
+            GreetingError::NotFound(_) => (StatusCode::NOT_FOUND, "not-found"),
+            GreetingError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+        };
+        Self { status, kind, message: error.to_string() }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if self.status.is_server_error() {
+            tracing::error!("{}", self.message);
+        }
+        let problem = ProblemDetails::new(self.status, self.kind, self.message.clone());
+        let mut response = (self.status, Json(serde_json::json!({ "error": self.message }))).into_response();
+        response.extensions_mut().insert(problem);
+        response
+    }
 }
 
 // Create the router with all routes
 pub fn create_router(state: AppState) -> Router {
-    Router::new()
-        .route("/", get(network_settings_handler))
+    let mut api = Router::new()
         .route("/api/greetings", get(list_greetings_handler))
         .route("/api/greetings", post(create_greeting_handler))
         .route("/api/greetings/default", get(get_default_greeting_handler))
+        .route("/api/greetings/:id/render", post(render_greeting_handler))
         // Network API handlers
         .route("/api/network/settings", get(get_network_settings_api_handler))
-        .route("/api/network/wifi", post(create_wifi_config_handler))
+        .route("/api/network/wifi", get(list_wifi_configs_handler).post(create_wifi_config_handler))
+        .route("/api/network/wifi/validate", post(validate_wifi_config_handler))
         .route("/api/network/wifi/scan", get(scan_wifi_networks_handler))
+        .route("/api/network/wifi/connect-best", post(connect_best_wifi_handler))
+        .route("/api/network/wifi/switch", post(switch_wifi_handler))
         .route("/api/network/wifi/:id/activate", post(activate_wifi_config_handler))
-        .route("/api/network/wifi/:id", delete(delete_wifi_config_handler))
-        .route("/api/network/static-ip", post(create_static_ip_config_handler))
+        .route("/api/network/wifi/:id/auto-connect", post(set_wifi_auto_connect_handler))
+        .route("/api/network/wifi/:id", get(get_wifi_config_handler).delete(delete_wifi_config_handler))
+        .route("/api/network/static-ip", get(list_static_ip_configs_handler).post(create_static_ip_config_handler))
+        .route("/api/network/static-ip/batch", post(batch_create_static_ip_configs_handler))
+        .route("/api/network/static-ip/validate", post(validate_static_ip_config_handler))
+        .route("/api/network/static-ip/precheck", post(precheck_static_ip_config_handler))
         .route("/api/network/static-ip/:id/enable", post(enable_static_ip_config_handler))
+        .route("/api/network/static-ip/:id/confirm", post(confirm_static_ip_config_handler))
+        .route("/api/network/static-ip/:id/dns-order", post(reorder_static_ip_dns_handler))
+        .route("/api/network/static-ip/:id/dns", put(replace_static_ip_dns_handler))
         .route("/api/network/static-ip/:id/disable", post(disable_static_ip_config_handler))
         .route("/api/network/static-ip/:id", delete(delete_static_ip_config_handler))
-        .with_state(state)
+        .route("/api/network/interfaces/stats", get(get_all_interface_stats_handler))
+        .route("/api/network/interfaces/grouped", get(get_grouped_network_interfaces_handler))
+        .route("/api/network/interfaces/:name", get(get_network_interface_handler))
+        .route("/api/network/interfaces/:name/state", post(set_interface_admin_state_handler))
+        .route("/api/network/interfaces/:name/rate", get(get_interface_rate_handler))
+        .route("/api/network/interfaces/:name/flaps", get(get_interface_flaps_handler))
+        .route("/api/network/interfaces/:name/wifi-link", get(get_wifi_link_status_handler))
+        .route("/api/network/default-route", get(get_default_route_handler))
+        .route("/api/network/probe-ip", post(probe_ip_handler))
+        .route("/api/network/dns-latency", get(get_dns_latency_handler))
+        .route("/api/network/conflicts", get(get_address_conflicts_handler))
+        .route("/api/network/profiles", get(list_network_profiles_handler).post(create_network_profile_handler))
+        .route("/api/network/profiles/:id/activate", post(activate_profile_handler))
+        .route("/api/network/by-interface", get(get_interface_overview_handler))
+        .route("/api/network/current", get(get_current_network_handler))
+        .route(
+            "/api/network/settings/preferences",
+            get(get_network_preferences_handler).put(set_network_preferences_handler),
+        )
+        .route("/api/network/audit", get(get_network_audit_handler))
+        .route("/api/network/import", post(import_configs_handler))
+        .route("/api/network/restart", post(restart_networking_handler))
+        .route("/api/network/export/netplan", get(export_netplan_handler))
+        .route("/api/network/events/history", get(event_history_handler))
+        .route("/api/system/hostname", get(get_hostname_handler).put(set_hostname_handler))
+        .route("/api/system/ip-forwarding", get(get_ip_forwarding_handler).put(set_ip_forwarding_handler))
+        .route("/api/system/capabilities", get(get_capabilities_handler))
+        .route(
+            "/api/logs",
+            get(logs_handler).route_layer(middleware::from_fn_with_state(
+                state.admin_token.clone(),
+                require_admin_token_middleware,
+            )),
+        )
+        .route("/api/version", get(version_handler));
+    if state.web_ui {
+        api = api
+            .route("/", get(network_settings_handler))
+            .route("/assets/tailwind.css", get(tailwind_css_handler))
+            .route("/favicon.ico", get(favicon_handler));
+    }
+    if state.metrics_endpoint {
+        api = api.route("/metrics", get(metrics_handler));
+    }
+    if let Some(timeout) = state.request_timeout {
+        api = api.layer(TimeoutLayer::with_status_code(StatusCode::GATEWAY_TIMEOUT, timeout));
+    }
+
+    // Long-lived streams stay open until the client leaves, so they are kept out of the timeout
+    let streams = Router::new()
+        .route("/api/network/events/sse", get(interface_events_sse_handler))
+        .route("/api/network/wifi-signal", get(wifi_signal_sse_handler));
+
+    let mut router = api
+        .merge(streams)
+        .layer(middleware::from_fn(cache_control_middleware))
+        .layer(middleware::from_fn_with_state(
+            state.idempotency_cache.clone(),
+            idempotency_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.rate_limiter.clone(),
+            rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.admin_allowlist.clone(),
+            admin_allowlist_middleware,
+        ))
+        .layer(middleware::from_fn(problem_json_middleware))
+        .layer(middleware::from_fn(pretty_json_middleware))
+        .layer(middleware::from_fn_with_state(
+            state.request_metrics.clone(),
+            metrics_middleware,
+        ))
+        .layer(middleware::from_fn(request_id_middleware));
+    // Outermost, so preflight requests are answered before the allowlist or rate limit see them
+    if !state.cors_origins.is_empty() {
+        router = router.layer(
+            CorsLayer::new()
+                .allow_origin(AllowOrigin::list(state.cors_origins.clone()))
+                .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+                .allow_headers([
+                    header::CONTENT_TYPE,
+                    header::AUTHORIZATION,
+                    HeaderName::from_static(IDEMPOTENCY_KEY_HEADER),
+                ]),
+        );
+    }
+    router.with_state(state)
 }
 
 
 
+// JSON for a value embedded in the page's <script>. SSIDs, interface names and the like can
+// contain "</script>", so every '<' is escaped; it is the same string to the JavaScript parser.
+fn script_json<T: serde::Serialize>(value: &T, fallback: &str) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_else(|_| fallback.to_string())
+        .replace('<', "\\u003c")
+}
+
 // Network settings page handler
 async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<String>, StatusCode> {
-    match state.get_network_settings_use_case.execute().await {
+    match state.get_network_settings_use_case.execute(false).await {
         Ok(data) => {
-            let wifi_configs_json = serde_json::to_string(&data.wifi_configs).unwrap_or_else(|_| "[]".to_string());
-            let static_ip_configs_json = serde_json::to_string(&data.static_ip_configs).unwrap_or_else(|_| "[]".to_string());
-            let interfaces_json = serde_json::to_string(&data.network_interfaces).unwrap_or_else(|_| "[]".to_string());
-            let active_wifi_json = serde_json::to_string(&data.active_wifi).unwrap_or_else(|_| "null".to_string());
+            let wifi_configs_json = script_json(&data.wifi_configs, "[]");
+            let static_ip_configs_json = script_json(&data.static_ip_configs, "[]");
+            let interfaces_json = script_json(&data.network_interfaces, "[]");
+            let active_wifi_json = script_json(&data.active_wifi, "null");
+            let recent_scan_json = script_json(&data.recent_scan, "null");
+            let warnings_html: String = data
+                .warnings
+                .iter()
+                .map(|warning| {
+                    let warning = warning.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+                    format!(
+                        r#"<div class="bg-yellow-500/20 border border-yellow-400/40 text-yellow-100 rounded-lg px-4 py-3 mb-4">⚠️ {}</div>"#,
+                        warning
+                    )
+                })
+                .collect();
+            // Rendered as the fields' default values, so resetting a form after a save restores them
+            let security_options: String = [
+                WifiSecurityType::WPA2,
+                WifiSecurityType::WPA3,
+                WifiSecurityType::WPA,
+                WifiSecurityType::WEP,
+                WifiSecurityType::Open,
+            ]
+            .iter()
+            .map(|security_type| {
+                let selected = if *security_type == data.form_defaults.security_type { " selected" } else { "" };
+                format!(r#"<option value="{0}"{1}>{0}</option>"#, security_type.as_str(), selected)
+            })
+            .collect();
+            let dns_primary_value = data.form_defaults.dns_primary.as_deref().unwrap_or("");
+            let dns_secondary_value = data.form_defaults.dns_secondary.as_deref().unwrap_or("");
             
             let html = format!(
                 r#"
@@ -70,19 +331,8 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                     <meta charset="UTF-8">
                     <meta name="viewport" content="width=device-width, initial-scale=1.0">
                     <title>Homelabme - Network Settings</title>
-                    <script src="https://cdn.tailwindcss.com"></script>
-                    <script>
-                        tailwind.config = {{
-                            theme: {{
-                                extend: {{
-                                    colors: {{
-                                        primary: '#667eea',
-                                        secondary: '#764ba2'
-                                    }}
-                                }}
-                            }}
-                        }}
-                    </script>
+                    <link rel="icon" href="/favicon.ico">
+                    <link rel="stylesheet" href="/assets/tailwind.css">
                 </head>
                 <body class="bg-gradient-to-br from-primary to-secondary min-h-screen">
                     <!-- Navigation -->
@@ -106,6 +356,7 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                             <h2 class="text-3xl font-bold text-white mb-2">Network Settings</h2>
                             <p class="text-white/80">Configure WiFi and static IP settings for your homelab server</p>
                         </div>
+                        {warnings_html}
 
                         <!-- Network Interfaces Status -->
                         <div class="bg-white/10 backdrop-blur-md rounded-lg p-6 mb-8 border border-white/20">
@@ -153,6 +404,10 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                             <input type="text" id="wifi-ssid-custom" placeholder="Or enter custom SSID..."
                                                    class="w-full px-3 py-2 bg-white/20 border border-white/30 rounded-md text-white placeholder-white/60 focus:outline-none focus:ring-2 focus:ring-white/50 focus:border-transparent">
                                         </div>
+                                        <label for="wifi-hidden" class="mt-2 flex items-center text-sm text-white/90">
+                                            <input type="checkbox" id="wifi-hidden" disabled class="mr-2">
+                                            Hidden network (custom SSID only)
+                                        </label>
                                     </div>
                                     <div>
                                         <label for="wifi-password" class="block text-sm font-medium text-white/90 mb-2">Password</label>
@@ -163,11 +418,17 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                         <label for="wifi-security" class="block text-sm font-medium text-white/90 mb-2">Security Type</label>
                                         <select id="wifi-security" name="security_type"
                                                 class="w-full px-3 py-2 bg-white/20 border border-white/30 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-white/50 focus:border-transparent">
-                                            <option value="WPA2">WPA2</option>
-                                            <option value="WPA3">WPA3</option>
-                                            <option value="WPA">WPA</option>
-                                            <option value="WEP">WEP</option>
-                                            <option value="Open">Open</option>
+                                            {security_options}
+                                        </select>
+                                    </div>
+                                    <div>
+                                        <label for="wifi-band" class="block text-sm font-medium text-white/90 mb-2">Band</label>
+                                        <select id="wifi-band" name="preferred_band"
+                                                class="w-full px-3 py-2 bg-white/20 border border-white/30 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-white/50 focus:border-transparent">
+                                            <option value="">Any</option>
+                                            <option value="2.4GHz">2.4GHz only</option>
+                                            <option value="5GHz">5GHz only</option>
+                                            <option value="6GHz">6GHz only</option>
                                         </select>
                                     </div>
                                     <button type="submit"
@@ -221,15 +482,20 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                     <div class="grid grid-cols-1 sm:grid-cols-2 gap-4">
                                         <div>
                                             <label for="dns-primary" class="block text-sm font-medium text-white/90 mb-2">Primary DNS</label>
-                                            <input type="text" id="dns-primary" name="dns_primary" required placeholder="8.8.8.8"
+                                            <input type="text" id="dns-primary" name="dns_primary" required placeholder="8.8.8.8" value="{dns_primary_value}"
                                                    class="w-full px-3 py-2 bg-white/20 border border-white/30 rounded-md text-white placeholder-white/60 focus:outline-none focus:ring-2 focus:ring-white/50 focus:border-transparent">
                                         </div>
                                         <div>
                                             <label for="dns-secondary" class="block text-sm font-medium text-white/90 mb-2">Secondary DNS</label>
-                                            <input type="text" id="dns-secondary" name="dns_secondary" placeholder="8.8.4.4"
+                                            <input type="text" id="dns-secondary" name="dns_secondary" placeholder="8.8.4.4" value="{dns_secondary_value}"
                                                    class="w-full px-3 py-2 bg-white/20 border border-white/30 rounded-md text-white placeholder-white/60 focus:outline-none focus:ring-2 focus:ring-white/50 focus:border-transparent">
                                         </div>
                                     </div>
+                                    <div>
+                                        <label for="search-domains" class="block text-sm font-medium text-white/90 mb-2">Search Domains</label>
+                                        <input type="text" id="search-domains" name="search_domains" placeholder="home.lan, lab.home.lan"
+                                               class="w-full px-3 py-2 bg-white/20 border border-white/30 rounded-md text-white placeholder-white/60 focus:outline-none focus:ring-2 focus:ring-white/50 focus:border-transparent">
+                                    </div>
                                     <button type="submit"
                                             class="w-full bg-white/20 hover:bg-white/30 text-white font-medium py-2 px-4 rounded-md transition-colors focus:outline-none focus:ring-2 focus:ring-white/50">
                                         Add Static IP Configuration
@@ -254,10 +520,11 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
 
                     <script>
                         // Initial data from server
-                        const wifiConfigs = {wifi_configs_json};
-                        const staticIpConfigs = {static_ip_configs_json};
+                        let wifiConfigs = {wifi_configs_json};
+                        let staticIpConfigs = {static_ip_configs_json};
                         const networkInterfaces = {interfaces_json};
                         const activeWifi = {active_wifi_json};
+                        const recentScan = {recent_scan_json};
 
                         // Toast notification function
                         function showToast(message, type = 'success') {{
@@ -273,6 +540,26 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                             }}, 3000);
                         }}
 
+                        // Append whether the change was actually pushed to the system
+                        // Append the server's {{"error": "..."}} reason when there is one
+                        async function errorMessage(message, response) {{
+                            const body = await response.json().catch(() => null);
+                            return body && body.error ? `${{message}}: ${{body.error}}` : message;
+                        }}
+
+                        function applyMessage(message, result) {{
+                            if (result && result.already_active) {{
+                                return `${{message}} (already active)`;
+                            }}
+                            if (result && !result.applied) {{
+                                return `${{message}} (not applied: ${{result.detail || 'unknown reason'}})`;
+                            }}
+                            if (result && result.warnings && result.warnings.length) {{
+                                return `${{message}} (${{result.warnings.join('; ')}})`;
+                            }}
+                            return message;
+                        }}
+
                         // Store all interfaces globally for filtering
                         let allInterfaces = [...networkInterfaces];
                         let filteredInterfaces = [...networkInterfaces];
@@ -298,18 +585,25 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                     ipDisplay = `<div>IP: ${{iface.current_ip}}</div>`;
                                 }}
                                 
+                                // Admin up without a link (e.g. no cable) gets its own badge
+                                const noCarrier = iface.is_up && iface.has_carrier === false;
+                                const statusClass = noCarrier
+                                    ? 'bg-yellow-500/20 text-yellow-300'
+                                    : (iface.is_up ? 'bg-green-500/20 text-green-300' : 'bg-red-500/20 text-red-300');
+                                const statusLabel = noCarrier ? 'NO CARRIER' : (iface.is_up ? 'UP' : 'DOWN');
+
                                 // Interface status card
                                 const card = document.createElement('div');
                                 card.className = 'bg-white/10 rounded-lg p-4 border border-white/20';
                                 card.innerHTML = `
                                     <div class="flex items-center justify-between mb-2">
                                         <span class="font-medium text-white">${{iface.name}}</span>
-                                        <span class="px-2 py-1 rounded text-xs ${{iface.is_up ? 'bg-green-500/20 text-green-300' : 'bg-red-500/20 text-red-300'}}">
-                                            ${{iface.is_up ? 'UP' : 'DOWN'}}
+                                        <span class="px-2 py-1 rounded text-xs ${{statusClass}}">
+                                            ${{statusLabel}}
                                         </span>
                                     </div>
                                     <div class="text-sm text-white/70">
-                                        <div>Type: ${{iface.interface_type}}</div>
+                                        <div>Type: ${{iface.display_name}}</div>
                                         <div>MAC: ${{iface.mac_address}}</div>
                                         ${{ipDisplay}}
                                     </div>
@@ -319,10 +613,10 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                             
                             // Always populate select with all interfaces (not filtered)
                             allInterfaces.forEach(iface => {{
-                                if (iface.interface_type !== 'Loopback') {{
+                                if (iface.category !== 'loopback') {{
                                     const option = document.createElement('option');
                                     option.value = iface.name;
-                                    option.textContent = `${{iface.name}} (${{iface.interface_type}})`;
+                                    option.textContent = `${{iface.name}} (${{iface.display_name}})`;
                                     interfaceSelect.appendChild(option);
                                 }}
                             }});
@@ -364,10 +658,14 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                 item.innerHTML = `
                                     <div class="flex items-center justify-between mb-2">
                                         <span class="font-medium text-white">${{config.ssid}}</span>
-                                        ${{config.is_active ? '<span class="px-2 py-1 bg-green-500/20 text-green-300 rounded text-xs">ACTIVE</span>' : ''}}
+                                        <span class="space-x-1">
+                                            ${{config.last_error ? '<span class="px-2 py-1 bg-red-500/20 text-red-300 rounded text-xs">FAILED</span>' : ''}}
+                                            ${{config.is_active ? '<span class="px-2 py-1 bg-green-500/20 text-green-300 rounded text-xs">ACTIVE</span>' : ''}}
+                                        </span>
                                     </div>
                                     <div class="text-sm text-white/70 mb-3">
-                                        Security: ${{config.security_type}}
+                                        Security: ${{config.security_type}}${{config.hidden ? ' · Hidden' : ''}}${{config.auto_connect === false ? ' · Manual connect' : ''}}${{config.preferred_band ? ` · ${{config.preferred_band}} only` : ''}}
+                                        ${{config.last_error ? `<div class="text-red-300">Last error: ${{config.last_error}}</div>` : ''}}
                                     </div>
                                     <div class="flex space-x-2">
                                         ${{!config.is_active ? `<button onclick="activateWifi('${{config.id}}')" class="px-3 py-1 bg-blue-500/20 text-blue-300 rounded text-sm hover:bg-blue-500/30 transition-colors">Activate</button>` : ''}}
@@ -394,14 +692,22 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                 item.innerHTML = `
                                     <div class="flex items-center justify-between mb-2">
                                         <span class="font-medium text-white">${{config.interface_name}}</span>
-                                        <span class="px-2 py-1 rounded text-xs ${{config.is_enabled ? 'bg-green-500/20 text-green-300' : 'bg-gray-500/20 text-gray-300'}}">
-                                            ${{config.is_enabled ? 'ENABLED' : 'DISABLED'}}
+                                        <span class="space-x-1">
+                                            ${{config.last_error ? '<span class="px-2 py-1 bg-red-500/20 text-red-300 rounded text-xs">FAILED</span>' : ''}}
+                                            ${{config.interface_present === false ? '<span class="px-2 py-1 bg-yellow-500/20 text-yellow-300 rounded text-xs">NO INTERFACE</span>' : ''}}
+                                            <span class="px-2 py-1 rounded text-xs ${{config.is_enabled ? 'bg-green-500/20 text-green-300' : 'bg-gray-500/20 text-gray-300'}}">
+                                                ${{config.is_enabled ? 'ENABLED' : 'DISABLED'}}
+                                            </span>
                                         </span>
                                     </div>
                                     <div class="text-sm text-white/70 mb-3">
                                         <div>IP: ${{config.ip_address}}/${{config.subnet_mask}}</div>
                                         <div>Gateway: ${{config.gateway}}</div>
                                         <div>DNS: ${{config.dns_primary}}${{config.dns_secondary ? `, ${{config.dns_secondary}}` : ''}}</div>
+                                        ${{config.search_domains && config.search_domains.length ? `<div>Search: ${{config.search_domains.join(', ')}}</div>` : ''}}
+                                        ${{config.is_enabled && config.enabled_at ? `<div>Enabled since ${{new Date(config.enabled_at).toLocaleString()}}</div>` : ''}}
+                                        ${{!config.is_enabled && config.disabled_at ? `<div>Disabled since ${{new Date(config.disabled_at).toLocaleString()}}</div>` : ''}}
+                                        ${{config.last_error ? `<div class="text-red-300">Last error: ${{config.last_error}}</div>` : ''}}
                                     </div>
                                     <div class="flex space-x-2">
                                         ${{config.is_enabled ? 
@@ -417,6 +723,39 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
 
 
 
+                        // Re-render only the lists a change touched; changes in quick succession share one fetch
+                        const pendingRefresh = new Set();
+                        let refreshTimer = null;
+
+                        function scheduleRefresh(section) {{
+                            pendingRefresh.add(section);
+                            clearTimeout(refreshTimer);
+                            refreshTimer = setTimeout(refreshSections, 300);
+                        }}
+
+                        async function refreshSections() {{
+                            const sections = [...pendingRefresh];
+                            pendingRefresh.clear();
+                            try {{
+                                if (sections.includes('wifi')) {{
+                                    const response = await fetch('/api/network/wifi');
+                                    if (response.ok) {{
+                                        wifiConfigs = (await response.json()).configs;
+                                        populateWifiConfigs();
+                                    }}
+                                }}
+                                if (sections.includes('static-ip')) {{
+                                    const response = await fetch('/api/network/static-ip');
+                                    if (response.ok) {{
+                                        staticIpConfigs = (await response.json()).configs;
+                                        populateStaticIpConfigs();
+                                    }}
+                                }}
+                            }} catch (error) {{
+                                showToast('Error refreshing configurations', 'error');
+                            }}
+                        }}
+
                         // Static IP form submission
                         document.getElementById('static-ip-form').addEventListener('submit', async (e) => {{
                             e.preventDefault();
@@ -427,7 +766,8 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                 subnet_mask: formData.get('subnet_mask'),
                                 gateway: formData.get('gateway'),
                                 dns_primary: formData.get('dns_primary'),
-                                dns_secondary: formData.get('dns_secondary') || null
+                                dns_secondary: formData.get('dns_secondary') || null,
+                                search_domains: (formData.get('search_domains') || '').split(/[\s,]+/).filter(Boolean)
                             }};
                             
                             try {{
@@ -440,11 +780,16 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                 }});
                                 
                                 if (response.ok) {{
-                                    showToast('Static IP configuration added successfully!');
+                                    const created = await response.json();
+                                    if (created.warnings.length) {{
+                                        showToast(`Static IP configuration added. Warning: ${{created.warnings.join(' ')}}`, 'error');
+                                    }} else {{
+                                        showToast('Static IP configuration added successfully!');
+                                    }}
                                     e.target.reset();
-                                    setTimeout(() => location.reload(), 1000);
+                                    scheduleRefresh('static-ip');
                                 }} else {{
-                                    showToast('Failed to add static IP configuration', 'error');
+                                    showToast(await errorMessage('Failed to add static IP configuration', response), 'error');
                                 }}
                             }} catch (error) {{
                                 showToast('Error adding static IP configuration', 'error');
@@ -459,8 +804,8 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                 }});
                                 
                                 if (response.ok) {{
-                                    showToast('WiFi configuration activated!');
-                                    setTimeout(() => location.reload(), 1000);
+                                    showToast(applyMessage('WiFi configuration activated!', await response.json()));
+                                    scheduleRefresh('wifi');
                                 }} else {{
                                     showToast('Failed to activate WiFi configuration', 'error');
                                 }}
@@ -472,13 +817,19 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                         async function deleteWifi(id) {{
                             if (confirm('Are you sure you want to delete this WiFi configuration?')) {{
                                 try {{
-                                    const response = await fetch(`/api/network/wifi/${{id}}`, {{
+                                    let response = await fetch(`/api/network/wifi/${{id}}`, {{
                                         method: 'DELETE'
                                     }});
+                                    // The active network needs an explicit force, which disconnects first
+                                    if (response.status === 409 && confirm('This network is active. Disconnect and delete it anyway?')) {{
+                                        response = await fetch(`/api/network/wifi/${{id}}?force=true`, {{
+                                            method: 'DELETE'
+                                        }});
+                                    }}
                                     
                                     if (response.ok) {{
                                         showToast('WiFi configuration deleted!');
-                                        setTimeout(() => location.reload(), 1000);
+                                        scheduleRefresh('wifi');
                                     }} else {{
                                         showToast('Failed to delete WiFi configuration', 'error');
                                     }}
@@ -491,13 +842,34 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                         // Static IP management functions
                         async function enableStaticIp(id) {{
                             try {{
-                                const response = await fetch(`/api/network/static-ip/${{id}}/enable`, {{
+                                const params = new URLSearchParams();
+                                let response = await fetch(`/api/network/static-ip/${{id}}/enable`, {{
                                     method: 'POST'
                                 }});
+                                // Another config owns the interface, or an enabled config on another interface has an
+                                // overlapping subnet; the error names the flag that overrides it, each asked for once
+                                while (response.status === 409) {{
+                                    const error = (await response.json()).error || '';
+                                    const flag = ['takeover', 'force'].find(flag => !params.has(flag) && error.includes(`${{flag}}=true`));
+                                    if (!flag) {{
+                                        break;
+                                    }}
+                                    if (!confirm(`${{error.split('; pass')[0]}}. Enable it anyway?`)) {{
+                                        return;
+                                    }}
+                                    params.set(flag, 'true');
+                                    response = await fetch(`/api/network/static-ip/${{id}}/enable?${{params}}`, {{
+                                        method: 'POST'
+                                    }});
+                                }}
                                 
                                 if (response.ok) {{
-                                    showToast('Static IP configuration enabled!');
-                                    setTimeout(() => location.reload(), 1000);
+                                    const result = await response.json();
+                                    showToast(applyMessage('Static IP configuration enabled!', result));
+                                    if (result.revert_at) {{
+                                        await confirmStaticIp(id, result.revert_at);
+                                    }}
+                                    scheduleRefresh('static-ip');
                                 }} else {{
                                     showToast('Failed to enable static IP configuration', 'error');
                                 }}
@@ -506,6 +878,26 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                             }}
                         }}
 
+                        // Safe mode: only a browser that can still reach the server after the change can keep it
+                        async function confirmStaticIp(id, revertAt) {{
+                            const revertTime = new Date(revertAt).toLocaleTimeString();
+                            if (!confirm(`Keep this static IP configuration? It will be reverted at ${{revertTime}} unless confirmed.`)) {{
+                                return;
+                            }}
+                            try {{
+                                const response = await fetch(`/api/network/static-ip/${{id}}/confirm`, {{
+                                    method: 'POST'
+                                }});
+                                if (response.ok) {{
+                                    showToast('Static IP configuration confirmed!');
+                                }} else {{
+                                    showToast(await errorMessage('Failed to confirm static IP configuration', response), 'error');
+                                }}
+                            }} catch (error) {{
+                                showToast('Error confirming static IP configuration', 'error');
+                            }}
+                        }}
+
                         async function disableStaticIp(id) {{
                             try {{
                                 const response = await fetch(`/api/network/static-ip/${{id}}/disable`, {{
@@ -513,8 +905,8 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                 }});
                                 
                                 if (response.ok) {{
-                                    showToast('Static IP configuration disabled!');
-                                    setTimeout(() => location.reload(), 1000);
+                                    showToast(applyMessage('Static IP configuration disabled!', await response.json()));
+                                    scheduleRefresh('static-ip');
                                 }} else {{
                                     showToast('Failed to disable static IP configuration', 'error');
                                 }}
@@ -532,7 +924,7 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                     
                                     if (response.ok) {{
                                         showToast('Static IP configuration deleted!');
-                                        setTimeout(() => location.reload(), 1000);
+                                        scheduleRefresh('static-ip');
                                     }} else {{
                                         showToast('Failed to delete static IP configuration', 'error');
                                     }}
@@ -575,12 +967,13 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                             ssidSelect.innerHTML = '<option value="">Select a network...</option>';
                             
                             // Sort networks by signal strength (descending)
-                            networks.sort((a, b) => b.signal_level - a.signal_level);
+                            // Networks whose signal could not be read go last
+                            networks.sort((a, b) => (b.signal_level ?? -1000) - (a.signal_level ?? -1000));
                             
                             networks.forEach(network => {{
                                 const option = document.createElement('option');
                                 option.value = network.ssid;
-                                option.textContent = `${{network.ssid}} (${{network.security}}, ${{network.signal_level}}dBm)`;
+                                option.textContent = `${{network.ssid}} (${{network.security}}, ${{network.signal_level !== null ? `${{network.signal_level}}dBm` : network.signal_raw}})`;
                                 ssidSelect.appendChild(option);
                             }});
                         }}
@@ -589,9 +982,12 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                         function handleSsidSelection() {{
                             const ssidSelect = document.getElementById('wifi-ssid');
                             const customInput = document.getElementById('wifi-ssid-custom');
+                            const hiddenCheckbox = document.getElementById('wifi-hidden');
                             
                             if (ssidSelect.value) {{
                                 customInput.value = '';
+                                hiddenCheckbox.checked = false;
+                                hiddenCheckbox.disabled = true;
                                 customInput.removeAttribute('required');
                                 ssidSelect.setAttribute('required', 'required');
                             }} else {{
@@ -607,6 +1003,12 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                             
                             ssidSelect.addEventListener('change', handleSsidSelection);
                             customInput.addEventListener('input', function() {{
+                                // Hidden networks never show up in a scan, so they can only be entered by hand
+                                const hiddenCheckbox = document.getElementById('wifi-hidden');
+                                hiddenCheckbox.disabled = !this.value;
+                                if (!this.value) {{
+                                    hiddenCheckbox.checked = false;
+                                }}
                                 if (this.value) {{
                                     ssidSelect.value = '';
                                     ssidSelect.removeAttribute('required');
@@ -636,7 +1038,9 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                 const wifiConfig = {{
                                     ssid: ssid,
                                     password: formData.get('password'),
-                                    security_type: formData.get('security_type')
+                                    security_type: formData.get('security_type'),
+                                    hidden: !!customInput.value && document.getElementById('wifi-hidden').checked,
+                                    preferred_band: formData.get('preferred_band') || null
                                 }};
                                 
                                 try {{
@@ -654,9 +1058,9 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                                         ssidSelect.value = '';
                                         customInput.value = '';
                                         handleSsidSelection();
-                                        setTimeout(() => location.reload(), 1000);
+                                        scheduleRefresh('wifi');
                                     }} else {{
-                                        showToast('Failed to add WiFi configuration', 'error');
+                                        showToast(await errorMessage('Failed to add WiFi configuration', response), 'error');
                                     }}
                                 }} catch (error) {{
                                     showToast('Error adding WiFi configuration', 'error');
@@ -669,6 +1073,21 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                         populateInterfaces();
                         populateWifiConfigs();
                         populateStaticIpConfigs();
+                        if (recentScan) {{
+                            populateWifiNetworks(recentScan.networks);
+                        }}
+
+                        // Warn up front when saved configs cannot reach the system
+                        fetch('/api/system/capabilities')
+                            .then(response => response.ok ? response.json() : null)
+                            .then(capabilities => {{
+                                if (capabilities && !capabilities.can_apply_network) {{
+                                    showToast(capabilities.apply_feature_compiled
+                                        ? 'Not running as root or with CAP_NET_ADMIN; changes will not be applied'
+                                        : 'Built without apply-system; changes are saved but not applied', 'error');
+                                }}
+                            }})
+                            .catch(() => {{}});
                     </script>
                 </body>
                 </html>
@@ -676,7 +1095,12 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
                 wifi_configs_json = wifi_configs_json,
                 static_ip_configs_json = static_ip_configs_json,
                 interfaces_json = interfaces_json,
-                active_wifi_json = active_wifi_json
+                active_wifi_json = active_wifi_json,
+                recent_scan_json = recent_scan_json,
+                security_options = security_options,
+                dns_primary_value = dns_primary_value,
+                dns_secondary_value = dns_secondary_value,
+                warnings_html = warnings_html
             );
             Ok(Html(html))
         }
@@ -684,6 +1108,18 @@ async fn network_settings_handler(State(state): State<AppState>) -> Result<Html<
     }
 }
 
+// Served from the binary so the page works without internet access
+const TAILWIND_CSS: &str = include_str!("../../assets/tailwind.css");
+const FAVICON: &[u8] = include_bytes!("../../assets/favicon.ico");
+
+async fn tailwind_css_handler() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/css; charset=utf-8")], TAILWIND_CSS)
+}
+
+async fn favicon_handler() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "image/x-icon")], FAVICON)
+}
+
 // API handlers
 async fn get_default_greeting_handler(
     State(state): State<AppState>,
@@ -697,25 +1133,34 @@ async fn get_default_greeting_handler(
 async fn create_greeting_handler(
     State(state): State<AppState>,
     Json(request): Json<CreateGreetingRequest>,
-) -> Result<Json<GreetingResponse>, StatusCode> {
-    match state.create_greeting_use_case.execute(request).await {
-        Ok(response) => Ok(Json(response)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-    }
+) -> Result<Json<GreetingResponse>, ApiError> {
+    let response = state.create_greeting_use_case.execute(request).await?;
+    Ok(Json(response))
 }
 
 async fn list_greetings_handler(
     State(state): State<AppState>,
-) -> Result<Json<GreetingsListResponse>, StatusCode> {
-    match state.list_greetings_use_case.execute().await {
-        Ok(response) => Ok(Json(response)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-    }
+    Query(query): Query<ListGreetingsQuery>,
+) -> Result<Json<GreetingsListResponse>, ApiError> {
+    let response = state.list_greetings_use_case.execute(query).await?;
+    Ok(Json(response))
+}
+
+async fn render_greeting_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<RenderGreetingRequest>,
+) -> Result<Json<RenderGreetingResponse>, ApiError> {
+    let response = state.render_greeting_use_case.execute(id, request).await?;
+    Ok(Json(response))
 }
 
 // Network API handlers
-async fn get_network_settings_api_handler(State(state): State<AppState>) -> Result<Json<NetworkSettingsPageData>, StatusCode> {
-    match state.get_network_settings_use_case.execute().await {
+async fn get_network_settings_api_handler(
+    State(state): State<AppState>,
+    Query(query): Query<InterfaceListQuery>,
+) -> Result<Json<NetworkSettingsPageData>, StatusCode> {
+    match state.get_network_settings_use_case.execute(query.include_loopback).await {
         Ok(response) => Ok(Json(response)),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
@@ -724,59 +1169,189 @@ async fn get_network_settings_api_handler(State(state): State<AppState>) -> Resu
 async fn create_wifi_config_handler(
     State(state): State<AppState>,
     Json(request): Json<CreateWifiConfigRequest>,
-) -> Result<Json<WifiConfigResponse>, StatusCode> {
-    match state.create_wifi_config_use_case.execute(request).await {
-        Ok(response) => Ok(Json(response)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-    }
+) -> Result<Json<WifiConfigResponse>, ApiError> {
+    let response = state.create_wifi_config_use_case.execute(request).await?;
+    Ok(Json(response))
+}
+
+// Run the create validation without saving anything
+async fn validate_wifi_config_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CreateWifiConfigRequest>,
+) -> Json<ValidationResponse> {
+    Json(state.validate_wifi_config_use_case.execute(request).await)
+}
+
+async fn list_wifi_configs_handler(
+    State(state): State<AppState>,
+    Query(query): Query<ListWifiConfigsQuery>,
+) -> Result<Json<WifiConfigsListResponse>, ApiError> {
+    let response = state.list_wifi_configs_use_case.execute(query).await?;
+    Ok(Json(response))
+}
+
+async fn list_static_ip_configs_handler(
+    State(state): State<AppState>,
+) -> Result<Json<StaticIpConfigsListResponse>, ApiError> {
+    let response = state.list_static_ip_configs_use_case.execute().await?;
+    Ok(Json(response))
 }
 
 async fn activate_wifi_config_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> Result<StatusCode, StatusCode> {
-    match state.activate_wifi_config_use_case.execute(id).await {
-        Ok(_) => Ok(StatusCode::OK),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-    }
+) -> Result<Json<ApplyResultResponse>, ApiError> {
+    let response = state.activate_wifi_config_use_case.execute(id).await?;
+    Ok(Json(response))
+}
+
+async fn switch_wifi_handler(
+    State(state): State<AppState>,
+    Json(request): Json<SwitchWifiRequest>,
+) -> Result<Json<SwitchWifiResponse>, ApiError> {
+    let response = state.switch_wifi_use_case.execute(request).await?;
+    Ok(Json(response))
+}
+
+async fn get_network_audit_handler(State(state): State<AppState>) -> Result<Json<NetworkAuditResponse>, ApiError> {
+    let response = state.get_network_audit_use_case.execute().await?;
+    Ok(Json(response))
+}
+
+// 400 with the per-item errors when any item is invalid; nothing is saved in that case
+async fn import_configs_handler(
+    State(state): State<AppState>,
+    Json(request): Json<ImportConfigsRequest>,
+) -> Result<(StatusCode, Json<ImportConfigsResponse>), ApiError> {
+    let response = state.import_configs_use_case.execute(request).await?;
+    let status = if response.imported { StatusCode::OK } else { StatusCode::BAD_REQUEST };
+    Ok((status, Json(response)))
+}
+
+async fn restart_networking_handler(
+    State(state): State<AppState>,
+) -> Result<Json<RestartNetworkingResponse>, ApiError> {
+    let response = state.restart_networking_use_case.execute().await?;
+    Ok(Json(response))
+}
+
+async fn connect_best_wifi_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ConnectBestWifiResponse>, ApiError> {
+    let response = state.connect_best_wifi_use_case.execute().await?;
+    Ok(Json(response))
+}
+
+async fn get_wifi_config_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<GetWifiConfigQuery>,
+    headers: HeaderMap,
+) -> Result<Json<WifiConfigDetailResponse>, ApiError> {
+    // Only the admin token counts: the admin allowlist checks where a request comes from, not who sent it
+    let authenticated = state.admin_token.verify(&headers);
+    let response = state.get_wifi_config_use_case.execute(id, query, authenticated).await?;
+    Ok(Json(response))
 }
 
 async fn delete_wifi_config_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> Result<StatusCode, StatusCode> {
-    match state.delete_wifi_config_use_case.execute(id).await {
-        Ok(_) => Ok(StatusCode::OK),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-    }
+    Query(query): Query<DeleteWifiConfigQuery>,
+) -> Result<StatusCode, ApiError> {
+    state.delete_wifi_config_use_case.execute(id, query.force).await?;
+    Ok(StatusCode::OK)
+}
+
+async fn set_wifi_auto_connect_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<SetWifiAutoConnectRequest>,
+) -> Result<Json<WifiConfigResponse>, ApiError> {
+    let response = state.set_wifi_auto_connect_use_case.execute(id, request).await?;
+    Ok(Json(response))
 }
 
 async fn create_static_ip_config_handler(
     State(state): State<AppState>,
     Json(request): Json<CreateStaticIpConfigRequest>,
-) -> Result<Json<StaticIpConfigResponse>, StatusCode> {
-    match state.create_static_ip_config_use_case.execute(request).await {
-        Ok(response) => Ok(Json(response)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-    }
+) -> Result<Json<StaticIpConfigResponse>, ApiError> {
+    let response = state.create_static_ip_config_use_case.execute(request).await?;
+    Ok(Json(response))
+}
+
+// 400 when an atomic batch was refused, so nothing was saved; a partial batch is always 200
+async fn batch_create_static_ip_configs_handler(
+    State(state): State<AppState>,
+    Query(query): Query<BatchCreateQuery>,
+    Json(requests): Json<Vec<CreateStaticIpConfigRequest>>,
+) -> Result<(StatusCode, Json<BatchCreateResponse>), ApiError> {
+    let response = state
+        .batch_create_static_ip_configs_use_case
+        .execute(requests, query.atomic)
+        .await?;
+    let refused = query.atomic && response.results.iter().any(|result| !result.errors.is_empty());
+    let status = if refused { StatusCode::BAD_REQUEST } else { StatusCode::OK };
+    Ok((status, Json(response)))
+}
+
+async fn validate_static_ip_config_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CreateStaticIpConfigRequest>,
+) -> Json<ValidationResponse> {
+    Json(state.validate_static_ip_config_use_case.execute(request).await)
+}
+
+// Always 200: a failed check is part of the answer, not an error
+async fn precheck_static_ip_config_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CreateStaticIpConfigRequest>,
+) -> Json<StaticIpPrecheckResponse> {
+    Json(state.precheck_static_ip_config_use_case.execute(request).await)
 }
 
 async fn enable_static_ip_config_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> Result<StatusCode, StatusCode> {
-    match state.enable_static_ip_config_use_case.execute(id).await {
-        Ok(_) => Ok(StatusCode::OK),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-    }
+    Query(query): Query<EnableStaticIpQuery>,
+) -> Result<Json<ApplyResultResponse>, ApiError> {
+    let response = state.enable_static_ip_config_use_case.execute(id, query.takeover, query.force).await?;
+    Ok(Json(response))
+}
+
+// Keep a static IP change that safe mode would otherwise revert
+async fn confirm_static_ip_config_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ConfirmStaticIpResponse>, ApiError> {
+    let response = state.confirm_static_ip_config_use_case.execute(id).await?;
+    Ok(Json(response))
+}
+
+async fn reorder_static_ip_dns_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<ReorderDnsRequest>,
+) -> Result<Json<StaticIpConfigResponse>, ApiError> {
+    let response = state.reorder_static_ip_dns_use_case.execute(id, request).await?;
+    Ok(Json(response))
+}
+
+async fn replace_static_ip_dns_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<ReplaceDnsRequest>,
+) -> Result<Json<StaticIpConfigResponse>, ApiError> {
+    let response = state.replace_static_ip_dns_use_case.execute(id, request).await?;
+    Ok(Json(response))
 }
 
 async fn disable_static_ip_config_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> Result<StatusCode, StatusCode> {
+) -> Result<Json<ApplyResultResponse>, StatusCode> {
     match state.disable_static_ip_config_use_case.execute(id).await {
-        Ok(_) => Ok(StatusCode::OK),
+        Ok(response) => Ok(Json(response)),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -791,11 +1366,863 @@ async fn delete_static_ip_config_handler(
     }
 }
 
-async fn scan_wifi_networks_handler(
+async fn set_interface_admin_state_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<SetInterfaceAdminStateRequest>,
+) -> Result<Json<InterfaceAdminStateResponse>, ApiError> {
+    let response = state.set_interface_admin_state_use_case.execute(name, request).await?;
+    Ok(Json(response))
+}
+
+async fn get_interface_rate_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<InterfaceRateDto>, ApiError> {
+    let rate = state.get_interface_rate_use_case.execute(name).await?;
+    Ok(Json(rate))
+}
+
+// Counters for every interface at once, for dashboards that chart them all
+async fn get_all_interface_stats_handler(
+    State(state): State<AppState>,
+) -> Result<Json<std::collections::BTreeMap<String, InterfaceStatsDto>>, StatusCode> {
+    match state.get_all_interface_stats_use_case.execute().await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn get_grouped_network_interfaces_handler(
+    State(state): State<AppState>,
+    Query(query): Query<InterfaceListQuery>,
+) -> Result<Json<GroupedNetworkInterfacesResponse>, ApiError> {
+    let grouped = state
+        .get_grouped_network_interfaces_use_case
+        .execute(query.include_loopback)
+        .await
+        .map_err(NetworkError::from)?;
+    Ok(Json(grouped))
+}
+
+async fn get_network_interface_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<NetworkInterfaceDto>, ApiError> {
+    let interface = state.get_network_interface_use_case.execute(name).await?;
+    Ok(Json(interface))
+}
+
+async fn get_wifi_link_status_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<WifiLinkStatusDto>, ApiError> {
+    let status = state.get_wifi_link_status_use_case.execute(name).await?;
+    Ok(Json(status))
+}
+
+async fn get_default_route_handler(
     State(state): State<AppState>,
-) -> Result<Json<Vec<ScannedWifiNetworkDto>>, StatusCode> {
-    match state.scan_wifi_networks_use_case.execute().await {
-        Ok(networks) => Ok(Json(networks)),
+) -> Result<Json<DefaultRouteDto>, StatusCode> {
+    match state.get_default_route_use_case.execute().await {
+        Ok(route) => Ok(Json(route)),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
-}
\ No newline at end of file
+}
+
+async fn create_network_profile_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CreateNetworkProfileRequest>,
+) -> Result<Json<NetworkProfileResponse>, ApiError> {
+    let response = state.create_network_profile_use_case.execute(request).await?;
+    Ok(Json(response))
+}
+
+async fn list_network_profiles_handler(
+    State(state): State<AppState>,
+) -> Result<Json<NetworkProfilesListResponse>, StatusCode> {
+    match state.list_network_profiles_use_case.execute().await {
+        Ok(response) => Ok(Json(response)),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn activate_profile_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ActivateProfileResponse>, ApiError> {
+    let response = state.activate_profile_use_case.execute(id).await?;
+    Ok(Json(response))
+}
+
+async fn probe_ip_handler(
+    State(state): State<AppState>,
+    Json(request): Json<ProbeIpRequest>,
+) -> Result<Json<ProbeIpResponse>, ApiError> {
+    let response = state.probe_ip_use_case.execute(request).await?;
+    Ok(Json(response))
+}
+
+async fn get_dns_latency_handler(
+    State(state): State<AppState>,
+    Query(query): Query<DnsLatencyQuery>,
+) -> Result<Json<DnsLatencyResponse>, ApiError> {
+    let response = state.get_dns_latency_use_case.execute(query).await?;
+    Ok(Json(response))
+}
+
+async fn get_address_conflicts_handler(State(state): State<AppState>) -> Result<Json<AddressConflictsResponse>, ApiError> {
+    let response = state.get_address_conflicts_use_case.execute().await?;
+    Ok(Json(response))
+}
+
+async fn get_interface_overview_handler(
+    State(state): State<AppState>,
+    Query(query): Query<InterfaceListQuery>,
+) -> Result<Json<Vec<InterfaceOverviewDto>>, StatusCode> {
+    match state.get_interface_overview_use_case.execute(query.include_loopback).await {
+        Ok(overview) => Ok(Json(overview)),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// The primary interface: the pinned preference when it exists, else a best guess
+async fn get_current_network_handler(
+    State(state): State<AppState>,
+) -> Result<Json<CurrentNetworkDto>, StatusCode> {
+    match state.get_current_network_use_case.execute().await {
+        Ok(current) => Ok(Json(current)),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn get_network_preferences_handler(
+    State(state): State<AppState>,
+) -> Result<Json<NetworkPreferencesDto>, StatusCode> {
+    match state.get_network_preferences_use_case.execute().await {
+        Ok(preferences) => Ok(Json(preferences)),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn set_network_preferences_handler(
+    State(state): State<AppState>,
+    Json(request): Json<NetworkPreferencesDto>,
+) -> Result<Json<NetworkPreferencesDto>, ApiError> {
+    let response = state.set_network_preferences_use_case.execute(request).await?;
+    Ok(Json(response))
+}
+
+// The bare array stays the default so existing clients (including the settings page) keep working
+async fn export_netplan_handler(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let yaml = state.export_netplan_use_case.execute().await?;
+    Ok(([(header::CONTENT_TYPE, "application/yaml")], yaml))
+}
+
+async fn scan_wifi_networks_handler(
+    State(state): State<AppState>,
+    Query(query): Query<ScanWifiNetworksQuery>,
+) -> Result<Json<ScanWifiNetworksView>, ApiError> {
+    let result = state.scan_wifi_networks_use_case.execute(&query).await?;
+    let view = match query.v {
+        Some(2) => ScanWifiNetworksView::Result(result),
+        _ => ScanWifiNetworksView::Networks(result.networks),
+    };
+    Ok(Json(view))
+}
+
+// Persisted interface events and config changes, newest first
+async fn event_history_handler(
+    State(state): State<AppState>,
+    Query(query): Query<EventHistoryQuery>,
+) -> Result<Json<EventHistoryResponse>, ApiError> {
+    let response = state.get_event_history_use_case.execute(query).await?;
+    Ok(Json(response))
+}
+
+// Interface change events as server-sent events, resuming after Last-Event-ID when the client reconnects
+async fn interface_events_sse_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Subscribe before reading the backlog so no event falls between the two
+    let receiver = state.interface_monitor.subscribe();
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    let backlog = last_event_id
+        .map(|id| state.interface_monitor.events_since(id))
+        .unwrap_or_default();
+    let last_sent = backlog.last().map(|event| event.id).or(last_event_id).unwrap_or(0);
+
+    let live = BroadcastStream::new(receiver).filter_map(move |result| match result {
+        Ok(event) if event.id > last_sent => Some(event),
+        _ => None,
+    });
+
+    let stream = tokio_stream::iter(backlog)
+        .chain(live)
+        .map(|event| Ok(interface_event_to_sse(event)));
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+const WIFI_SIGNAL_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+// Live signal of the active WiFi connection as server-sent events; a single `connected: false`
+// sample ends the stream, so clients are not left polling a radio with nothing to report
+async fn wifi_signal_sse_handler(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let use_case = state.sample_wifi_signal_use_case.clone();
+    let stream = futures_util::stream::unfold(Some((use_case, true)), |next| async move {
+        let (use_case, first) = next?;
+        if !first {
+            tokio::time::sleep(WIFI_SIGNAL_POLL_INTERVAL).await;
+        }
+        let sample = use_case.execute().await;
+        let next = sample.connected.then_some((use_case, false));
+        Some((sample, next))
+    });
+    Sse::new(stream.map(|sample| Ok(wifi_signal_to_sse(sample)))).keep_alive(KeepAlive::default())
+}
+
+fn wifi_signal_to_sse(sample: WifiSignalSampleDto) -> Event {
+    Event::default()
+        .event("signal")
+        .json_data(sample)
+        .unwrap_or_else(|_| Event::default().comment("failed to serialize signal sample"))
+}
+
+// The monitor keeps a bounded transition history, so longer windows would read truncated data
+const DEFAULT_FLAP_WINDOW_SECS: u64 = 300;
+const MAX_FLAP_WINDOW_SECS: u64 = 86_400;
+
+// Link up/down transitions seen by the interface monitor over a recent window
+async fn get_interface_flaps_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Query(query): Query<InterfaceFlapsQuery>,
+) -> Result<Json<InterfaceFlapsDto>, ApiError> {
+    let window_secs = query.window.unwrap_or(DEFAULT_FLAP_WINDOW_SECS);
+    if window_secs == 0 || window_secs > MAX_FLAP_WINDOW_SECS {
+        return Err(NetworkError::Validation(format!(
+            "window must be between 1 and {} seconds",
+            MAX_FLAP_WINDOW_SECS
+        ))
+        .into());
+    }
+    let flaps = state
+        .interface_monitor
+        .flaps(&name, window_secs)
+        .ok_or_else(|| NetworkError::NotFound(format!("Interface {} not found", name)))?;
+    Ok(Json(flaps.into()))
+}
+
+fn interface_event_to_sse(event: InterfaceEvent) -> Event {
+    let id = event.id.to_string();
+    Event::default()
+        .id(id)
+        .event("interface")
+        .json_data(InterfaceEventDto::from(event))
+        .unwrap_or_else(|_| Event::default().comment("failed to serialize interface event"))
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct LogsQuery {
+    level: Option<String>,
+    limit: Option<usize>,
+}
+
+// Recent server log records for debugging without shell access; defaults to info and above
+async fn logs_handler(
+    State(state): State<AppState>,
+    Query(query): Query<LogsQuery>,
+) -> Result<Json<Vec<LogRecord>>, StatusCode> {
+    let level = match query.level.as_deref() {
+        Some(level) => level.parse::<tracing::Level>().map_err(|_| StatusCode::BAD_REQUEST)?,
+        None => tracing::Level::INFO,
+    };
+    let limit = query.limit.unwrap_or(100).min(LOG_BUFFER_CAPACITY);
+    Ok(Json(state.log_buffer.recent(level, limit)))
+}
+
+#[derive(Debug, serde::Serialize)]
+struct VersionResponse {
+    version: &'static str,
+    git_sha: Option<&'static str>,
+    started_at: String,
+    uptime_secs: u64,
+}
+
+async fn get_hostname_handler(State(state): State<AppState>) -> Result<Json<HostnameDto>, ApiError> {
+    let response = state.get_hostname_use_case.execute().await?;
+    Ok(Json(response))
+}
+
+async fn set_hostname_handler(
+    State(state): State<AppState>,
+    Json(request): Json<HostnameDto>,
+) -> Result<Json<HostnameDto>, ApiError> {
+    let response = state.set_hostname_use_case.execute(request).await?;
+    Ok(Json(response))
+}
+
+async fn get_ip_forwarding_handler(State(state): State<AppState>) -> Result<Json<IpForwardingDto>, ApiError> {
+    let response = state.get_ip_forwarding_use_case.execute().await?;
+    Ok(Json(response))
+}
+
+async fn set_ip_forwarding_handler(
+    State(state): State<AppState>,
+    Json(request): Json<IpForwardingDto>,
+) -> Result<Json<IpForwardingDto>, ApiError> {
+    let response = state.set_ip_forwarding_use_case.execute(request).await?;
+    Ok(Json(response))
+}
+
+async fn get_capabilities_handler(State(state): State<AppState>) -> Result<Json<CapabilitiesDto>, ApiError> {
+    let response = state.get_capabilities_use_case.execute().await?;
+    Ok(Json(response))
+}
+
+// Prometheus text exposition of the request counters and latency histograms
+async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.request_metrics.render(),
+    )
+}
+
+async fn version_handler(State(state): State<AppState>) -> Json<VersionResponse> {
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        // Set by build.rs when built from a git checkout, or passed in by the build environment
+        git_sha: option_env!("HOMELABME_GIT_SHA").filter(|sha| !sha.is_empty()),
+        started_at: state.started_at.to_rfc3339(),
+        uptime_secs: state.started.elapsed().as_secs(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use crate::domain::network_entities::{
+        ApplyOutcome, DefaultRoute, NetworkInterface, NewWifiConfig, ScannedWifiNetwork, WifiLinkStatus,
+    };
+    use crate::domain::network_repositories::{NetworkInterfaceRepository, StaticIpConfigRepository, WifiConfigRepository};
+    use crate::domain::network_services::{NetworkConfigService, NetworkConfigServiceImpl};
+    use async_trait::async_trait;
+    use std::time::Duration;
+
+    // The next chunk of a streaming body, or None if nothing arrives within `wait`
+    async fn next_chunk(stream: &mut axum::body::BodyDataStream, wait: Duration) -> Option<String> {
+        match tokio::time::timeout(wait, stream.next()).await {
+            Ok(Some(chunk)) => Some(String::from_utf8(chunk.unwrap().to_vec()).unwrap()),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn one_interface_change_is_one_sse_event() {
+        let app = TestApp::new();
+        let monitor = app.state.interface_monitor.clone();
+        monitor.poll().await.unwrap();
+
+        let response = app.get("/api/network/events/sse").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+        let mut stream = response.into_body().into_data_stream();
+
+        app.network.interfaces.set_link_state("eth0", false).await.unwrap();
+        monitor.poll().await.unwrap();
+        let chunk = next_chunk(&mut stream, Duration::from_secs(1)).await.unwrap();
+        assert_eq!(chunk.matches("event: interface").count(), 1);
+        assert!(chunk.contains("id: 1\n"));
+        assert!(chunk.contains("\"interface_name\":\"eth0\""));
+
+        // Nothing changed since, so nothing more is sent
+        monitor.poll().await.unwrap();
+        assert!(next_chunk(&mut stream, Duration::from_millis(100)).await.is_none());
+    }
+
+    fn admin_get(uri: &str) -> Request<Body> {
+        Request::get(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn logs_show_warnings_and_filter_out_debug() {
+        use crate::infrastructure::log_buffer::LogBufferLayer;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let app = TestApp::new();
+        let subscriber = tracing_subscriber::registry().with(LogBufferLayer::new(app.state.log_buffer.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!("wlan0 lost its carrier");
+            tracing::debug!("polled 3 interfaces");
+        });
+
+        let response = app.send(admin_get("/api/logs?level=info")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let records = body_json(response).await;
+        let records = records.as_array().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["level"], "WARN");
+        assert_eq!(records[0]["message"], "wlan0 lost its carrier");
+
+        let records = body_json(app.send(admin_get("/api/logs?level=debug")).await).await;
+        assert_eq!(records.as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn logs_need_the_admin_token() {
+        let app = TestApp::new();
+        let response = app.get("/api/logs").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+
+        let request = Request::get("/api/logs")
+            .header(header::AUTHORIZATION, "Bearer not-the-token")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.send(request).await.status(), StatusCode::UNAUTHORIZED);
+
+        // Nobody is an admin when no token is configured
+        let mut app = TestApp::new();
+        app.state.admin_token = Arc::new(AdminToken::new(None));
+        assert_eq!(app.send(admin_get("/api/logs")).await.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn sse_replays_events_after_last_event_id() {
+        let app = TestApp::new();
+        let monitor = app.state.interface_monitor.clone();
+        monitor.poll().await.unwrap();
+        app.network.interfaces.set_link_state("eth0", false).await.unwrap();
+        monitor.poll().await.unwrap();
+        app.network.interfaces.set_link_state("eth0", true).await.unwrap();
+        monitor.poll().await.unwrap();
+
+        let request = Request::get("/api/network/events/sse")
+            .header("last-event-id", "1")
+            .body(Body::empty())
+            .unwrap();
+        let mut stream = app.send(request).await.into_body().into_data_stream();
+        let chunk = next_chunk(&mut stream, Duration::from_secs(1)).await.unwrap();
+        assert!(chunk.contains("id: 2\n"));
+        assert!(!chunk.contains("id: 1\n"));
+    }
+
+    // The validate endpoint's error list, checked against the 400 a create of the same payload gets
+    async fn assert_validate_matches_create(app: &TestApp, collection: &str, payload: serde_json::Value) {
+        let validated = body_json(app.post_json(&format!("{}/validate", collection), payload.clone()).await).await;
+        assert_eq!(validated["valid"], false);
+        let errors: Vec<String> = serde_json::from_value(validated["errors"].clone()).unwrap();
+        assert!(!errors.is_empty());
+
+        let created = app.post_json(collection, payload).await;
+        assert_eq!(created.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_json(created).await["error"], errors.join("; "));
+    }
+
+    #[tokio::test]
+    async fn validate_endpoints_report_the_errors_create_would() {
+        let app = TestApp::new();
+        assert_validate_matches_create(
+            &app,
+            "/api/network/wifi",
+            serde_json::json!({ "ssid": "", "password": "short", "security_type": "WPA2" }),
+        )
+        .await;
+        assert_validate_matches_create(
+            &app,
+            "/api/network/static-ip",
+            serde_json::json!({
+                "interface_name": "eth0",
+                "ip_address": "192.168.1.300",
+                "prefix_len": 24,
+                "gateway": "10.0.0.1",
+                "dns_primary": "not-an-ip",
+            }),
+        )
+        .await;
+
+        // Neither the validations nor the failed creates stored anything
+        assert!(app.network.wifi_repository.find_all().await.unwrap().is_empty());
+        assert!(app.network.static_ip_repository.find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn validate_endpoint_accepts_what_create_would_accept() {
+        let app = TestApp::new();
+        let payload = serde_json::json!({ "ssid": "HomeNetwork", "password": "correct horse", "security_type": "WPA2" });
+        let validated = body_json(app.post_json("/api/network/wifi/validate", payload).await).await;
+        assert_eq!(validated, serde_json::json!({ "valid": true, "errors": [] }));
+        assert!(app.network.wifi_repository.find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn version_reports_the_crate_version() {
+        let app = TestApp::new();
+        let response = app.get("/api/version").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let version = body_json(response).await;
+        assert_eq!(version["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(version["started_at"], app.state.started_at.to_rfc3339());
+        assert!(version["uptime_secs"].is_u64());
+    }
+
+    async fn get_wifi_config(app: &TestApp, uri: &str, token: Option<&str>) -> Response {
+        let mut request = Request::get(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        app.send(request.body(Body::empty()).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn wifi_secret_is_only_revealed_to_the_admin_token() {
+        let app = TestApp::new();
+        let config = app.service.create_wifi_config(wifi("Home")).await.unwrap();
+        let uri = format!("/api/network/wifi/{}", config.id);
+
+        let plain = get_wifi_config(&app, &uri, Some(ADMIN_TOKEN)).await;
+        assert_eq!(plain.status(), StatusCode::OK);
+        assert!(!body_text(plain).await.contains("correct horse"));
+
+        let reveal = format!("{}?reveal_secret=true", uri);
+        for token in [None, Some("not-the-admin-token-at-all")] {
+            let refused = get_wifi_config(&app, &reveal, token).await;
+            assert_eq!(refused.status(), StatusCode::FORBIDDEN);
+            assert!(!body_text(refused).await.contains("correct horse"));
+        }
+
+        let revealed = get_wifi_config(&app, &reveal, Some(ADMIN_TOKEN)).await;
+        assert_eq!(revealed.status(), StatusCode::OK);
+        assert_eq!(body_json(revealed).await["config"]["password"], "correct horse");
+    }
+
+    #[tokio::test]
+    async fn versioned_scan_response_carries_scan_metadata() {
+        let app = TestApp::new();
+        let before = chrono::Utc::now();
+        let scan = body_json(app.get("/api/network/wifi/scan?v=2").await).await;
+        let scanned_at = chrono::DateTime::parse_from_rfc3339(scan["scanned_at"].as_str().unwrap()).unwrap();
+        assert!(scanned_at >= before && scanned_at <= chrono::Utc::now());
+        assert!(scan["duration_ms"].is_u64());
+        assert_eq!(scan["cached"], false);
+        assert_eq!(scan["networks"].as_array().unwrap().len(), 3);
+
+        // The unversioned response stays a bare array for the existing UI
+        let bare = body_json(app.get("/api/network/wifi/scan").await).await;
+        assert_eq!(bare.as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn list_endpoints_return_the_saved_configs() {
+        let app = TestApp::new();
+        let empty = body_json(app.get("/api/network/wifi").await).await;
+        assert_eq!(empty, serde_json::json!({ "configs": [] }));
+
+        let home = app.service.create_wifi_config(wifi("Home")).await.unwrap();
+        let lan = app.service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        app.service.create_static_ip_config(static_ip("eth9", "10.0.0.60")).await.unwrap();
+
+        let response = app.get("/api/network/wifi").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let wifi_configs = body_json(response).await;
+        let configs = wifi_configs["configs"].as_array().unwrap();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0]["id"], home.id);
+        assert_eq!(configs[0]["ssid"], "Home");
+        assert!(configs[0].get("password").is_none());
+
+        let response = app.get("/api/network/static-ip").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let static_configs = body_json(response).await;
+        let mut rows: Vec<(String, bool)> = static_configs["configs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|config| (config["interface_name"].as_str().unwrap().to_string(), config["interface_present"].as_bool().unwrap()))
+            .collect();
+        rows.sort();
+        assert_eq!(rows, vec![("eth0".to_string(), true), ("eth9".to_string(), false)]);
+        assert!(static_configs["configs"].as_array().unwrap().iter().any(|config| config["id"] == lan.id));
+    }
+
+    #[tokio::test]
+    async fn page_embeds_the_cached_scan_without_breaking_the_script() {
+        let app = TestApp::new();
+        let hostile = "</script><script>alert(1)";
+        let network = ScannedWifiNetwork {
+            ssid: hostile.to_string(),
+            mac: "02:00:00:00:10:09".to_string(),
+            signal_level: Some(-60),
+            signal_raw: "-60".to_string(),
+            channel: "6".to_string(),
+            channel_number: Some(6),
+            band: None,
+            security: "WPA2".to_string(),
+        };
+        app.scan_cache.store(chrono::Utc::now(), vec![network]);
+        app.service
+            .create_wifi_config(NewWifiConfig { exact_ssid: true, ..wifi("</script>") })
+            .await
+            .unwrap();
+
+        let page = body_text(app.get("/").await).await;
+        assert!(page.contains("const recentScan = {"), "the cached scan is not embedded");
+        assert!(page.contains("\\u003c/script>\\u003cscript>alert(1)"));
+        assert!(!page.contains(hostile));
+        assert_eq!(page.matches("</script>").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn import_with_one_invalid_item_creates_nothing() {
+        let app = TestApp::new();
+        let wifi = |id: &str, password: &str| {
+            serde_json::json!({
+                "id": id,
+                "ssid": "Home",
+                "password": password,
+                "security_type": "WPA2",
+                "is_active": false,
+                "created_at": "2026-01-01T00:00:00Z",
+            })
+        };
+        let static_ip = serde_json::json!({
+            "id": "lan",
+            "interface_name": "eth0",
+            "ip_address": "192.168.1.60",
+            "subnet_mask": "255.255.255.0",
+            "gateway": "192.168.1.1",
+            "dns_primary": "1.1.1.1",
+            "dns_secondary": null,
+            "is_enabled": false,
+            "created_at": "2026-01-01T00:00:00Z",
+        });
+        let payload = serde_json::json!({
+            "wifi_configs": [wifi("home", "correct horse"), wifi("broken", "short")],
+            "static_ip_configs": [static_ip],
+        });
+
+        let response = app.post_json("/api/network/import", payload).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = body_json(response).await;
+        assert_eq!(body["imported"], false);
+        assert_eq!(body["created"], 0);
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0]["kind"].as_str(), errors[0]["index"].as_u64(), errors[0]["id"].as_str()), (Some("wifi"), Some(1), Some("broken")));
+        assert!(app.network.wifi_repository.find_all().await.unwrap().is_empty());
+        assert!(app.network.static_ip_repository.find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn single_interface_is_looked_up_by_name() {
+        let app = TestApp::new();
+        let found = app.get("/api/network/interfaces/eth0").await;
+        assert_eq!(found.status(), StatusCode::OK);
+        let interface = body_json(found).await;
+        assert_eq!(interface["name"], "eth0");
+        assert_eq!(interface["ipv4_addresses"], serde_json::json!(["192.168.1.50"]));
+        assert_eq!(interface["ipv6_addresses"], serde_json::json!([]));
+
+        let missing = app.get("/api/network/interfaces/eth9").await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        assert_eq!(app.get("/api/network/interfaces/-eth0").await.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn settings_forms_are_prefilled_from_the_configured_defaults() {
+        let mut app = TestApp::new();
+        app.state.get_network_settings_use_case = Arc::new(
+            GetNetworkSettingsUseCaseImpl::new(app.service.clone()).with_form_defaults(FormDefaultsDto {
+                security_type: WifiSecurityType::WPA3,
+                dns_primary: Some("9.9.9.9".to_string()),
+                dns_secondary: Some("149.112.112.112".to_string()),
+            }),
+        );
+
+        let page = body_text(app.get("/").await).await;
+        assert!(page.contains(r#"<option value="WPA3" selected>WPA3</option>"#));
+        assert!(page.contains(r#"<option value="WPA2">WPA2</option>"#));
+        assert!(page.contains(r#"value="9.9.9.9""#));
+        assert!(page.contains(r#"value="149.112.112.112""#));
+
+        let settings = body_json(app.get("/api/network/settings").await).await;
+        assert_eq!(
+            settings["form_defaults"],
+            serde_json::json!({ "security_type": "WPA3", "dns_primary": "9.9.9.9", "dns_secondary": "149.112.112.112" })
+        );
+    }
+
+    // Interface enumeration that always fails, as when netlink is unavailable
+    struct BrokenInterfaces;
+
+    #[async_trait]
+    impl NetworkInterfaceRepository for BrokenInterfaces {
+        async fn get_interfaces(&self) -> Result<Vec<NetworkInterface>, String> {
+            Err("netlink unavailable".to_string())
+        }
+
+        async fn get_interface_by_name(&self, _name: &str) -> Result<Option<NetworkInterface>, String> {
+            Err("netlink unavailable".to_string())
+        }
+
+        async fn set_link_state(&self, _name: &str, _up: bool) -> Result<ApplyOutcome, String> {
+            Err("netlink unavailable".to_string())
+        }
+
+        async fn get_wifi_link_status(&self, _name: &str) -> Result<WifiLinkStatus, String> {
+            Err("netlink unavailable".to_string())
+        }
+
+        async fn get_default_route(&self) -> Result<DefaultRoute, String> {
+            Err("netlink unavailable".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn settings_page_renders_with_a_warning_when_interfaces_cannot_be_listed() {
+        let network = TestNetwork::new();
+        let service = Arc::new(NetworkConfigServiceImpl::new(
+            network.wifi_repository.clone(),
+            network.static_ip_repository.clone(),
+            Arc::new(BrokenInterfaces),
+            network.admin_states.clone(),
+            network.stats.clone(),
+            network.applier.clone(),
+            network.applier.clone(),
+            network.scanner.clone(),
+        ));
+        service.create_wifi_config(wifi("Home")).await.unwrap();
+        let app = TestApp::with_service(network, service);
+
+        let page = app.get("/").await;
+        assert_eq!(page.status(), StatusCode::OK);
+        let page = body_text(page).await;
+        assert!(page.contains("Could not load network interfaces: netlink unavailable"));
+        assert!(page.contains("Home"));
+
+        let settings = body_json(app.get("/api/network/settings").await).await;
+        assert_eq!(settings["warnings"], serde_json::json!(["Could not load network interfaces: netlink unavailable"]));
+        assert_eq!(settings["network_interfaces"], serde_json::json!([]));
+        assert_eq!(settings["wifi_configs"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn embedded_assets_are_served_with_their_content_type() {
+        let app = TestApp::new();
+        let css = app.get("/assets/tailwind.css").await;
+        assert_eq!(css.status(), StatusCode::OK);
+        assert_eq!(css.headers()[header::CONTENT_TYPE], "text/css; charset=utf-8");
+        assert_eq!(css.headers()[header::CACHE_CONTROL], "public, max-age=86400");
+        assert_eq!(body_text(css).await, TAILWIND_CSS);
+
+        let favicon = app.get("/favicon.ico").await;
+        assert_eq!(favicon.status(), StatusCode::OK);
+        assert_eq!(favicon.headers()[header::CONTENT_TYPE], "image/x-icon");
+
+        // The page links the local stylesheet instead of a CDN
+        let page = body_text(app.get("/").await).await;
+        assert!(page.contains(r#"<link rel="stylesheet" href="/assets/tailwind.css">"#));
+        assert!(!page.contains("cdn.tailwindcss.com"));
+    }
+
+    #[tokio::test]
+    async fn interface_lists_leave_out_loopback_unless_asked() {
+        let app = TestApp::new();
+        let names = |interfaces: &serde_json::Value| {
+            let mut names: Vec<String> =
+                interfaces.as_array().unwrap().iter().map(|i| i["name"].as_str().unwrap().to_string()).collect();
+            names.sort();
+            names
+        };
+
+        let settings = body_json(app.get("/api/network/settings").await).await;
+        assert_eq!(names(&settings["network_interfaces"]), vec!["eth0", "wlan0"]);
+        let grouped = body_json(app.get("/api/network/interfaces/grouped").await).await;
+        assert_eq!(grouped["loopback"], serde_json::json!([]));
+
+        let settings = body_json(app.get("/api/network/settings?include_loopback=true").await).await;
+        assert_eq!(names(&settings["network_interfaces"]), vec!["eth0", "lo", "wlan0"]);
+        let grouped = body_json(app.get("/api/network/interfaces/grouped?include_loopback=true").await).await;
+        assert_eq!(names(&grouped["loopback"]), vec!["lo"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_requests_time_out_with_504() {
+        let mut app = TestApp::new();
+        app.state.request_timeout = Some(Duration::from_secs(5));
+        let config = app.service.create_static_ip_config(static_ip("eth0", "192.168.1.60")).await.unwrap();
+        app.network.applier.set_delay(Duration::from_secs(60));
+
+        let started = tokio::time::Instant::now();
+        let response = app.post_json(&format!("/api/network/static-ip/{}/enable", config.id), serde_json::json!({})).await;
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(started.elapsed(), Duration::from_secs(5));
+
+        // Without a limit the same request waits for the applier
+        app.state.request_timeout = None;
+        app.network.applier.set_delay(Duration::from_secs(10));
+        let response = app.post_json(&format!("/api/network/static-ip/{}/disable", config.id), serde_json::json!({})).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn errors_are_problem_details_when_the_client_accepts_them() {
+        let app = TestApp::new();
+        let get = |accept: Option<&'static str>| {
+            let mut request = Request::get("/api/network/interfaces/eth9");
+            if let Some(accept) = accept {
+                request = request.header(header::ACCEPT, accept);
+            }
+            app.send(request.body(Body::empty()).unwrap())
+        };
+
+        let plain = get(None).await;
+        assert_eq!(plain.status(), StatusCode::NOT_FOUND);
+        assert_eq!(plain.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(body_json(plain).await, serde_json::json!({ "error": "Interface eth9 not found" }));
+
+        let problem = get(Some("application/json, application/problem+json;q=0.9")).await;
+        assert_eq!(problem.status(), StatusCode::NOT_FOUND);
+        assert_eq!(problem.headers()[header::CONTENT_TYPE], "application/problem+json");
+        assert_eq!(
+            body_json(problem).await,
+            serde_json::json!({
+                "type": "urn:homelabme:problem:not-found",
+                "title": "Not Found",
+                "status": 404,
+                "detail": "Interface eth9 not found",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn web_ui_and_metrics_can_be_turned_off() {
+        let app = TestApp::new();
+        assert_eq!(app.get("/").await.status(), StatusCode::OK);
+        assert_eq!(app.get("/metrics").await.status(), StatusCode::OK);
+
+        let mut app = TestApp::new();
+        app.state.web_ui = false;
+        app.state.metrics_endpoint = false;
+        for path in ["/", "/assets/tailwind.css", "/favicon.ico", "/metrics"] {
+            assert_eq!(app.get(path).await.status(), StatusCode::NOT_FOUND, "{}", path);
+        }
+        assert_eq!(app.get("/api/version").await.status(), StatusCode::OK);
+    }
+}
@@ -2,93 +2,644 @@
 mod domain;
 mod application;
 mod infrastructure;
+#[cfg(test)]
+mod test_support;
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 use infrastructure::repositories::InMemoryGreetingRepository;
+use domain::clock::{Clock, SystemClock};
 use domain::services::GreetingServiceImpl;
 use application::use_cases::*;
 use application::network_use_cases::*;
+use application::network_dto::FormDefaultsDto;
+use application::interface_monitor::InterfaceMonitor;
+use application::wifi_scan_cache::WifiScanCache;
+use application::static_ip_safe_mode::StaticIpSafeMode;
+use application::system_use_cases::*;
 use domain::network_services::*;
+use domain::system_services::{HostnameServiceImpl, IpForwardingServiceImpl};
+use domain::system_repositories::{HostnameRepository, IpForwardingRepository, PrivilegeProbe};
 use infrastructure::network_repositories::*;
+use infrastructure::network_appliers::*;
+use infrastructure::system_repositories::{EtcHostnameRepository, ProcStatusPrivilegeProbe, ProcSysIpForwardingRepository};
+use domain::network_appliers::{ServiceRestarter, StaticIpApplier, WifiApplier};
+use domain::network_events::{EventSink, NoopEventSink};
+use infrastructure::event_sinks::{WebhookEventSink, WebhookNotificationSink};
+use domain::notifications::{NotificationService, NotificationServiceImpl};
 use infrastructure::web::{create_router, AppState};
+use infrastructure::config::Config;
+use infrastructure::log_buffer::{LogBuffer, LogBufferLayer};
+use infrastructure::metrics::RequestMetrics;
+use infrastructure::middleware::{AdminAllowlist, AdminToken, IdempotencyCache, RateLimiter};
+use infrastructure::simulation::*;
+use infrastructure::snapshot::RepositorySnapshot;
+use infrastructure::sysfs::detect_sysfs_reader;
+use domain::network_repositories::{DnsProber, EventHistoryRepository, InterfaceAdminStateRepository, InterfaceStatsRepository, SettingsRepository, IpProber, NetworkInterfaceRepository, WifiScanner};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+// Everything that observes the live system; swapped wholesale in simulation mode
+type SystemBackends = (
+    Arc<dyn NetworkInterfaceRepository>,
+    Arc<dyn InterfaceStatsRepository>,
+    Arc<dyn WifiScanner>,
+    Arc<dyn IpProber>,
+    Arc<dyn DnsProber>,
+);
 
 #[tokio::main]
 async fn main() {
+    let started_at = chrono::Utc::now();
+    let started = std::time::Instant::now();
+    
+    // Logs go to stdout and into an in-memory buffer served by GET /api/logs
+    let log_buffer = Arc::new(LogBuffer::default());
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .with(LogBufferLayer::new(log_buffer.clone()))
+        .init();
+    
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+    let admin_allowlist = match AdminAllowlist::parse(&config.admin_cidrs) {
+        Ok(allowlist) => Arc::new(allowlist),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+    let event_sink: Arc<dyn EventSink> = match &config.event_webhook_url {
+        Some(url) => match WebhookEventSink::new(url) {
+            Ok(sink) => Arc::new(sink),
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => Arc::new(NoopEventSink),
+    };
+    let notifications: Option<Arc<dyn NotificationService>> = match &config.notification_webhook_url {
+        Some(url) => match WebhookNotificationSink::new(url) {
+            Ok(sink) => Some(Arc::new(NotificationServiceImpl::new(Arc::new(sink)))),
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    if let Err(e) = std::fs::create_dir_all(&config.data_dir) {
+        tracing::warn!("Failed to create data directory {}: {}", config.data_dir.display(), e);
+    }
+    
     // Dependency injection - build the application from the outside in
     
     // Infrastructure layer
     let greeting_repository = Arc::new(InMemoryGreetingRepository::new());
     let wifi_config_repository = Arc::new(InMemoryWifiConfigRepository::new());
     let static_ip_config_repository = Arc::new(InMemoryStaticIpConfigRepository::new());
-    let network_interface_repository = Arc::new(SystemNetworkInterfaceRepository::new());
+    // Configs saved at the last graceful shutdown
+    let repository_snapshot = RepositorySnapshot::new(
+        config.data_dir.join("snapshot.json"),
+        wifi_config_repository.clone(),
+        static_ip_config_repository.clone(),
+        greeting_repository.clone(),
+    );
+    match repository_snapshot.restore().await {
+        Ok(true) => tracing::info!("Restored configuration snapshot"),
+        Ok(false) => {}
+        Err(e) => tracing::warn!("Failed to restore configuration snapshot: {}", e),
+    }
+    let network_profile_repository = Arc::new(InMemoryNetworkProfileRepository::new());
+    // Simulated interfaces start fresh on every run, so their states are not worth keeping
+    let interface_admin_state_repository: Arc<dyn InterfaceAdminStateRepository> = if config.simulate {
+        Arc::new(InMemoryInterfaceAdminStateRepository::new())
+    } else {
+        Arc::new(JsonFileInterfaceAdminStateRepository::new(config.data_dir.join("interface_states.json")))
+    };
+    let event_history_repository: Arc<dyn EventHistoryRepository> =
+        Arc::new(JsonlEventHistoryRepository::new(config.data_dir.join("events.jsonl")));
+    let settings_repository: Arc<dyn SettingsRepository> =
+        Arc::new(JsonFileSettingsRepository::new(config.data_dir.join("settings.json")));
+    let (network_interface_repository, interface_stats_repository, wifi_scanner, ip_prober, dns_prober): SystemBackends = if config.simulate {
+        let fixture = match &config.simulation_fixture {
+            Some(path) => match SimulationFixture::from_file(path) {
+                Ok(fixture) => fixture,
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    std::process::exit(1);
+                }
+            },
+            None => SimulationFixture::default(),
+        };
+        tracing::warn!("Simulation mode: serving fake interfaces and WiFi networks, system changes are only logged");
+        let repository = Arc::new(
+            SimulatedNetworkInterfaceRepository::new(fixture.interfaces, fixture.default_route)
+                .with_wifi_link(fixture.wifi_link),
+        );
+        (
+            repository.clone(),
+            repository.clone(),
+            Arc::new(SimulatedWifiScanner::new(fixture.networks)),
+            Arc::new(SimulatedIpProber::new(repository)),
+            Arc::new(SimulatedDnsProber),
+        )
+    } else {
+        let sysfs_reader = detect_sysfs_reader();
+        // Raw ARP needs CAP_NET_RAW, which only system-apply deployments are expected to grant
+        #[cfg(feature = "apply-system")]
+        let ip_prober: Arc<dyn IpProber> = Arc::new(ArpingIpProber);
+        #[cfg(not(feature = "apply-system"))]
+        let ip_prober: Arc<dyn IpProber> = Arc::new(PingIpProber);
+        (
+            Arc::new(SystemNetworkInterfaceRepository::new(sysfs_reader.clone())),
+            Arc::new(SysfsInterfaceStatsRepository::new(sysfs_reader)),
+            Arc::new(SystemWifiScanner::new()),
+            ip_prober,
+            Arc::new(UdpDnsProber::new()),
+        )
+    };
+    
+    // System appliers - only touch the live network config when compiled with `apply-system`
+    #[cfg(feature = "apply-system")]
+    let (static_ip_applier, wifi_applier): (Arc<dyn StaticIpApplier>, Arc<dyn WifiApplier>) = {
+        let mut networkd = SystemdNetworkdApplier::new("/etc/systemd/network");
+        let mut wpa_supplicant = WpaSupplicantApplier::new(config.wifi_interface.clone(), "/etc/wpa_supplicant");
+        if config.keep_generated_files {
+            let archive = Arc::new(GeneratedFileArchive::new(config.data_dir.join("generated")));
+            networkd = networkd.with_archive(archive.clone());
+            wpa_supplicant = wpa_supplicant.with_archive(archive);
+        }
+        (Arc::new(networkd), Arc::new(wpa_supplicant))
+    };
+    #[cfg(not(feature = "apply-system"))]
+    let (static_ip_applier, wifi_applier): (Arc<dyn StaticIpApplier>, Arc<dyn WifiApplier>) = {
+        if config.keep_generated_files {
+            tracing::warn!("keep_generated_files has no effect without the apply-system feature");
+        }
+        (Arc::new(DisabledApplier), Arc::new(DisabledApplier))
+    };
+    let (static_ip_applier, wifi_applier): (Arc<dyn StaticIpApplier>, Arc<dyn WifiApplier>) = if config.simulate {
+        (Arc::new(SimulatedApplier), Arc::new(SimulatedApplier))
+    } else {
+        (static_ip_applier, wifi_applier)
+    };
+    #[cfg(feature = "apply-system")]
+    let service_restarter: Arc<dyn ServiceRestarter> = Arc::new(SystemctlRestarter);
+    #[cfg(not(feature = "apply-system"))]
+    let service_restarter: Arc<dyn ServiceRestarter> = Arc::new(DisabledApplier);
+    let service_restarter: Arc<dyn ServiceRestarter> = if config.simulate {
+        Arc::new(SimulatedApplier)
+    } else {
+        service_restarter
+    };
+    // Finish every apply even when the request that started it goes away
+    let static_ip_applier: Arc<dyn StaticIpApplier> = Arc::new(DetachedApplier::new(static_ip_applier));
+    let wifi_applier: Arc<dyn WifiApplier> = Arc::new(DetachedApplier::new(wifi_applier));
+    let service_restarter: Arc<dyn ServiceRestarter> = Arc::new(DetachedApplier::new(service_restarter));
     
     // Domain layer
-    let greeting_service = Arc::new(GreetingServiceImpl::new(greeting_repository));
-    let network_config_service = Arc::new(NetworkConfigServiceImpl::new(
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let greeting_service = Arc::new(GreetingServiceImpl::new(greeting_repository).with_clock(clock.clone()));
+    let mut network_config_service = NetworkConfigServiceImpl::new(
         wifi_config_repository.clone(),
         static_ip_config_repository.clone(),
-        network_interface_repository.clone(),
-    ));
+        network_interface_repository,
+        interface_admin_state_repository,
+        interface_stats_repository,
+        static_ip_applier,
+        wifi_applier,
+        wifi_scanner,
+    )
+    .with_scan_timeout(std::time::Duration::from_secs(config.scan_timeout_secs.max(1)))
+    .with_config_limits(config.config_limits())
+    .with_event_history(event_history_repository.clone())
+    .with_event_sink(event_sink)
+    .with_clock(clock.clone());
+    if let Some(notifications) = notifications {
+        network_config_service = network_config_service.with_notifications(notifications);
+    }
+    let network_config_service = Arc::new(network_config_service);
+    
+    let network_profile_service = Arc::new(
+        NetworkProfileServiceImpl::new(network_profile_repository, network_config_service.clone()).with_clock(clock),
+    );
+    
+    // Boot-time reconciliation - re-apply administratively stored interface states
+    match network_config_service.reconcile_interface_admin_states().await {
+        Ok(failures) => {
+            for failure in failures {
+                tracing::warn!("Interface state reconciliation failed: {}", failure);
+            }
+        }
+        Err(e) => tracing::warn!("Interface state reconciliation failed: {}", e),
+    }
+
+    // Bring the system in line with the stored enabled/active configs; only meaningful when
+    // appliers actually touch the system (or pretend to, in simulation mode)
+    if cfg!(feature = "apply-system") || config.simulate {
+        match network_config_service.reconcile_configs().await {
+            Ok(report) => {
+                for applied in report.applied {
+                    tracing::info!("Reconciled {}", applied);
+                }
+                for failure in report.failures {
+                    tracing::warn!("Config reconciliation failed: {}", failure);
+                }
+            }
+            Err(e) => tracing::warn!("Config reconciliation failed: {}", e),
+        }
+    }
     
     // Application layer - use cases
     let get_default_greeting_use_case = Arc::new(GetDefaultGreetingUseCaseImpl::new(greeting_service.clone()));
     let create_greeting_use_case = Arc::new(CreateGreetingUseCaseImpl::new(greeting_service.clone()));
-    let list_greetings_use_case = Arc::new(ListGreetingsUseCaseImpl::new(greeting_service));
+    let list_greetings_use_case = Arc::new(ListGreetingsUseCaseImpl::new(greeting_service.clone()));
+    let render_greeting_use_case = Arc::new(RenderGreetingUseCaseImpl::new(greeting_service));
     
     // Network use cases
-    let get_network_settings_use_case = Arc::new(GetNetworkSettingsUseCaseImpl::new(network_config_service.clone()));
+    let safe_mode_revert_after = (config.safe_mode_revert_secs > 0)
+        .then(|| std::time::Duration::from_secs(config.safe_mode_revert_secs));
+    let static_ip_safe_mode = Arc::new(StaticIpSafeMode::new(network_config_service.clone(), safe_mode_revert_after));
+    // Latest WiFi scan for the settings page, filled by client scans and, if enabled, in the background
+    let wifi_scan_cache = Arc::new(
+        WifiScanCache::new(network_config_service.clone())
+            .with_ttl(std::time::Duration::from_secs(config.scan_cache_ttl_secs)),
+    );
+    if config.background_scan_secs > 0 {
+        wifi_scan_cache.clone().spawn(std::time::Duration::from_secs(config.background_scan_secs));
+    }
+    let get_network_settings_use_case = Arc::new(
+        GetNetworkSettingsUseCaseImpl::new(network_config_service.clone())
+            .with_scan_cache(wifi_scan_cache.clone())
+            .with_form_defaults(FormDefaultsDto {
+                security_type: config.default_security_type.clone(),
+                dns_primary: config.default_dns_primary.map(|dns| dns.to_string()),
+                dns_secondary: config.default_dns_secondary.map(|dns| dns.to_string()),
+            }),
+    );
     let create_wifi_config_use_case = Arc::new(CreateWifiConfigUseCaseImpl::new(network_config_service.clone()));
+    let validate_wifi_config_use_case = Arc::new(ValidateWifiConfigUseCaseImpl::new(network_config_service.clone()));
+    let get_wifi_config_use_case = Arc::new(GetWifiConfigUseCaseImpl::new(network_config_service.clone()));
+    let list_wifi_configs_use_case = Arc::new(ListWifiConfigsUseCaseImpl::new(network_config_service.clone()));
     let activate_wifi_config_use_case = Arc::new(ActivateWifiConfigUseCaseImpl::new(network_config_service.clone()));
+    // Association can only be checked when the switch really reaches wpa_supplicant
+    let switch_wifi_use_case = Arc::new(SwitchWifiUseCaseImpl::new(
+        network_config_service.clone(),
+        config.wifi_interface.clone(),
+        cfg!(feature = "apply-system") && !config.simulate,
+    ));
+    let connect_best_wifi_use_case = Arc::new(ConnectBestWifiUseCaseImpl::new(network_config_service.clone()));
     let delete_wifi_config_use_case = Arc::new(DeleteWifiConfigUseCaseImpl::new(network_config_service.clone()));
+    let set_wifi_auto_connect_use_case = Arc::new(SetWifiAutoConnectUseCaseImpl::new(network_config_service.clone()));
+    let list_static_ip_configs_use_case = Arc::new(ListStaticIpConfigsUseCaseImpl::new(network_config_service.clone()));
     let create_static_ip_config_use_case = Arc::new(CreateStaticIpConfigUseCaseImpl::new(network_config_service.clone()));
-    let enable_static_ip_config_use_case = Arc::new(EnableStaticIpConfigUseCaseImpl::new(network_config_service.clone()));
+    let batch_create_static_ip_configs_use_case = Arc::new(BatchCreateStaticIpConfigsUseCaseImpl::new(network_config_service.clone()));
+    let validate_static_ip_config_use_case = Arc::new(ValidateStaticIpConfigUseCaseImpl::new(network_config_service.clone()));
+    let enable_static_ip_config_use_case = Arc::new(EnableStaticIpConfigUseCaseImpl::new(network_config_service.clone(), static_ip_safe_mode.clone()));
+    let reorder_static_ip_dns_use_case = Arc::new(ReorderStaticIpDnsUseCaseImpl::new(network_config_service.clone()));
+    let replace_static_ip_dns_use_case = Arc::new(ReplaceStaticIpDnsUseCaseImpl::new(network_config_service.clone()));
+    let confirm_static_ip_config_use_case = Arc::new(ConfirmStaticIpConfigUseCaseImpl::new(static_ip_safe_mode));
     let disable_static_ip_config_use_case = Arc::new(DisableStaticIpConfigUseCaseImpl::new(network_config_service.clone()));
     let delete_static_ip_config_use_case = Arc::new(DeleteStaticIpConfigUseCaseImpl::new(network_config_service.clone()));
-    let scan_wifi_networks_use_case = Arc::new(ScanWifiNetworksUseCaseImpl::new(network_config_service.clone()));
+    let set_interface_admin_state_use_case = Arc::new(SetInterfaceAdminStateUseCaseImpl::new(network_config_service.clone()));
+    let get_interface_rate_use_case = Arc::new(GetInterfaceRateUseCaseImpl::new(network_config_service.clone()));
+    let get_all_interface_stats_use_case = Arc::new(GetAllInterfaceStatsUseCaseImpl::new(network_config_service.clone()));
+    let get_grouped_network_interfaces_use_case = Arc::new(GetGroupedNetworkInterfacesUseCaseImpl::new(network_config_service.clone()));
+    let get_network_interface_use_case = Arc::new(GetNetworkInterfaceUseCaseImpl::new(network_config_service.clone()));
+    let get_wifi_link_status_use_case = Arc::new(GetWifiLinkStatusUseCaseImpl::new(network_config_service.clone()));
+    let sample_wifi_signal_use_case = Arc::new(SampleWifiSignalUseCaseImpl::new(network_config_service.clone(), config.wifi_interface.clone()));
+    let get_default_route_use_case = Arc::new(GetDefaultRouteUseCaseImpl::new(network_config_service.clone()));
+    let precheck_static_ip_config_use_case = Arc::new(PrecheckStaticIpConfigUseCaseImpl::new(network_config_service.clone(), ip_prober.clone()));
+    let probe_ip_use_case = Arc::new(ProbeIpUseCaseImpl::new(ip_prober));
+    let get_dns_latency_use_case = Arc::new(GetDnsLatencyUseCaseImpl::new(network_config_service.clone(), dns_prober));
+    let get_address_conflicts_use_case = Arc::new(GetAddressConflictsUseCaseImpl::new(network_config_service.clone()));
+    let create_network_profile_use_case = Arc::new(CreateNetworkProfileUseCaseImpl::new(network_profile_service.clone()));
+    let list_network_profiles_use_case = Arc::new(ListNetworkProfilesUseCaseImpl::new(network_profile_service.clone()));
+    let activate_profile_use_case = Arc::new(ActivateProfileUseCaseImpl::new(network_profile_service));
+    let get_interface_overview_use_case = Arc::new(GetInterfaceOverviewUseCaseImpl::new(network_config_service.clone(), config.wifi_interface.clone()));
+    let export_netplan_use_case = Arc::new(ExportNetplanUseCaseImpl::new(network_config_service.clone(), config.wifi_interface.clone()));
+    let scan_wifi_networks_use_case = Arc::new(
+        ScanWifiNetworksUseCaseImpl::new(network_config_service.clone()).with_scan_cache(wifi_scan_cache),
+    );
+    let get_network_audit_use_case = Arc::new(GetNetworkAuditUseCaseImpl::new(
+        list_wifi_configs_use_case.clone(),
+        list_static_ip_configs_use_case.clone(),
+    ));
+    let import_configs_use_case = Arc::new(ImportConfigsUseCaseImpl::new(network_config_service.clone()));
+    // Like the boot-time reconcile, only meaningful when changes reach the system (or the simulation)
+    let restart_networking_use_case = Arc::new(RestartNetworkingUseCaseImpl::new(
+        network_config_service.clone(),
+        service_restarter,
+        &config.wifi_interface,
+        cfg!(feature = "apply-system") || config.simulate,
+    ));
+    let get_event_history_use_case = Arc::new(GetEventHistoryUseCaseImpl::new(event_history_repository.clone()));
+    let get_network_preferences_use_case = Arc::new(GetNetworkPreferencesUseCaseImpl::new(settings_repository.clone()));
+    let set_network_preferences_use_case = Arc::new(SetNetworkPreferencesUseCaseImpl::new(settings_repository.clone()));
+    let get_current_network_use_case = Arc::new(GetCurrentNetworkUseCaseImpl::new(network_config_service.clone(), settings_repository));
+
+    // System settings; like network changes, renaming the host needs `apply-system` (or simulation)
+    let hostname_repository: Arc<dyn HostnameRepository> = if config.simulate {
+        Arc::new(SimulatedHostnameRepository::new())
+    } else {
+        Arc::new(EtcHostnameRepository::default())
+    };
+    let hostname_service = Arc::new(HostnameServiceImpl::new(
+        hostname_repository,
+        cfg!(feature = "apply-system") || config.simulate,
+    ));
+    let get_hostname_use_case = Arc::new(GetHostnameUseCaseImpl::new(hostname_service.clone()));
+    let set_hostname_use_case = Arc::new(SetHostnameUseCaseImpl::new(hostname_service));
+    let ip_forwarding_repository: Arc<dyn IpForwardingRepository> = if config.simulate {
+        Arc::new(SimulatedIpForwardingRepository::default())
+    } else {
+        Arc::new(ProcSysIpForwardingRepository::default())
+    };
+    let ip_forwarding_service = Arc::new(IpForwardingServiceImpl::new(
+        ip_forwarding_repository,
+        cfg!(feature = "apply-system") || config.simulate,
+    ));
+    let get_ip_forwarding_use_case = Arc::new(GetIpForwardingUseCaseImpl::new(ip_forwarding_service.clone()));
+    let set_ip_forwarding_use_case = Arc::new(SetIpForwardingUseCaseImpl::new(ip_forwarding_service));
+    let privilege_probe: Arc<dyn PrivilegeProbe> = if config.simulate {
+        Arc::new(SimulatedPrivilegeProbe)
+    } else {
+        Arc::new(ProcStatusPrivilegeProbe::default())
+    };
+    let get_capabilities_use_case = Arc::new(GetCapabilitiesUseCaseImpl::new(
+        privilege_probe,
+        cfg!(feature = "apply-system") || config.simulate,
+    ));
+    
+    // Background interface monitor feeding the event streams and the persisted history
+    let interface_monitor = Arc::new(
+        InterfaceMonitor::new(network_config_service.clone()).with_event_history(event_history_repository),
+    );
+    interface_monitor.clone().spawn(std::time::Duration::from_secs(config.monitor_interval_secs.max(1)));
     
     // Application state
     let app_state = AppState {
         get_default_greeting_use_case,
         create_greeting_use_case,
         list_greetings_use_case,
+        render_greeting_use_case,
         get_network_settings_use_case,
         create_wifi_config_use_case,
+        validate_wifi_config_use_case,
+        get_wifi_config_use_case,
+        list_wifi_configs_use_case,
         activate_wifi_config_use_case,
+        switch_wifi_use_case,
+        connect_best_wifi_use_case,
         delete_wifi_config_use_case,
+        set_wifi_auto_connect_use_case,
+        list_static_ip_configs_use_case,
         create_static_ip_config_use_case,
+        batch_create_static_ip_configs_use_case,
+        validate_static_ip_config_use_case,
+        precheck_static_ip_config_use_case,
         enable_static_ip_config_use_case,
+        reorder_static_ip_dns_use_case,
+        replace_static_ip_dns_use_case,
+        confirm_static_ip_config_use_case,
         disable_static_ip_config_use_case,
         delete_static_ip_config_use_case,
+        set_interface_admin_state_use_case,
+        get_interface_rate_use_case,
+        get_all_interface_stats_use_case,
+        get_grouped_network_interfaces_use_case,
+        get_network_interface_use_case,
+        get_wifi_link_status_use_case,
+        sample_wifi_signal_use_case,
+        get_default_route_use_case,
+        get_interface_overview_use_case,
+        export_netplan_use_case,
+        probe_ip_use_case,
+        get_dns_latency_use_case,
+        get_address_conflicts_use_case,
+        create_network_profile_use_case,
+        list_network_profiles_use_case,
+        activate_profile_use_case,
         scan_wifi_networks_use_case,
+        get_network_audit_use_case,
+        import_configs_use_case,
+        restart_networking_use_case,
+        get_event_history_use_case,
+        get_network_preferences_use_case,
+        set_network_preferences_use_case,
+        get_current_network_use_case,
+        get_hostname_use_case,
+        set_hostname_use_case,
+        get_ip_forwarding_use_case,
+        set_ip_forwarding_use_case,
+        get_capabilities_use_case,
+        interface_monitor,
+        log_buffer,
+        admin_allowlist,
+        admin_token: Arc::new(AdminToken::new(config.admin_token.clone())),
+        idempotency_cache: Arc::new(IdempotencyCache::new(std::time::Duration::from_secs(config.idempotency_ttl_secs))),
+        rate_limiter: Arc::new(RateLimiter::new(config.rate_limit_per_minute)),
+        request_timeout: (config.request_timeout_secs > 0).then(|| std::time::Duration::from_secs(config.request_timeout_secs)),
+        request_metrics: Arc::new(RequestMetrics::new()),
+        // Checked when the config was loaded
+        cors_origins: config
+            .cors_origins
+            .iter()
+            .filter_map(|origin| axum::http::HeaderValue::from_str(origin).ok())
+            .collect(),
+        web_ui: config.web_ui,
+        metrics_endpoint: config.metrics,
+        started_at,
+        started,
     };
     
     // Presentation layer - web routes
     let app = create_router(app_state);
     
-    // Start the server
-    let port = std::env::var("PORT")
-        .unwrap_or_else(|_| "80".to_string())
-        .parse::<u16>()
-        .unwrap_or(80);
-    
-    let bind_address = format!("0.0.0.0:{}", port);
-    let listener = tokio::net::TcpListener::bind(&bind_address).await.unwrap();
-    
-    let server_url = if port == 80 {
-        "http://localhost".to_string()
+    // Start the server - on a Unix socket when one is configured, otherwise on host:port
+    let server: Pin<Box<dyn Future<Output = Result<(), String>> + Send>> = if let Some(path) = &config.unix_socket {
+        #[cfg(unix)]
+        {
+            let listener = match infrastructure::unix_socket::bind(path) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    std::process::exit(1);
+                }
+            };
+            if config.tls_cert.is_some() {
+                tracing::warn!("TLS is not used on the Unix socket; terminate TLS in the reverse proxy");
+            }
+            if !config.admin_cidrs.is_empty() {
+                tracing::warn!("admin_cidrs does not apply to Unix socket clients; restrict access with the socket's permissions");
+            }
+            print_endpoints(&format!("unix:{}", path.display()));
+            Box::pin(async move {
+                infrastructure::unix_socket::serve(listener, app).await;
+                Ok(())
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            eprintln!("❌ Unix sockets are not supported on this platform: {}", path.display());
+            std::process::exit(1);
+        }
+    } else if let (Some(cert), Some(key)) = (&config.tls_cert, &config.tls_key) {
+        let tls = match infrastructure::tls::load(cert, key).await {
+            Ok(tls) => tls,
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+        };
+        let server_url = if config.port == 443 {
+            "https://localhost".to_string()
+        } else {
+            format!("https://localhost:{}", config.port)
+        };
+        print_endpoints(&server_url);
+        let bind_address = config.bind_address();
+        Box::pin(async move { infrastructure::tls::serve(&bind_address, tls, app).await })
     } else {
-        format!("http://localhost:{}", port)
+        let listener = match tokio::net::TcpListener::bind(&config.bind_address()).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("❌ Failed to listen on {}: {}", config.bind_address(), e);
+                std::process::exit(1);
+            }
+        };
+        
+        let server_url = if config.port == 80 {
+            "http://localhost".to_string()
+        } else {
+            format!("http://localhost:{}", config.port)
+        };
+        print_endpoints(&server_url);
+        
+        // Connection info gives the admin allowlist the real peer address
+        Box::pin(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+            )
+            .await
+            .map_err(|e| format!("HTTP server failed: {}", e))
+        })
     };
-    
+
+    tokio::select! {
+        result = server => {
+            if let Err(e) = result {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+        }
+        _ = shutdown_signal() => tracing::info!("Shutting down"),
+    }
+    // Requests still in flight are dropped; the snapshot has whatever they had stored so far
+    match repository_snapshot.save().await {
+        Ok(()) => tracing::info!("Saved configuration snapshot"),
+        Err(e) => tracing::warn!("Failed to save configuration snapshot: {}", e),
+    }
+}
+
+// Resolves on Ctrl+C or, on Unix, SIGTERM as sent by `systemctl stop`
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+fn print_endpoints(server_url: &str) {
     println!("🦀 Rust Clean Architecture Server running on {}", server_url);
     println!("📋 Available endpoints:");
     println!("   GET  /                     - Network settings page");
     println!("   GET  /api/greetings/default - Get default greeting");
-    println!("   GET  /api/greetings        - List all greetings");
-    println!("   POST /api/greetings        - Create new greeting");
+    println!("   GET  /api/greetings        - List greetings (?created_after=&created_before=)");
+    println!("   POST /api/greetings        - Create new greeting (\"language\": \"en\"/\"en-US\", \"dedup\": true reuses an identical one)");
+    println!("   POST /api/greetings/:id/render - Fill a greeting's {{key}} placeholders from {{\"vars\": {{...}}}}");
     println!("   GET  /api/network/settings - Get network settings");
+    println!("   GET  /api/network/wifi     - List WiFi configs (?security=Open,WEP)");
     println!("   POST /api/network/wifi     - Create WiFi config");
+    println!("   GET  /api/network/wifi/:id - Get one WiFi config");
+    println!("   POST /api/network/wifi/:id/auto-connect - Let wpa_supplicant join a network on its own, or only on request");
+    println!("   GET  /api/network/wifi/scan - Scan for WiFi networks (?v=2 adds scan time and duration)");
+    println!("   POST /api/network/wifi/validate - Validate a WiFi config without saving");
+    println!("   POST /api/network/static-ip/batch - Create many static IP configs (?atomic=true for all or nothing)");
+    println!("   POST /api/network/static-ip/validate - Validate a static IP config without saving");
+    println!("   POST /api/network/static-ip/precheck - Every check for a static IP config, incl. live interface and ARP probe");
+    println!("   POST /api/network/wifi/connect-best - Activate the strongest saved network in range");
+    println!("   POST /api/network/wifi/switch - Switch networks, reverting if the new one does not associate");
+    println!("   GET  /api/network/static-ip - List static IP configs");
     println!("   POST /api/network/static-ip - Create static IP config");
-    
-    axum::serve(listener, app).await.unwrap();
+    println!("   POST /api/network/static-ip/:id/confirm - Keep a static IP change under safe mode");
+    println!("   POST /api/network/static-ip/:id/dns-order - Reorder a static IP config's DNS servers");
+    println!("   PUT  /api/network/static-ip/:id/dns - Replace a static IP config's DNS servers");
+    println!("   GET  /api/network/interfaces/grouped - Interfaces split by type");
+    println!("   GET  /api/network/interfaces/:name - One interface with its addresses");
+    println!("   POST /api/network/interfaces/:name/state - Set interface up/down");
+    println!("   GET  /api/network/interfaces/:name/rate - Interface throughput");
+    println!("   GET  /api/network/interfaces/stats - RX/TX byte counters of every interface");
+    println!("   GET  /api/network/interfaces/:name/wifi-link - WiFi association details");
+    println!("   GET  /api/network/default-route - Kernel default route");
+    println!("   GET  /api/network/profiles - List network profiles");
+    println!("   POST /api/network/profiles - Create a WiFi + static IP profile");
+    println!("   POST /api/network/profiles/:id/activate - Switch to a profile");
+    println!("   POST /api/network/probe-ip - Check whether an IP is already in use");
+    println!("   GET  /api/network/dns-latency - Min/avg/max query latency of the configured DNS servers");
+    println!("   GET  /api/network/conflicts - Addresses assigned to more than one interface");
+    println!("   GET  /api/network/by-interface - Interfaces with their static IP config");
+    println!("   GET  /api/network/current  - Primary interface, gateway and active WiFi");
+    println!("   GET  /api/network/settings/preferences - Preferred primary interface");
+    println!("   PUT  /api/network/settings/preferences - Pin or clear the preferred primary interface");
+    println!("   GET  /api/network/audit    - Insecure, orphaned, failing and duplicate configs");
+    println!("   POST /api/network/import   - Upsert WiFi and static IP configs; all or nothing");
+    println!("   POST /api/network/restart  - Re-apply every stored config and restart networkd/wpa_supplicant");
+    println!("   GET  /api/network/export/netplan - Enabled configs as a netplan YAML file");
+    println!("   GET  /api/network/interfaces/:name/flaps - Link up/down transitions in the last ?window= seconds");
+    println!("   GET  /api/network/events/sse - Interface change events (SSE)");
+    println!("   GET  /api/network/wifi-signal - Live signal of the active WiFi connection (SSE)");
+    println!("   GET  /api/network/events/history - Past interface events and config changes (?since=&type=&limit=)");
+    println!("   GET  /api/system/hostname  - Current hostname");
+    println!("   PUT  /api/system/hostname  - Rename the host (apply-system only)");
+    println!("   GET  /api/system/ip-forwarding - Whether IPv4/IPv6 forwarding is on");
+    println!("   PUT  /api/system/ip-forwarding - Turn IPv4/IPv6 forwarding on or off (apply-system only)");
+    println!("   GET  /api/system/capabilities - Whether this process can apply network changes");
+    println!("   GET  /api/logs             - Recent server log records");
+    println!("   GET  /api/version          - Version, git commit and uptime");
+    println!("   GET  /metrics              - Request counts and latency in Prometheus format");
 }
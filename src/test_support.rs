@@ -0,0 +1,430 @@
+// Test helpers - in-memory repositories and simulated system backends wired into the services
+
+#![allow(dead_code)]
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::domain::clock::Clock;
+use crate::domain::network_appliers::{ServiceRestarter, StaticIpApplier, WifiApplier};
+use crate::domain::network_entities::*;
+use crate::domain::network_repositories::{InterfaceStatsRepository, WifiScanner};
+use crate::domain::network_services::{NetworkConfigService, NetworkConfigServiceImpl, NetworkProfileServiceImpl};
+use crate::infrastructure::network_repositories::{
+    InMemoryInterfaceAdminStateRepository, InMemoryStaticIpConfigRepository, InMemoryWifiConfigRepository,
+};
+use crate::infrastructure::simulation::*;
+use crate::infrastructure::sysfs::LinuxSysfsReader;
+use crate::application::interface_monitor::InterfaceMonitor;
+use crate::application::network_dto::FormDefaultsDto;
+use crate::application::network_use_cases::*;
+use crate::application::static_ip_safe_mode::StaticIpSafeMode;
+use crate::application::system_use_cases::*;
+use crate::application::use_cases::*;
+use crate::application::wifi_scan_cache::WifiScanCache;
+use crate::domain::network_repositories::{EventHistoryRepository, SettingsRepository};
+use crate::domain::services::GreetingServiceImpl;
+use crate::domain::system_services::{HostnameServiceImpl, IpForwardingServiceImpl};
+use crate::infrastructure::log_buffer::LogBuffer;
+use crate::infrastructure::metrics::RequestMetrics;
+use crate::infrastructure::middleware::{AdminAllowlist, AdminToken, IdempotencyCache, RateLimiter};
+use crate::infrastructure::network_repositories::{
+    InMemoryNetworkProfileRepository, JsonFileSettingsRepository, JsonlEventHistoryRepository,
+};
+use crate::infrastructure::repositories::InMemoryGreetingRepository;
+use crate::infrastructure::web::{create_router, AppState};
+use axum::body::Body;
+use axum::http::{Request, Response};
+use axum::Router;
+use tower::ServiceExt;
+
+// A fresh directory under the system temp dir; left behind for inspection when a test fails
+pub fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("homelabme-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A sysfs tree under a temp dir holding only the given `(interface, attribute, contents)` files
+pub fn fake_sysfs(files: &[(&str, &str, &str)]) -> Arc<LinuxSysfsReader> {
+    let root = temp_dir();
+    for (interface_name, attribute, contents) in files {
+        let path = root.join(interface_name).join(attribute);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    Arc::new(LinuxSysfsReader::with_root(root))
+}
+
+// The repositories and simulated backends behind a test `NetworkConfigServiceImpl`, kept so
+// tests can look at what the service stored or change what the "system" reports
+pub struct TestNetwork {
+    pub wifi_repository: Arc<InMemoryWifiConfigRepository>,
+    pub static_ip_repository: Arc<InMemoryStaticIpConfigRepository>,
+    pub admin_states: Arc<InMemoryInterfaceAdminStateRepository>,
+    pub interfaces: Arc<SimulatedNetworkInterfaceRepository>,
+    // The simulated interfaces unless a test swaps in its own counters
+    pub stats: Arc<dyn InterfaceStatsRepository>,
+    pub applier: Arc<RecordingApplier>,
+    // The simulated networks unless a test swaps in its own scanner
+    pub scanner: Arc<dyn WifiScanner>,
+}
+
+impl TestNetwork {
+    // The built-in simulation: lo, eth0 (192.168.1.50/24) and wlan0
+    pub fn new() -> Self {
+        Self::with_fixture(SimulationFixture::default())
+    }
+
+    pub fn with_fixture(fixture: SimulationFixture) -> Self {
+        let interfaces = Arc::new(
+            SimulatedNetworkInterfaceRepository::new(fixture.interfaces, fixture.default_route)
+                .with_wifi_link(fixture.wifi_link),
+        );
+        Self {
+            wifi_repository: Arc::new(InMemoryWifiConfigRepository::new()),
+            static_ip_repository: Arc::new(InMemoryStaticIpConfigRepository::new()),
+            admin_states: Arc::new(InMemoryInterfaceAdminStateRepository::new()),
+            stats: interfaces.clone(),
+            interfaces,
+            applier: Arc::new(RecordingApplier::default()),
+            scanner: Arc::new(SimulatedWifiScanner::new(fixture.networks)),
+        }
+    }
+
+    // A service over these repositories; tests add `with_*` options as they need them
+    pub fn service(&self) -> NetworkConfigServiceImpl {
+        NetworkConfigServiceImpl::new(
+            self.wifi_repository.clone(),
+            self.static_ip_repository.clone(),
+            self.interfaces.clone(),
+            self.admin_states.clone(),
+            self.stats.clone(),
+            self.applier.clone(),
+            self.applier.clone(),
+            self.scanner.clone(),
+        )
+    }
+}
+
+// Applier that records every call and reports success, optionally after a delay
+#[derive(Default)]
+pub struct RecordingApplier {
+    calls: Mutex<Vec<String>>,
+    delay: Mutex<Option<std::time::Duration>>,
+    // Calls starting with the prefix fail with the error
+    failure: Mutex<Option<(String, String)>>,
+}
+
+impl RecordingApplier {
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    // Make every later call take `delay` before it finishes
+    pub fn set_delay(&self, delay: std::time::Duration) {
+        *self.delay.lock().unwrap() = Some(delay);
+    }
+
+    // Make every later call fail with `error` (still recorded), or succeed again with None
+    pub fn set_failure(&self, error: Option<&str>) {
+        self.set_failure_for("", error);
+    }
+
+    // Like `set_failure`, for the calls starting with `prefix` only, e.g. "apply static"
+    pub fn set_failure_for(&self, prefix: &str, error: Option<&str>) {
+        *self.failure.lock().unwrap() = error.map(|error| (prefix.to_string(), error.to_string()));
+    }
+
+    async fn record(&self, call: String) -> Result<ApplyOutcome, String> {
+        let delay = *self.delay.lock().unwrap();
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+        self.calls.lock().unwrap().push(call.clone());
+        if let Some((prefix, error)) = self.failure.lock().unwrap().clone()
+            && call.starts_with(&prefix)
+        {
+            return Err(error);
+        }
+        Ok(ApplyOutcome {
+            applied: true,
+            already_active: false,
+            detail: Some(call),
+        })
+    }
+}
+
+#[async_trait]
+impl StaticIpApplier for RecordingApplier {
+    async fn apply(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+        self.record(format!("apply static {}", config.id)).await
+    }
+
+    async fn remove(&self, config: &StaticIpConfig) -> Result<ApplyOutcome, String> {
+        self.record(format!("remove static {}", config.id)).await
+    }
+}
+
+#[async_trait]
+impl WifiApplier for RecordingApplier {
+    async fn apply(&self, config: &WifiConfig) -> Result<ApplyOutcome, String> {
+        self.record(format!("apply wifi {}", config.id)).await
+    }
+
+    async fn disconnect(&self, config: &WifiConfig) -> Result<ApplyOutcome, String> {
+        self.record(format!("disconnect wifi {}", config.id)).await
+    }
+}
+
+#[async_trait]
+impl ServiceRestarter for RecordingApplier {
+    async fn restart(&self, unit: &str) -> Result<ApplyOutcome, String> {
+        self.record(format!("restart {}", unit)).await
+    }
+}
+
+// Counters that come from a list of samples, one per read; the last one repeats once they run out
+pub struct SampledStats {
+    samples: Mutex<std::collections::VecDeque<InterfaceStats>>,
+}
+
+impl SampledStats {
+    pub fn new(samples: Vec<(u64, u64)>) -> Self {
+        Self {
+            samples: Mutex::new(
+                samples
+                    .into_iter()
+                    .map(|(rx_bytes, tx_bytes)| InterfaceStats { rx_bytes, tx_bytes })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl InterfaceStatsRepository for SampledStats {
+    async fn get_stats(&self, _interface_name: &str) -> Result<InterfaceStats, String> {
+        let mut samples = self.samples.lock().unwrap();
+        match samples.len() {
+            0 => Err("no counters".to_string()),
+            1 => Ok(samples[0].clone()),
+            _ => Ok(samples.pop_front().unwrap()),
+        }
+    }
+}
+
+// Stands still until moved, for tests that check ordering or time windows
+pub struct FixedClock {
+    now: Mutex<chrono::DateTime<chrono::Utc>>,
+}
+
+impl FixedClock {
+    pub fn new(now: chrono::DateTime<chrono::Utc>) -> Self {
+        Self { now: Mutex::new(now) }
+    }
+
+    pub fn advance(&self, by: chrono::Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+// A valid static IP request for `interface_name`, e.g. `static_ip("eth0", "192.168.1.60")` in a /24
+pub fn static_ip(interface_name: &str, ip_address: &str) -> NewStaticIpConfig {
+    let gateway = ip_address.rsplit_once('.').map(|(net, _)| format!("{}.1", net)).unwrap();
+    NewStaticIpConfig {
+        id: None,
+        interface_name: interface_name.to_string(),
+        ip_address: ip_address.to_string(),
+        subnet_mask: None,
+        prefix_len: Some(24),
+        gateway,
+        dns_primary: "1.1.1.1".to_string(),
+        dns_secondary: None,
+        search_domains: Vec::new(),
+    }
+}
+
+// A valid WPA2 network request
+pub fn wifi(ssid: &str) -> NewWifiConfig {
+    NewWifiConfig {
+        id: None,
+        ssid: ssid.to_string(),
+        password: "correct horse".to_string(),
+        security_type: WifiSecurityType::WPA2,
+        hidden: false,
+        auto_connect: true,
+        preferred_band: None,
+        exact_ssid: false,
+    }
+}
+
+// Admin token of every `TestApp`
+pub const ADMIN_TOKEN: &str = "test-admin-token-0123456789";
+
+// The whole HTTP application over a `TestNetwork`, wired like main.rs in simulation mode but with
+// nothing running in the background. Tests adjust `state` before calling `router`.
+pub struct TestApp {
+    pub network: TestNetwork,
+    pub service: Arc<NetworkConfigServiceImpl>,
+    pub scan_cache: Arc<WifiScanCache>,
+    pub state: AppState,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        let network = TestNetwork::new();
+        let service = Arc::new(network.service());
+        Self::with_service(network, service)
+    }
+
+    pub fn with_service(network: TestNetwork, service: Arc<NetworkConfigServiceImpl>) -> Self {
+        let data_dir = temp_dir();
+        let network_service: Arc<dyn NetworkConfigService> = service.clone();
+        let greeting_service = Arc::new(GreetingServiceImpl::new(Arc::new(InMemoryGreetingRepository::new())));
+        let event_history: Arc<dyn EventHistoryRepository> =
+            Arc::new(JsonlEventHistoryRepository::new(data_dir.join("events.jsonl")));
+        let settings: Arc<dyn SettingsRepository> =
+            Arc::new(JsonFileSettingsRepository::new(data_dir.join("settings.json")));
+        let profile_service = Arc::new(NetworkProfileServiceImpl::new(
+            Arc::new(InMemoryNetworkProfileRepository::new()),
+            service.clone(),
+        ));
+        let ip_prober = Arc::new(SimulatedIpProber::new(network.interfaces.clone()));
+        let safe_mode = Arc::new(StaticIpSafeMode::new(network_service.clone(), None));
+        let scan_cache = Arc::new(WifiScanCache::new(network_service.clone()));
+        let list_wifi_configs_use_case = Arc::new(ListWifiConfigsUseCaseImpl::new(network_service.clone()));
+        let list_static_ip_configs_use_case = Arc::new(ListStaticIpConfigsUseCaseImpl::new(network_service.clone()));
+        let hostname_service = Arc::new(HostnameServiceImpl::new(Arc::new(SimulatedHostnameRepository::new()), true));
+        let ip_forwarding_service = Arc::new(IpForwardingServiceImpl::new(
+            Arc::new(SimulatedIpForwardingRepository::default()),
+            true,
+        ));
+
+        let state = AppState {
+            get_default_greeting_use_case: Arc::new(GetDefaultGreetingUseCaseImpl::new(greeting_service.clone())),
+            create_greeting_use_case: Arc::new(CreateGreetingUseCaseImpl::new(greeting_service.clone())),
+            list_greetings_use_case: Arc::new(ListGreetingsUseCaseImpl::new(greeting_service.clone())),
+            render_greeting_use_case: Arc::new(RenderGreetingUseCaseImpl::new(greeting_service)),
+            get_network_settings_use_case: Arc::new(
+                GetNetworkSettingsUseCaseImpl::new(network_service.clone())
+                    .with_scan_cache(scan_cache.clone())
+                    .with_form_defaults(FormDefaultsDto {
+                        security_type: WifiSecurityType::WPA2,
+                        dns_primary: None,
+                        dns_secondary: None,
+                    }),
+            ),
+            create_wifi_config_use_case: Arc::new(CreateWifiConfigUseCaseImpl::new(network_service.clone())),
+            validate_wifi_config_use_case: Arc::new(ValidateWifiConfigUseCaseImpl::new(network_service.clone())),
+            get_wifi_config_use_case: Arc::new(GetWifiConfigUseCaseImpl::new(network_service.clone())),
+            list_wifi_configs_use_case: list_wifi_configs_use_case.clone(),
+            activate_wifi_config_use_case: Arc::new(ActivateWifiConfigUseCaseImpl::new(network_service.clone())),
+            switch_wifi_use_case: Arc::new(SwitchWifiUseCaseImpl::new(network_service.clone(), "wlan0".to_string(), false)),
+            connect_best_wifi_use_case: Arc::new(ConnectBestWifiUseCaseImpl::new(network_service.clone())),
+            delete_wifi_config_use_case: Arc::new(DeleteWifiConfigUseCaseImpl::new(network_service.clone())),
+            set_wifi_auto_connect_use_case: Arc::new(SetWifiAutoConnectUseCaseImpl::new(network_service.clone())),
+            list_static_ip_configs_use_case: list_static_ip_configs_use_case.clone(),
+            create_static_ip_config_use_case: Arc::new(CreateStaticIpConfigUseCaseImpl::new(network_service.clone())),
+            batch_create_static_ip_configs_use_case: Arc::new(BatchCreateStaticIpConfigsUseCaseImpl::new(network_service.clone())),
+            validate_static_ip_config_use_case: Arc::new(ValidateStaticIpConfigUseCaseImpl::new(network_service.clone())),
+            precheck_static_ip_config_use_case: Arc::new(PrecheckStaticIpConfigUseCaseImpl::new(network_service.clone(), ip_prober.clone())),
+            enable_static_ip_config_use_case: Arc::new(EnableStaticIpConfigUseCaseImpl::new(network_service.clone(), safe_mode.clone())),
+            reorder_static_ip_dns_use_case: Arc::new(ReorderStaticIpDnsUseCaseImpl::new(network_service.clone())),
+            replace_static_ip_dns_use_case: Arc::new(ReplaceStaticIpDnsUseCaseImpl::new(network_service.clone())),
+            confirm_static_ip_config_use_case: Arc::new(ConfirmStaticIpConfigUseCaseImpl::new(safe_mode)),
+            disable_static_ip_config_use_case: Arc::new(DisableStaticIpConfigUseCaseImpl::new(network_service.clone())),
+            delete_static_ip_config_use_case: Arc::new(DeleteStaticIpConfigUseCaseImpl::new(network_service.clone())),
+            set_interface_admin_state_use_case: Arc::new(SetInterfaceAdminStateUseCaseImpl::new(network_service.clone())),
+            get_interface_rate_use_case: Arc::new(GetInterfaceRateUseCaseImpl::new(network_service.clone())),
+            get_all_interface_stats_use_case: Arc::new(GetAllInterfaceStatsUseCaseImpl::new(network_service.clone())),
+            get_grouped_network_interfaces_use_case: Arc::new(GetGroupedNetworkInterfacesUseCaseImpl::new(network_service.clone())),
+            get_network_interface_use_case: Arc::new(GetNetworkInterfaceUseCaseImpl::new(network_service.clone())),
+            get_wifi_link_status_use_case: Arc::new(GetWifiLinkStatusUseCaseImpl::new(network_service.clone())),
+            sample_wifi_signal_use_case: Arc::new(SampleWifiSignalUseCaseImpl::new(network_service.clone(), "wlan0".to_string())),
+            get_default_route_use_case: Arc::new(GetDefaultRouteUseCaseImpl::new(network_service.clone())),
+            probe_ip_use_case: Arc::new(ProbeIpUseCaseImpl::new(ip_prober)),
+            get_dns_latency_use_case: Arc::new(GetDnsLatencyUseCaseImpl::new(network_service.clone(), Arc::new(SimulatedDnsProber))),
+            get_address_conflicts_use_case: Arc::new(GetAddressConflictsUseCaseImpl::new(network_service.clone())),
+            create_network_profile_use_case: Arc::new(CreateNetworkProfileUseCaseImpl::new(profile_service.clone())),
+            list_network_profiles_use_case: Arc::new(ListNetworkProfilesUseCaseImpl::new(profile_service.clone())),
+            activate_profile_use_case: Arc::new(ActivateProfileUseCaseImpl::new(profile_service)),
+            get_interface_overview_use_case: Arc::new(GetInterfaceOverviewUseCaseImpl::new(network_service.clone(), "wlan0".to_string())),
+            export_netplan_use_case: Arc::new(ExportNetplanUseCaseImpl::new(network_service.clone(), "wlan0".to_string())),
+            scan_wifi_networks_use_case: Arc::new(
+                ScanWifiNetworksUseCaseImpl::new(network_service.clone()).with_scan_cache(scan_cache.clone()),
+            ),
+            get_network_audit_use_case: Arc::new(GetNetworkAuditUseCaseImpl::new(
+                list_wifi_configs_use_case,
+                list_static_ip_configs_use_case,
+            )),
+            import_configs_use_case: Arc::new(ImportConfigsUseCaseImpl::new(network_service.clone())),
+            restart_networking_use_case: Arc::new(RestartNetworkingUseCaseImpl::new(
+                network_service.clone(),
+                network.applier.clone(),
+                "wlan0",
+                true,
+            )),
+            get_event_history_use_case: Arc::new(GetEventHistoryUseCaseImpl::new(event_history.clone())),
+            get_network_preferences_use_case: Arc::new(GetNetworkPreferencesUseCaseImpl::new(settings.clone())),
+            set_network_preferences_use_case: Arc::new(SetNetworkPreferencesUseCaseImpl::new(settings.clone())),
+            get_current_network_use_case: Arc::new(GetCurrentNetworkUseCaseImpl::new(network_service.clone(), settings)),
+            get_hostname_use_case: Arc::new(GetHostnameUseCaseImpl::new(hostname_service.clone())),
+            set_hostname_use_case: Arc::new(SetHostnameUseCaseImpl::new(hostname_service)),
+            get_ip_forwarding_use_case: Arc::new(GetIpForwardingUseCaseImpl::new(ip_forwarding_service.clone())),
+            set_ip_forwarding_use_case: Arc::new(SetIpForwardingUseCaseImpl::new(ip_forwarding_service)),
+            get_capabilities_use_case: Arc::new(GetCapabilitiesUseCaseImpl::new(Arc::new(SimulatedPrivilegeProbe), true)),
+            interface_monitor: Arc::new(InterfaceMonitor::new(network_service).with_event_history(event_history)),
+            log_buffer: Arc::new(LogBuffer::default()),
+            admin_allowlist: Arc::new(AdminAllowlist::parse(&[]).unwrap()),
+            admin_token: Arc::new(AdminToken::new(Some(ADMIN_TOKEN.to_string()))),
+            idempotency_cache: Arc::new(IdempotencyCache::new(std::time::Duration::from_secs(600))),
+            rate_limiter: Arc::new(RateLimiter::new(0)),
+            request_metrics: Arc::new(RequestMetrics::new()),
+            cors_origins: Vec::new(),
+            web_ui: true,
+            metrics_endpoint: true,
+            request_timeout: Some(std::time::Duration::from_secs(30)),
+            started_at: chrono::Utc::now(),
+            started: std::time::Instant::now(),
+        };
+        Self { network, service, scan_cache, state }
+    }
+
+    pub fn router(&self) -> Router {
+        create_router(self.state.clone())
+    }
+
+    pub async fn send(&self, request: Request<Body>) -> Response<Body> {
+        self.router().oneshot(request).await.unwrap()
+    }
+
+    pub async fn get(&self, uri: &str) -> Response<Body> {
+        self.send(Request::get(uri).body(Body::empty()).unwrap()).await
+    }
+
+    pub async fn post_json(&self, uri: &str, body: serde_json::Value) -> Response<Body> {
+        self.send(
+            Request::post(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+    }
+}
+
+pub async fn body_text(response: Response<Body>) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+pub async fn body_json(response: Response<Body>) -> serde_json::Value {
+    serde_json::from_str(&body_text(response).await).unwrap()
+}